@@ -0,0 +1,328 @@
+use edit_xml::{Document, Element, Node, ReadOptions};
+
+use super::{SourceEncoding, SourceSpans};
+
+/// The line ending used by a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+/// The `<?xml ... ?>` declaration written at the start of a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum XmlDeclaration {
+    /// Write `<?xml version="1.0" encoding="UTF-8"?>`
+    #[default]
+    Default,
+    /// The declaration exactly as it was in the source. Keeps the encoding and standalone attributes
+    Source(String),
+    /// The source did not have a declaration
+    None,
+}
+/// How a file was formatted when it was loaded. So writing it back only changes what was edited.
+///
+/// The whitespace between elements is kept in the document. This is used for the parts outside of the root element
+/// and to indent elements that were added after loading. The indent size is kept in the `ident_level` of the editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFormat {
+    /// Either a space or a tab
+    pub indent_char: u8,
+    pub line_ending: LineEnding,
+    pub declaration: XmlDeclaration,
+    /// Should the file end with a newline. None writes it without one
+    pub trailing_newline: Option<bool>,
+    /// Should the file start with a UTF-8 byte order mark
+    pub byte_order_mark: bool,
+}
+impl Default for SourceFormat {
+    fn default() -> Self {
+        Self {
+            indent_char: b' ',
+            line_ending: LineEnding::Lf,
+            declaration: XmlDeclaration::Default,
+            trailing_newline: None,
+            byte_order_mark: false,
+        }
+    }
+}
+impl SourceFormat {
+    /// Detects the format of the source
+    ///
+    /// ```rust
+    /// use maven_rs::editor::{LineEnding, SourceFormat, XmlDeclaration};
+    /// let source = "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\r\n<project>\r\n\t<artifactId>test</artifactId>\r\n</project>";
+    /// let format = SourceFormat::detect(source);
+    /// assert_eq!(format.indent_char, b'\t');
+    /// assert_eq!(format.line_ending, LineEnding::CrLf);
+    /// assert_eq!(
+    ///     format.declaration,
+    ///     XmlDeclaration::Source("<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>".to_owned())
+    /// );
+    /// assert_eq!(format.trailing_newline, Some(false));
+    /// assert_eq!(SourceFormat::detect_indent_size(source), Some(1));
+    /// ```
+    pub fn detect(source: &str) -> Self {
+        let line_ending = if source.contains("\r\n") {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        };
+        let start = source.trim_start_matches('\u{feff}').trim_start();
+        let declaration = match start
+            .starts_with("<?xml")
+            .then(|| start.find("?>"))
+            .flatten()
+        {
+            Some(end) => XmlDeclaration::Source(start[..end + 2].to_owned()),
+            None => XmlDeclaration::None,
+        };
+        Self {
+            indent_char: detect_indent(source).map(|(c, _)| c).unwrap_or(b' '),
+            line_ending,
+            declaration,
+            trailing_newline: Some(source.ends_with('\n')),
+            byte_order_mark: source.starts_with('\u{feff}'),
+        }
+    }
+    /// The number of [SourceFormat::indent_char] used for each level. None if nothing is indented
+    pub fn detect_indent_size(source: &str) -> Option<usize> {
+        detect_indent(source).map(|(_, size)| size)
+    }
+    /// The options to read a document with so the whitespace between elements is kept.
+    ///
+    /// The text of elements is not trimmed either. Values are trimmed when they are read with [text_value](super::utils::text_value)
+    pub fn read_options() -> ReadOptions {
+        ReadOptions {
+            require_decl: false,
+            trim_text: false,
+            ignore_whitespace_only: false,
+            ..Default::default()
+        }
+    }
+    /// Writes the document.
+    ///
+    /// Elements from the source are written with the whitespace around them and their start tag unchanged if the attributes were not changed.
+    /// Elements added after loading are indented like their siblings or one level deeper than their parent. `indent_size` is the `ident_level` of the editor
+    pub fn write_document(
+        &self,
+        document: &Document,
+        spans: &SourceSpans,
+        indent_size: usize,
+    ) -> String {
+        let mut writer = DocumentWriter {
+            document,
+            spans,
+            indent: (self.indent_char as char).to_string().repeat(indent_size),
+            result: String::new(),
+        };
+        if self.byte_order_mark {
+            writer.result.push('\u{feff}');
+        }
+        match &self.declaration {
+            XmlDeclaration::Source(declaration) => writer.result.push_str(declaration),
+            XmlDeclaration::Default => writer.result.push_str(DEFAULT_DECLARATION),
+            XmlDeclaration::None => {}
+        }
+        for node in document.root_nodes() {
+            if !writer.result.trim_start_matches('\u{feff}').is_empty() {
+                writer.result.push('\n');
+            }
+            writer.write_node(node, "");
+        }
+        self.finish(writer.result)
+    }
+    /// The bytes of a written document in the encoding of its xml declaration. UTF-8 if it does not declare one
+    pub fn encode(&self, written: &str) -> Vec<u8> {
+        SourceEncoding::detect(written.trim_start_matches('\u{feff}').as_bytes()).encode(written)
+    }
+    /// Applies the trailing newline and line endings
+    fn finish(&self, mut result: String) -> String {
+        match self.trailing_newline {
+            Some(true) => {
+                result.truncate(result.trim_end_matches(['\r', '\n']).len());
+                result.push('\n');
+            }
+            Some(false) | None => result.truncate(result.trim_end_matches(['\r', '\n']).len()),
+        }
+        match self.line_ending {
+            LineEnding::Lf => result,
+            LineEnding::CrLf => result.replace("\r\n", "\n").replace('\n', "\r\n"),
+        }
+    }
+}
+const DEFAULT_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+/// A child of an element that is not whitespace and the whitespace before it
+struct Item<'a> {
+    whitespace: Option<String>,
+    node: &'a Node,
+    added: bool,
+}
+struct DocumentWriter<'a> {
+    document: &'a Document,
+    spans: &'a SourceSpans,
+    /// One level of indentation
+    indent: String,
+    result: String,
+}
+impl DocumentWriter<'_> {
+    fn write_node(&mut self, node: &Node, indent: &str) {
+        match node {
+            Node::Element(element) => self.write_element(*element, indent),
+            Node::Text(text) => self.result.push_str(&escape(text, false)),
+            Node::Comment(comment) => {
+                self.result.push_str(&format!("<!--{comment}-->"));
+            }
+            Node::CData(data) => self.result.push_str(&format!("<![CDATA[{data}]]>")),
+            Node::PI(instruction) => self.result.push_str(&format!("<?{instruction}?>")),
+            Node::DocType(doc_type) => self.result.push_str(&format!("<!DOCTYPE {doc_type}>")),
+        }
+    }
+    fn write_element(&mut self, element: Element, indent: &str) {
+        let document = self.document;
+        let name = element.full_name(document);
+        let (start_tag, self_closing) = match self.spans.unchanged_start_tag(document, element) {
+            Some(tag) => match tag.strip_suffix("/>") {
+                Some(open) => (open.to_owned(), true),
+                None => (tag[..tag.len() - 1].to_owned(), false),
+            },
+            None => {
+                let mut tag = format!("<{name}");
+                let mut attributes: Vec<_> = element.attributes(document).iter().collect();
+                attributes.sort();
+                for (key, value) in attributes {
+                    tag.push_str(&format!(" {key}=\"{}\"", escape(value, true)));
+                }
+                (tag, true)
+            }
+        };
+        self.result.push_str(&start_tag);
+        let children = element.children(document);
+        if children.is_empty() {
+            self.result.push_str(&if self_closing {
+                "/>".to_owned()
+            } else {
+                format!("></{name}>")
+            });
+            return;
+        }
+        self.result.push('>');
+        if children
+            .iter()
+            .all(|node| matches!(node, Node::Text(_) | Node::CData(_)))
+        {
+            for child in children {
+                self.write_node(child, indent);
+            }
+        } else {
+            self.write_items(children, indent);
+        }
+        self.result.push_str(&format!("</{name}>"));
+    }
+    /// Writes the children of an element that contains more than text
+    fn write_items(&mut self, children: &[Node], indent: &str) {
+        let mut items = Vec::new();
+        let mut whitespace: Option<String> = None;
+        for node in children {
+            match node {
+                // Whitespace next to whitespace is left from a removed element. Only the last one is kept
+                Node::Text(text) if text.trim().is_empty() => whitespace = Some(text.clone()),
+                _ => items.push(Item {
+                    whitespace: whitespace.take(),
+                    node,
+                    added: matches!(node, Node::Element(element) if self.spans.get(*element).is_none()),
+                }),
+            }
+        }
+        let mut trailing = whitespace;
+        // Elements pushed to the end come after the whitespace before the end tag
+        let first_added = items
+            .iter()
+            .rposition(|item| !item.added)
+            .map_or(0, |index| index + 1);
+        if trailing.is_none()
+            && let Some(item) = items.get_mut(first_added)
+        {
+            trailing = item.whitespace.take();
+        }
+        let child_indent = items
+            .iter()
+            .filter(|item| !item.added)
+            .find_map(|item| item.whitespace.as_deref().and_then(line_indent))
+            .map_or_else(|| format!("{indent}{}", self.indent), str::to_owned);
+        let any_added = items.iter().any(|item| item.added);
+        for item in items {
+            let item_indent = if item.added {
+                self.result.push('\n');
+                self.result.push_str(&child_indent);
+                child_indent.clone()
+            } else if let Some(whitespace) = &item.whitespace {
+                self.result.push_str(whitespace);
+                line_indent(whitespace).unwrap_or(&child_indent).to_owned()
+            } else {
+                child_indent.clone()
+            };
+            self.write_node(item.node, &item_indent);
+        }
+        match trailing {
+            Some(trailing) => self.result.push_str(&trailing),
+            None if any_added => {
+                self.result.push('\n');
+                self.result.push_str(indent);
+            }
+            None => {}
+        }
+    }
+}
+/// The indentation after the last line break of the whitespace
+fn line_indent(whitespace: &str) -> Option<&str> {
+    whitespace.rfind('\n').map(|index| &whitespace[index + 1..])
+}
+fn escape(value: &str, attribute: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' if !attribute => result.push_str("&gt;"),
+            '"' if attribute => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+/// The indent character and the smallest increase of indentation between two lines that start with an element or comment
+///
+/// Uses the increase instead of the indent so a document that is indented as a whole still finds the right size
+fn detect_indent(source: &str) -> Option<(u8, usize)> {
+    let mut indent_char = None;
+    let mut smallest: Option<usize> = None;
+    let mut previous = None;
+    for line in source.lines() {
+        let content = line.trim_start_matches([' ', '\t']);
+        if !content.starts_with('<') {
+            continue;
+        }
+        let indent = &line.as_bytes()[..line.len() - content.len()];
+        let size = indent.len();
+        if let Some(c) = indent.first() {
+            indent_char.get_or_insert(*c);
+        }
+        if let Some(previous) = previous
+            && size > previous
+        {
+            let increase = size - previous;
+            smallest = Some(smallest.map_or(increase, |smallest| smallest.min(increase)));
+        }
+        previous = Some(size);
+    }
+    Some((indent_char?, smallest?))
+}
@@ -7,7 +7,7 @@ use std::{fmt::Display, path::PathBuf};
 
 use edit_xml::{Document, EditXMLError, Element};
 use thiserror::Error;
-use utils::MissingElementError;
+use utils::{MissingElementError, text_value};
 
 use crate::{
     pom::{
@@ -15,9 +15,13 @@ use crate::{
         ParentBuilderError, PluginBuilderError, RepositoryBuilderError, ScmBuilderError,
         SubRepositoryRulesBuilderError,
     },
-    settings::{MirrorBuilderError, ServerBuilderError},
+    settings::{MirrorBuilderError, ProxyBuilderError, ServerBuilderError},
 };
+mod format;
+mod span;
 pub mod utils;
+pub use format::*;
+pub use span::*;
 
 #[derive(Debug, Error)]
 pub enum XMLEditorError {
@@ -37,6 +41,30 @@ pub enum XMLEditorError {
         pom_type: &'static str,
         error: String,
     },
+    /// An error while reading an element and where the element is in the source file
+    #[error("{position}: {element} {error}")]
+    Located {
+        position: SourcePosition,
+        /// The name of the element that could not be read
+        element: String,
+        error: Box<XMLEditorError>,
+    },
+}
+impl XMLEditorError {
+    /// The position in the source file if the error has one
+    pub fn position(&self) -> Option<SourcePosition> {
+        match self {
+            XMLEditorError::Located { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+    /// The error without the position
+    pub fn without_position(&self) -> &XMLEditorError {
+        match self {
+            XMLEditorError::Located { error, .. } => error.without_position(),
+            other => other,
+        }
+    }
 }
 macro_rules! builder_err {
     ($error_type:ident, $pom_type:literal) => {
@@ -44,7 +72,9 @@ macro_rules! builder_err {
             fn from(value: $error_type) -> Self {
                 match value {
                     $error_type::UninitializedField(missing_field) => {
-                        XMLEditorError::MissingElement(MissingElementError(missing_field))
+                        XMLEditorError::MissingElement(MissingElementError(
+                            utils::element_name_of_field(missing_field),
+                        ))
                     }
                     $error_type::ValidationError(other) => XMLEditorError::ValidationError {
                         pom_type: $pom_type,
@@ -72,6 +102,7 @@ builder_err![
     (ParentBuilderError, "Parent"),
     (ServerBuilderError, "Server"),
     (MirrorBuilderError, "Mirror"),
+    (ProxyBuilderError, "Proxy"),
     (SubRepositoryRulesBuilderError, "SubRepositoryRules"),
     (RepositoryBuilderError, "Repository"),
     (ScmBuilderError, "Scm"),
@@ -174,7 +205,7 @@ pub trait PomValue: Sized {
     where
         Self: Sized,
     {
-        let value = element.text_content(document);
+        let value = text_value(element, document);
         Self::from_str_for_editor(&value).map_err(|e| e.into())
     }
 }
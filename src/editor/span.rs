@@ -0,0 +1,342 @@
+use std::{collections::HashMap, fmt, ops::Deref};
+
+use edit_xml::{Document, Element};
+use serde::Serialize;
+
+use super::XMLEditorError;
+
+/// A position in the source file. `line` and `column` start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct SourcePosition {
+    pub line: usize,
+    /// Counted in characters
+    pub column: usize,
+    /// The byte offset from the start of the file
+    pub offset: usize,
+}
+impl fmt::Display for SourcePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+/// Where an element is in the source file.
+///
+/// `start` is the `<` of the start tag and `end` is right after the `>` of the end tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct SourceSpan {
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+}
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.start.fmt(f)
+    }
+}
+/// A value read from an element and where the element was in the source file.
+///
+/// The span is None if the element was added after the file was loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Option<SourceSpan>,
+}
+impl<T> Spanned<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+/// The spans of the elements of a document when it was loaded.
+///
+/// edit-xml does not keep positions so the source is scanned for the tags and matched with the elements in document order.
+#[derive(Debug, Clone, Default)]
+pub struct SourceSpans {
+    spans: HashMap<Element, SourceSpan>,
+    start_tags: HashMap<Element, SourceStartTag>,
+}
+/// The start tag as it was written in the source and the attributes it had
+#[derive(Debug, Clone)]
+struct SourceStartTag {
+    text: String,
+    attributes: HashMap<String, String>,
+}
+impl SourceSpans {
+    /// `source` must be the bytes `document` was parsed from.
+    ///
+    /// The positions are found in the bytes so they are right for any encoding that is compatible with ASCII.
+    /// Columns are counted in characters of the encoding in the xml declaration
+    pub fn new(source: &[u8], document: &Document) -> Self {
+        let mut elements = Vec::new();
+        if let Some(root) = document.root_element() {
+            collect_elements(document, root, &mut elements);
+        }
+        let encoding = SourceEncoding::detect(source);
+        let spans: HashMap<_, _> = elements
+            .into_iter()
+            .zip(scan_tags(source, encoding))
+            .collect();
+        let start_tags = spans
+            .iter()
+            .filter_map(|(element, span)| {
+                let end = start_tag_end(source, span.start.offset)?;
+                let start_tag = SourceStartTag {
+                    text: encoding.decode(&source[span.start.offset..end]),
+                    attributes: element.attributes(document).clone(),
+                };
+                Some((*element, start_tag))
+            })
+            .collect();
+        Self { spans, start_tags }
+    }
+    pub fn get(&self, element: Element) -> Option<SourceSpan> {
+        self.spans.get(&element).copied()
+    }
+    /// The start tag of `element` as it was in the source. None if the element is new or its attributes were changed
+    pub(crate) fn unchanged_start_tag(
+        &self,
+        document: &Document,
+        element: Element,
+    ) -> Option<&str> {
+        self.start_tags
+            .get(&element)
+            .filter(|tag| tag.attributes == *element.attributes(document))
+            .map(|tag| tag.text.as_str())
+    }
+    /// Adds the position and name of `element` to the error. Errors that already have a position are not changed
+    pub fn locate(
+        &self,
+        document: &Document,
+        element: Element,
+        error: XMLEditorError,
+    ) -> XMLEditorError {
+        match (self.get(element), error) {
+            (_, error @ XMLEditorError::Located { .. }) | (None, error) => error,
+            (Some(span), error) => XMLEditorError::Located {
+                position: span.start,
+                element: element.name(document).to_owned(),
+                error: Box::new(error),
+            },
+        }
+    }
+    /// Wraps the value with the span of `element`
+    pub fn spanned<T>(&self, element: Element, value: T) -> Spanned<T> {
+        Spanned {
+            value,
+            span: self.get(element),
+        }
+    }
+}
+fn collect_elements(document: &Document, element: Element, result: &mut Vec<Element>) {
+    result.push(element);
+    for child in element.child_elements(document) {
+        collect_elements(document, child, result);
+    }
+}
+/// How the characters of the source are stored in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceEncoding {
+    Utf8,
+    /// ISO-8859-1, windows-1252, US-ASCII and the other encodings with one byte for each character
+    SingleByte,
+}
+impl SourceEncoding {
+    /// Uses the encoding of the xml declaration. UTF-8 if there is none
+    pub(crate) fn detect(source: &[u8]) -> Self {
+        let declaration = source
+            .strip_prefix(b"<?xml")
+            .and_then(|rest| find(rest, 0, b"?>").map(|end| &rest[..end]))
+            .map(|declaration| String::from_utf8_lossy(declaration).to_ascii_lowercase());
+        let encoding = declaration.as_deref().and_then(|declaration| {
+            let value = declaration.split_once("encoding")?.1.trim_start();
+            let value = value.strip_prefix('=')?.trim_start();
+            let quote = value.chars().next()?;
+            value[1..].split(quote).next()
+        });
+        match encoding {
+            None | Some("utf-8" | "utf8") => SourceEncoding::Utf8,
+            Some(_) => SourceEncoding::SingleByte,
+        }
+    }
+    fn characters(&self, bytes: &[u8]) -> usize {
+        match self {
+            SourceEncoding::Utf8 => bytes.iter().filter(|byte| **byte & 0xC0 != 0x80).count(),
+            SourceEncoding::SingleByte => bytes.len(),
+        }
+    }
+    fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            SourceEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            SourceEncoding::SingleByte => bytes.iter().map(|byte| *byte as char).collect(),
+        }
+    }
+    /// The bytes of `text` in this encoding. Characters that do not fit in one byte are written as character references
+    pub(crate) fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            SourceEncoding::Utf8 => text.as_bytes().to_vec(),
+            SourceEncoding::SingleByte => {
+                let mut bytes = Vec::with_capacity(text.len());
+                for character in text.chars() {
+                    match u8::try_from(character) {
+                        Ok(byte) => bytes.push(byte),
+                        Err(_) => bytes.extend(format!("&#x{:X};", character as u32).bytes()),
+                    }
+                }
+                bytes
+            }
+        }
+    }
+}
+/// The offset of the first `pattern` in `source` at or after `from`
+fn find(source: &[u8], from: usize, pattern: &[u8]) -> Option<usize> {
+    source[from..]
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|index| from + index)
+}
+/// Converts byte offsets into lines and columns. Offsets must be given in increasing order
+struct LineTracker<'a> {
+    source: &'a [u8],
+    encoding: SourceEncoding,
+    line: usize,
+    line_start: usize,
+    offset: usize,
+}
+impl LineTracker<'_> {
+    fn position(&mut self, offset: usize) -> SourcePosition {
+        for (index, byte) in self.source[self.offset..offset].iter().enumerate() {
+            if *byte == b'\n' {
+                self.line += 1;
+                self.line_start = self.offset + index + 1;
+            }
+        }
+        self.offset = offset;
+        SourcePosition {
+            line: self.line,
+            column: self
+                .encoding
+                .characters(&self.source[self.line_start..offset])
+                + 1,
+            offset,
+        }
+    }
+}
+/// The spans of every element in the order of their start tags
+fn scan_tags(source: &[u8], encoding: SourceEncoding) -> Vec<SourceSpan> {
+    let mut lines = LineTracker {
+        source,
+        encoding,
+        line: 1,
+        line_start: 0,
+        offset: 0,
+    };
+    let find_from = |from: usize, pattern: &[u8]| {
+        find(source, from, pattern).map(|index| index + pattern.len())
+    };
+    let mut spans: Vec<SourceSpan> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    let mut index = 0;
+    while let Some(start) = find(source, index, b"<") {
+        let rest = &source[start..];
+        let end = if rest.starts_with(b"<!--") {
+            find_from(start, b"-->")
+        } else if rest.starts_with(b"<![CDATA[") {
+            find_from(start, b"]]>")
+        } else if rest.starts_with(b"<?") {
+            find_from(start, b"?>")
+        } else if rest.starts_with(b"<!") {
+            // A doctype may contain an internal subset with its own tags
+            match rest.iter().position(|byte| matches!(byte, b'[' | b'>')) {
+                Some(found) if rest[found] == b'[' => {
+                    find_from(start + found, b"]").and_then(|after| find_from(after, b">"))
+                }
+                Some(found) => Some(start + found + 1),
+                None => None,
+            }
+        } else if rest.starts_with(b"</") {
+            let end = find_from(start, b">");
+            if let (Some(end), Some(element)) = (end, open.pop()) {
+                spans[element].end = lines.position(end);
+            }
+            end
+        } else {
+            let end = start_tag_end(source, start);
+            if let Some(end) = end {
+                let position = lines.position(start);
+                let self_closing = source[end - 2] == b'/';
+                spans.push(SourceSpan {
+                    start: position,
+                    end: position,
+                });
+                if self_closing {
+                    spans.last_mut().unwrap().end = lines.position(end);
+                } else {
+                    open.push(spans.len() - 1);
+                }
+            }
+            end
+        };
+        let Some(end) = end else {
+            break;
+        };
+        index = end;
+    }
+    spans
+}
+/// The offset after the `>` of a start tag. Skips `>` inside of attribute values
+fn start_tag_end(source: &[u8], start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (index, byte) in source[start..].iter().enumerate() {
+        match (quote, byte) {
+            (None, b'"' | b'\'') => quote = Some(*byte),
+            (Some(open), _) if open == *byte => quote = None,
+            (None, b'>') => return Some(start + index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn spans() {
+        let source = "<?xml version=\"1.0\"?>\n<!-- <fake> -->\n<project a=\"x>y\">\n  <name>é<![CDATA[<b>]]></name><empty/>\n</project>\n";
+        let document = Document::parse_str(source).unwrap();
+        let spans = SourceSpans::new(source.as_bytes(), &document);
+        let root = document.root_element().unwrap();
+        let project = spans.get(root).unwrap();
+        assert_eq!((project.start.line, project.start.column), (3, 1));
+        assert_eq!((project.end.line, project.end.column), (5, 11));
+
+        let name = spans.get(root.find(&document, "name").unwrap()).unwrap();
+        assert_eq!(name.to_string(), "4:3");
+        assert_eq!(
+            &source[name.start.offset..name.end.offset],
+            "<name>é<![CDATA[<b>]]></name>"
+        );
+        let empty = spans.get(root.find(&document, "empty").unwrap()).unwrap();
+        assert_eq!(empty.start.column, 32);
+        assert_eq!(&source[empty.start.offset..empty.end.offset], "<empty/>");
+    }
+    #[test]
+    fn single_byte_encoding() {
+        let source = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<project>\n  <name>\xe9\xe9</name><empty/>\n</project>\n";
+        let document =
+            Document::parse_str(&source.iter().map(|byte| *byte as char).collect::<String>())
+                .unwrap();
+        let spans = SourceSpans::new(source, &document);
+        let root = document.root_element().unwrap();
+        let empty = spans.get(root.find(&document, "empty").unwrap()).unwrap();
+        assert_eq!((empty.start.line, empty.start.column), (3, 18));
+        assert_eq!(&source[empty.start.offset..empty.end.offset], b"<empty/>");
+    }
+}
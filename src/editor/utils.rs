@@ -1,13 +1,35 @@
 use super::{
-    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, PomValue,
+    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, PomValue, SourceSpans,
     UpdatableElement, XMLEditorError,
 };
 use edit_xml::{Document, Element};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
-#[error("Missing Element {0}")]
+#[error("missing {0}")]
 pub struct MissingElementError(pub &'static str);
+/// The xml name of a required field of a builder. The other required fields have the same name in Rust and xml
+pub(crate) fn element_name_of_field(field: &'static str) -> &'static str {
+    match field {
+        "group_id" => "groupId",
+        "artifact_id" => "artifactId",
+        "mirror_of" => "mirrorOf",
+        other => other,
+    }
+}
+
+/// The text of an element without the whitespace around it.
+///
+/// Documents loaded with [SourceFormat::read_options](super::SourceFormat::read_options) keep that whitespace so it can be written back unchanged
+pub fn text_value(element: Element, document: &Document) -> String {
+    let text = element.text_content(document);
+    let trimmed = text.trim();
+    if trimmed.len() == text.len() {
+        text
+    } else {
+        trimmed.to_owned()
+    }
+}
 
 pub fn find_element_or_err(
     element: Element,
@@ -25,7 +47,7 @@ pub fn find_to_string_or_none(
 ) -> Option<String> {
     element
         .find(document, name)
-        .map(|x| x.text_content(document))
+        .map(|x| text_value(x, document))
 }
 pub fn create_basic_text_element(
     document: &mut Document,
@@ -130,11 +152,80 @@ where
     parent_container.push_child(document, value)?;
     Ok(None)
 }
+/// Removes the first item in the parent element that [ComparableElement::is_same_item] matches.
+///
+/// Returns the removed item. If no item matched, None is returned.
+pub(crate) fn remove_item<I>(
+    document: &mut Document,
+    parent_element: Option<Element>,
+    item: &I,
+) -> Result<Option<I>, XMLEditorError>
+where
+    I: ElementConverter + HasElementName + ComparableElement,
+{
+    let Some((current_value, element)) = find_item(document, parent_element, item)? else {
+        return Ok(None);
+    };
+    element.detach(document)?;
+    Ok(Some(current_value))
+}
+/// Finds the first item in the parent element that [ComparableElement::is_same_item] matches.
+///
+/// Returns the item and the element it was read from.
+pub(crate) fn find_item<I>(
+    document: &Document,
+    parent_element: Option<Element>,
+    item: &I,
+) -> Result<Option<(I, Element)>, XMLEditorError>
+where
+    I: ElementConverter + HasElementName + ComparableElement,
+{
+    let Some(parent_container) = parent_element else {
+        return Ok(None);
+    };
+    let elements_in_parent = get_all_children_of_element::<I>(document, parent_container)?;
+    Ok(elements_in_parent
+        .into_iter()
+        .find(|(current_value, _)| current_value.is_same_item(item)))
+}
+/// Modifies the first item in the parent element that [ComparableElement::is_same_item] matches.
+///
+/// The element is updated in place with [UpdatableElement::update_element]. So it keeps its position and comments.
+///
+/// Returns the modified item. If no item matched, None is returned.
+pub(crate) fn modify_item<I, F>(
+    document: &mut Document,
+    parent_element: Option<Element>,
+    item: &I,
+    modify: F,
+) -> Result<Option<I>, XMLEditorError>
+where
+    I: UpdatableElement + HasElementName + ComparableElement,
+    F: FnOnce(&mut I),
+{
+    let Some((mut current_value, element)) = find_item(document, parent_element, item)? else {
+        return Ok(None);
+    };
+    modify(&mut current_value);
+    current_value.update_element(element, document)?;
+    Ok(Some(current_value))
+}
 /// Gets all children of an element and converts them to a specific type.
 pub(crate) fn get_all_children_of_element<E>(
     document: &Document,
     element: Element,
 ) -> Result<Vec<(E, Element)>, XMLEditorError>
+where
+    E: ElementConverter + HasElementName,
+{
+    get_all_children_of_element_located(document, element, &SourceSpans::default())
+}
+/// Same as [get_all_children_of_element] but errors contain the position of the child that failed to convert
+pub(crate) fn get_all_children_of_element_located<E>(
+    document: &Document,
+    element: Element,
+    spans: &SourceSpans,
+) -> Result<Vec<(E, Element)>, XMLEditorError>
 where
     E: ElementConverter + HasElementName,
 {
@@ -143,12 +234,17 @@ where
     for raw_element in element.child_elements(document) {
         let element_name = raw_element.name(document);
         if element_name != E::element_name() {
-            return Err(XMLEditorError::UnexpectedElementType {
-                expected: E::element_name(),
-                found: element_name.to_owned(),
-            });
+            return Err(spans.locate(
+                document,
+                raw_element,
+                XMLEditorError::UnexpectedElementType {
+                    expected: E::element_name(),
+                    found: element_name.to_owned(),
+                },
+            ));
         }
-        let value = E::from_element(raw_element, document)?;
+        let value = E::from_element(raw_element, document)
+            .map_err(|error| spans.locate(document, raw_element, error))?;
         result.push((value, raw_element));
     }
     Ok(result)
@@ -188,7 +284,7 @@ macro_rules! from_element_using_builder {
             match child.name(document) {
                     $(
                     $name => {
-                        builder.$set_func(child.text_content(document));
+                        builder.$set_func($crate::editor::utils::text_value(child, document));
                     }
                     )*
                     _ => {}
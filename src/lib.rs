@@ -24,4 +24,10 @@ pub enum Error {
     InvalidFileExtension,
     #[error("No Home Directory Found")]
     NoHomeDirectory,
+    #[error("The specified {kind} settings file does not exist: {}", .path.display())]
+    MissingSettingsFile {
+        /// `user` or `global`
+        kind: &'static str,
+        path: std::path::PathBuf,
+    },
 }
@@ -0,0 +1,477 @@
+//! Metadata Editor
+//!
+//! [MetadataEditor] is to `maven-metadata.xml` what [PomEditor](crate::pom::editor::PomEditor) is to pom files.
+//!
+//! [DeployMetadata](crate::meta::DeployMetadata) and [SnapshotMetadata](crate::meta::SnapshotMetadata) are read with Serde.
+//! Writing them back with Serde reorders elements and drops anything they do not know about.
+//! [MetadataEditor] edits the dom in place. So comments, unknown elements and ordering are kept.
+use std::io::Write;
+
+use chrono::NaiveDateTime;
+use edit_xml::{Document, Element, ReadOptions, WriteOptions};
+
+use super::{MetadataVersion, Snapshot, SnapshotVersion, StableVersioning, parse_standard_time};
+use crate::{
+    editor::{
+        ElementConverter, UpdatableElement, XMLEditorError,
+        utils::{
+            MissingElementError, add_or_update_item, get_all_children_of_element,
+            get_or_create_top_level_element, remove_item, sync_element,
+        },
+    },
+    utils::time::standard_time,
+};
+
+/// A struct that allows editing and creating `maven-metadata.xml` files
+///
+/// Works for both the artifact level metadata (list of versions) and the snapshot version level metadata.
+#[derive(Debug)]
+pub struct MetadataEditor {
+    /// The root element must always be a metadata element.
+    document: Document,
+    pub ident_level: usize,
+}
+impl Default for MetadataEditor {
+    fn default() -> Self {
+        let document = Document::new_with_root("metadata", |metadata| metadata);
+        Self {
+            document,
+            ident_level: 2,
+        }
+    }
+}
+macro_rules! versioning_getter_setter {
+    (
+        $(#[$docs:meta])*
+        $name:literal {
+            set: $set:ident,
+            get: $get:ident,
+        }
+    ) => {
+        $(#[$docs])*
+        ///
+        /// If [None] is passed in. The element will be removed
+        pub fn $set<S, O>(&mut self, value: O)
+        where
+            S: Into<String>,
+            O: Into<Option<S>>,
+        {
+            let value: Option<S> = value.into();
+            let versioning = self.versioning_element();
+            sync_element(&mut self.document, versioning, $name, value);
+        }
+        $(#[$docs])*
+        pub fn $get(&self) -> Option<String> {
+            let versioning = self.root().find(&self.document, "versioning")?;
+            versioning
+                .find(&self.document, $name)
+                .map(|x| x.text_content(&self.document))
+        }
+    };
+}
+macro_rules! root_getter_setter {
+    (
+        $(#[$docs:meta])*
+        $name:literal {
+            set: $set:ident,
+            get: $get:ident,
+        }
+    ) => {
+        $(#[$docs])*
+        pub fn $set(&mut self, value: impl Into<String>) {
+            let root = self.root();
+            crate::editor::utils::find_or_create_then_set_text_content(
+                &mut self.document,
+                root,
+                $name,
+                value,
+            );
+        }
+        $(#[$docs])*
+        pub fn $get(&self) -> Option<String> {
+            self.root()
+                .find(&self.document, $name)
+                .map(|x| x.text_content(&self.document))
+        }
+    };
+}
+impl MetadataEditor {
+    /// Creates a new artifact level metadata file
+    pub fn new(group_id: &str, artifact_id: &str) -> Self {
+        let mut editor = Self::default();
+        editor.set_group_id(group_id);
+        editor.set_artifact_id(artifact_id);
+        editor
+    }
+    /// Creates a new snapshot version level metadata file
+    pub fn new_for_snapshot(group_id: &str, artifact_id: &str, version: &str) -> Self {
+        let mut editor = Self::new(group_id, artifact_id);
+        let root = editor.root();
+        root.set_attribute(&mut editor.document, "modelVersion", "1.1.0");
+        editor.set_version(version);
+        editor
+    }
+    root_getter_setter!(
+        /// The group id of the metadata
+        "groupId" {
+            set: set_group_id,
+            get: get_group_id,
+        }
+    );
+    root_getter_setter!(
+        /// The artifact id of the metadata
+        "artifactId" {
+            set: set_artifact_id,
+            get: get_artifact_id,
+        }
+    );
+    root_getter_setter!(
+        /// The version. Only present in snapshot version level metadata
+        "version" {
+            set: set_version,
+            get: get_version,
+        }
+    );
+    versioning_getter_setter!(
+        /// The latest version of the artifact
+        "latest" {
+            set: set_latest,
+            get: get_latest,
+        }
+    );
+    versioning_getter_setter!(
+        /// The latest release version of the artifact
+        "release" {
+            set: set_release,
+            get: get_release,
+        }
+    );
+    /// Gets the entire versioning element
+    pub fn get_versioning(&self) -> Result<Option<StableVersioning>, XMLEditorError> {
+        self.root()
+            .find(&self.document, "versioning")
+            .map(|x| StableVersioning::from_element(x, &self.document))
+            .transpose()
+    }
+    /// Sets the entire versioning element.
+    ///
+    /// Existing versions stay where they are. See [StableVersioning::update_element]
+    pub fn set_versioning(&mut self, versioning: StableVersioning) -> Result<(), XMLEditorError> {
+        let versioning_element = self.versioning_element();
+        versioning.update_element(versioning_element, &mut self.document)
+    }
+    /// Gets all the versions in the order they appear in the file
+    pub fn get_versions(&self) -> Result<Vec<String>, XMLEditorError> {
+        let Some(versions) = self.versions_element() else {
+            return Ok(vec![]);
+        };
+        let result = get_all_children_of_element::<MetadataVersion>(&self.document, versions)?;
+        Ok(result.into_iter().map(|(version, _)| version.0).collect())
+    }
+    /// Adds a version to the end of the version list.
+    ///
+    /// Returns false if the version was already present.
+    ///
+    /// ```rust
+    /// use maven_rs::meta::editor::MetadataEditor;
+    /// let mut editor = MetadataEditor::new("dev.wyatt-herkamp", "test");
+    /// assert!(editor.add_version("1.0.0").unwrap());
+    /// assert!(!editor.add_version("1.0.0").unwrap());
+    /// assert_eq!(editor.get_versions().unwrap(), vec!["1.0.0".to_string()]);
+    /// ```
+    pub fn add_version(&mut self, version: impl Into<String>) -> Result<bool, XMLEditorError> {
+        let versioning = self.versioning_element();
+        let versions = self.versions_element();
+        let existing = add_or_update_item(
+            &mut self.document,
+            versions,
+            versioning,
+            MetadataVersion(version.into()),
+        )?;
+        Ok(existing.is_none())
+    }
+    /// Removes a version from the version list.
+    ///
+    /// Latest and release are not changed.
+    ///
+    /// Returns false if the version was not present.
+    pub fn remove_version(&mut self, version: &str) -> Result<bool, XMLEditorError> {
+        let versions = self.versions_element();
+        let removed = remove_item(
+            &mut self.document,
+            versions,
+            &MetadataVersion::from(version),
+        )?;
+        Ok(removed.is_some())
+    }
+    /// Gets the last updated time of the metadata
+    pub fn get_last_updated(&self) -> Result<Option<NaiveDateTime>, XMLEditorError> {
+        let Some(versioning) = self.root().find(&self.document, "versioning") else {
+            return Ok(None);
+        };
+        let Some(last_updated) = versioning.find(&self.document, "lastUpdated") else {
+            return Ok(None);
+        };
+        let value = last_updated.text_content(&self.document);
+        Ok(Some(parse_standard_time(&value)?))
+    }
+    /// Sets the last updated time of the metadata
+    pub fn set_last_updated(&mut self, last_updated: NaiveDateTime) {
+        let versioning = self.versioning_element();
+        sync_element(
+            &mut self.document,
+            versioning,
+            "lastUpdated",
+            Some(standard_time::format(&last_updated)),
+        );
+    }
+    /// Sets the last updated time to the current UTC time
+    pub fn touch_last_updated(&mut self) {
+        self.set_last_updated(chrono::Utc::now().naive_utc());
+    }
+    /// Gets the snapshot element of snapshot version level metadata
+    pub fn get_snapshot(&self) -> Result<Option<Snapshot>, XMLEditorError> {
+        let Some(versioning) = self.root().find(&self.document, "versioning") else {
+            return Ok(None);
+        };
+        versioning
+            .find(&self.document, "snapshot")
+            .map(|x| Snapshot::from_element(x, &self.document))
+            .transpose()
+    }
+    /// Sets the snapshot element of snapshot version level metadata
+    pub fn set_snapshot(&mut self, snapshot: Snapshot) -> Result<(), XMLEditorError> {
+        let versioning = self.versioning_element();
+        if let Some(element) = versioning.find(&self.document, "snapshot") {
+            return snapshot.update_element(element, &mut self.document);
+        }
+        let element = snapshot.into_element(&mut self.document)?;
+        versioning.push_child(&mut self.document, element)?;
+        Ok(())
+    }
+    /// Gets all the snapshot versions of snapshot version level metadata
+    pub fn get_snapshot_versions(&self) -> Result<Vec<SnapshotVersion>, XMLEditorError> {
+        let Some(snapshot_versions) = self.snapshot_versions_element() else {
+            return Ok(vec![]);
+        };
+        let result =
+            get_all_children_of_element::<SnapshotVersion>(&self.document, snapshot_versions)?;
+        Ok(result.into_iter().map(|(version, _)| version).collect())
+    }
+    /// Adds or updates the snapshot version with the same classifier and extension
+    pub fn add_or_update_snapshot_version(
+        &mut self,
+        snapshot_version: SnapshotVersion,
+    ) -> Result<Option<SnapshotVersion>, XMLEditorError> {
+        let versioning = self.versioning_element();
+        let snapshot_versions = self.snapshot_versions_element();
+        add_or_update_item(
+            &mut self.document,
+            snapshot_versions,
+            versioning,
+            snapshot_version,
+        )
+    }
+
+    /// Loads metadata from a string
+    pub fn load_from_str(value: &str) -> Result<Self, XMLEditorError> {
+        let document = Document::parse_str_with_opts(
+            value,
+            ReadOptions {
+                require_decl: false,
+                ..Default::default()
+            },
+        )?;
+        Self::assert_requirements_for_metadata(&document)?;
+        Ok(Self {
+            document,
+            ident_level: 2,
+        })
+    }
+    /// Loads metadata from a reader
+    ///
+    /// # Errors
+    /// If the xml is not a valid metadata file
+    pub fn load_from_reader<R: std::io::Read>(reader: R) -> Result<Self, XMLEditorError> {
+        let document = Document::parse_reader_with_opts(
+            reader,
+            ReadOptions {
+                require_decl: false,
+                ..Default::default()
+            },
+        )?;
+        Self::assert_requirements_for_metadata(&document)?;
+        Ok(Self {
+            document,
+            ident_level: 2,
+        })
+    }
+    /// Asserts that the document has a root element of metadata
+    fn assert_requirements_for_metadata(document: &Document) -> Result<(), XMLEditorError> {
+        let root = document
+            .root_element()
+            .ok_or(MissingElementError("metadata"))?;
+        if root.name(document) != "metadata" {
+            return Err(XMLEditorError::UnexpectedElementType {
+                expected: "metadata",
+                found: root.name(document).to_owned(),
+            });
+        }
+        Ok(())
+    }
+    fn root(&self) -> Element {
+        self.document.root_element().unwrap()
+    }
+    fn versioning_element(&mut self) -> Element {
+        let root = self.root();
+        get_or_create_top_level_element("versioning", &mut self.document, root)
+    }
+    fn versions_element(&self) -> Option<Element> {
+        self.root()
+            .find(&self.document, "versioning")?
+            .find(&self.document, "versions")
+    }
+    fn snapshot_versions_element(&self) -> Option<Element> {
+        self.root()
+            .find(&self.document, "versioning")?
+            .find(&self.document, "snapshotVersions")
+    }
+    pub fn write_to_str(&self) -> Result<String, XMLEditorError> {
+        self.document
+            .write_str_with_opts(WriteOptions {
+                write_decl: true,
+                indent_size: self.ident_level,
+                ..Default::default()
+            })
+            .map_err(XMLEditorError::from)
+    }
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), XMLEditorError> {
+        self.document
+            .write_with_opts(
+                writer,
+                WriteOptions {
+                    write_decl: true,
+                    indent_size: self.ident_level,
+                    ..Default::default()
+                },
+            )
+            .map_err(XMLEditorError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::meta::DeployMetadata;
+
+    #[test]
+    pub fn create_and_read_with_serde() -> anyhow::Result<()> {
+        let mut editor = MetadataEditor::new("dev.wyatt-herkamp", "test");
+        editor.add_version("1.0.0")?;
+        editor.add_version("1.1.0")?;
+        editor.set_latest("1.1.0");
+        editor.set_release("1.1.0");
+        let last_updated = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        editor.set_last_updated(last_updated);
+        let value = editor.write_to_str()?;
+        println!("{}", value);
+
+        let metadata: DeployMetadata = quick_xml::de::from_str(&value)?;
+        assert_eq!(metadata.group_id, "dev.wyatt-herkamp");
+        assert_eq!(metadata.versioning.latest, Some("1.1.0".to_string()));
+        assert_eq!(metadata.versioning.release, Some("1.1.0".to_string()));
+        assert_eq!(metadata.versioning.last_updated, Some(last_updated));
+        assert_eq!(
+            metadata.versioning.versions.version,
+            vec!["1.0.0".to_string(), "1.1.0".to_string()]
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn keeps_comments_and_unknown_elements() -> anyhow::Result<()> {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <!-- Managed by hand -->
+  <groupId>org.kakara</groupId>
+  <artifactId>engine</artifactId>
+  <versioning>
+    <latest>1.0</latest>
+    <versions>
+      <version>1.0</version>
+      <!-- Broken Build -->
+      <version>1.1</version>
+    </versions>
+    <lastUpdated>20220826191631</lastUpdated>
+  </versioning>
+  <plugins/>
+</metadata>"#;
+        let mut editor = MetadataEditor::load_from_str(xml)?;
+        assert!(editor.remove_version("1.1")?);
+        assert!(!editor.remove_version("1.1")?);
+        editor.add_version("1.2")?;
+        editor.set_latest("1.2");
+        let value = editor.write_to_str()?;
+        println!("{}", value);
+        assert!(value.contains("<!-- Managed by hand -->"));
+        assert!(value.contains("<!-- Broken Build -->"));
+        assert!(value.contains("<plugins/>"));
+        assert_eq!(editor.get_versions()?, vec!["1.0", "1.2"]);
+        assert_eq!(editor.get_latest(), Some("1.2".to_string()));
+        assert_eq!(
+            editor
+                .get_last_updated()?
+                .map(|x| standard_time::format(&x)),
+            Some("20220826191631".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn snapshot_versions() -> anyhow::Result<()> {
+        let mut editor = MetadataEditor::new_for_snapshot("org.kakara", "engine", "1.0-SNAPSHOT");
+        let timestamp = NaiveDate::from_ymd_opt(2022, 9, 14)
+            .unwrap()
+            .and_hms_opt(17, 4, 40)
+            .unwrap();
+        editor.set_snapshot(Snapshot {
+            timestamp: Some(timestamp),
+            build_number: "5".to_string(),
+        })?;
+        editor.set_last_updated(timestamp);
+        let jar = SnapshotVersion {
+            classifier: None,
+            extension: "jar".to_string(),
+            value: "1.0-20220914.170440-5".to_string(),
+            updated: Some(timestamp),
+        };
+        assert!(
+            editor
+                .add_or_update_snapshot_version(jar.clone())?
+                .is_none()
+        );
+        let updated_jar = SnapshotVersion {
+            value: "1.0-20220914.170440-6".to_string(),
+            ..jar.clone()
+        };
+        assert_eq!(
+            editor.add_or_update_snapshot_version(updated_jar.clone())?,
+            Some(jar)
+        );
+        assert_eq!(editor.get_snapshot_versions()?, vec![updated_jar]);
+        let value = editor.write_to_str()?;
+        println!("{}", value);
+        let metadata: crate::meta::SnapshotMetadata = quick_xml::de::from_str(&value)?;
+        assert_eq!(
+            metadata.get_latest_artifact_name("jar"),
+            Some("engine-1.0-20220914.170440-6.jar".to_string())
+        );
+        Ok(())
+    }
+}
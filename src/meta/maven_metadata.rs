@@ -1,7 +1,20 @@
 use chrono::NaiveDateTime;
+use edit_xml::{Document, Element};
 use serde::{Deserialize, Serialize};
 
-use crate::extension::MavenFileExtension;
+use crate::{
+    editor::{
+        ChildOfListElement, ComparableElement, ElementConverter, HasElementName, InvalidValueError,
+        UpdatableElement, XMLEditorError,
+        utils::{
+            create_basic_text_element, get_all_children_of_element,
+            get_or_create_top_level_element, sync_element,
+        },
+    },
+    extension::MavenFileExtension,
+    types::{MavenVersion, VersionRange},
+    utils::time::standard_time,
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DeployMetadata {
@@ -15,14 +28,79 @@ pub struct DeployMetadata {
 impl DeployMetadata {
     /// Attempts to pull latest
     /// Then attempts to pull release
-    /// Then attempts te first version in the list
+    /// Then attempts the newest version in the list
     #[inline]
     pub fn get_latest_version(&self) -> Option<&String> {
         self.versioning
             .latest
             .as_ref()
             .or(self.versioning.release.as_ref())
-            .or_else(|| self.versioning.versions.version.first())
+            .or_else(|| self.newest_version_by(|_| true))
+    }
+    /// Computes the newest release from the versions list using Maven version ordering.
+    ///
+    /// Ignores `<latest>` and `<release>`. Snapshots are never included.
+    ///
+    /// If `skip_pre_releases` is true versions such as `2.0-rc1` or `2.0-beta` are skipped.
+    pub fn newest_release(&self, skip_pre_releases: bool) -> Option<&str> {
+        self.newest_version_by(|version| {
+            if version.is_snapshot() {
+                return false;
+            }
+            !skip_pre_releases || !version.is_pre_release()
+        })
+        .map(String::as_str)
+    }
+    /// Computes the newest snapshot from the versions list using Maven version ordering.
+    pub fn newest_snapshot(&self) -> Option<&str> {
+        self.newest_version_by(MavenVersion::is_snapshot)
+            .map(String::as_str)
+    }
+    /// Computes the newest version that matches the predicate
+    pub fn newest_matching<F>(&self, predicate: F) -> Option<&str>
+    where
+        F: Fn(&MavenVersion) -> bool,
+    {
+        self.newest_version_by(predicate).map(String::as_str)
+    }
+    /// Computes the newest version within the range
+    pub fn newest_in_range(&self, range: &VersionRange) -> Option<&str> {
+        self.newest_matching(|version| range.contains(version))
+    }
+    /// All versions that match the predicate. Sorted oldest to newest
+    pub fn versions_matching<F>(&self, predicate: F) -> Vec<&str>
+    where
+        F: Fn(&MavenVersion) -> bool,
+    {
+        let mut versions: Vec<_> = self
+            .parsed_versions()
+            .filter(|(parsed, _)| predicate(parsed))
+            .collect();
+        versions.sort_by(|(left, _), (right, _)| left.cmp(right));
+        versions
+            .into_iter()
+            .map(|(_, version)| version.as_str())
+            .collect()
+    }
+    /// All versions within the range. Sorted oldest to newest
+    pub fn versions_in_range(&self, range: &VersionRange) -> Vec<&str> {
+        self.versions_matching(|version| range.contains(version))
+    }
+    fn parsed_versions(&self) -> impl Iterator<Item = (MavenVersion, &String)> {
+        self.versioning
+            .versions
+            .version
+            .iter()
+            .map(|version| (MavenVersion::new(version.as_str()), version))
+    }
+    fn newest_version_by<F>(&self, predicate: F) -> Option<&String>
+    where
+        F: Fn(&MavenVersion) -> bool,
+    {
+        self.parsed_versions()
+            .filter(|(parsed, _)| predicate(parsed))
+            .max_by(|(left, _), (right, _)| left.cmp(right))
+            .map(|(_, version)| version)
     }
     /// Returns a tuple of the latest version and the artifact name.
     pub fn get_latest_artifact_name(
@@ -47,7 +125,7 @@ impl DeployMetadata {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct StableVersioning {
     pub release: Option<String>,
     pub latest: Option<String>,
@@ -56,10 +134,155 @@ pub struct StableVersioning {
     pub last_updated: Option<NaiveDateTime>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+impl HasElementName for StableVersioning {
+    fn element_name() -> &'static str {
+        "versioning"
+    }
+}
+impl ElementConverter for StableVersioning {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut versioning = StableVersioning::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "release" => versioning.release = Some(child.text_content(document)),
+                "latest" => versioning.latest = Some(child.text_content(document)),
+                "versions" => {
+                    versioning.versions.version =
+                        get_all_children_of_element::<MetadataVersion>(document, child)?
+                            .into_iter()
+                            .map(|(version, _)| version.0)
+                            .collect();
+                }
+                "lastUpdated" => {
+                    versioning.last_updated =
+                        Some(parse_standard_time(&child.text_content(document))?);
+                }
+                _ => {}
+            }
+        }
+        Ok(versioning)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            release,
+            latest,
+            versions,
+            last_updated,
+        } = self;
+        let mut children = vec![];
+        if let Some(latest) = latest {
+            children.push(create_basic_text_element(document, "latest", latest));
+        }
+        if let Some(release) = release {
+            children.push(create_basic_text_element(document, "release", release));
+        }
+        let versions_element = Element::new(document, "versions");
+        for version in versions.version {
+            let version = MetadataVersion(version).into_element(document)?;
+            versions_element.push_child(document, version)?;
+        }
+        children.push(versions_element);
+        if let Some(last_updated) = last_updated {
+            children.push(create_basic_text_element(
+                document,
+                "lastUpdated",
+                standard_time::format(&last_updated),
+            ));
+        }
+        Ok(children)
+    }
+}
+impl UpdatableElement for StableVersioning {
+    /// Versions that already exist are left in place. Missing versions are appended and removed versions are detached.
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        sync_element(document, element, "latest", self.latest.as_deref());
+        sync_element(document, element, "release", self.release.as_deref());
+        let versions_element = get_or_create_top_level_element("versions", document, element);
+        let mut existing_versions = vec![];
+        for (version, version_element) in
+            get_all_children_of_element::<MetadataVersion>(document, versions_element)?
+        {
+            if self.versions.version.contains(&version.0) {
+                existing_versions.push(version.0);
+            } else {
+                version_element.detach(document)?;
+            }
+        }
+        for version in &self.versions.version {
+            if !existing_versions.contains(version) {
+                let version = MetadataVersion(version.clone()).into_element(document)?;
+                versions_element.push_child(document, version)?;
+            }
+        }
+        sync_element(
+            document,
+            element,
+            "lastUpdated",
+            self.last_updated.as_ref().map(standard_time::format),
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct StableVersions {
     pub version: Vec<String>,
 }
+/// A single `<version>` inside of `<versions>`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MetadataVersion(pub String);
+impl From<String> for MetadataVersion {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+impl From<&str> for MetadataVersion {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+impl HasElementName for MetadataVersion {
+    fn element_name() -> &'static str {
+        "version"
+    }
+}
+impl ElementConverter for MetadataVersion {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        Ok(Self(element.text_content(document)))
+    }
+    fn into_element(self, document: &mut Document) -> Result<Element, XMLEditorError> {
+        Ok(create_basic_text_element(document, "version", self.0))
+    }
+    fn into_children(self, _: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        Ok(vec![])
+    }
+}
+impl ChildOfListElement for MetadataVersion {
+    fn parent_element_name() -> &'static str {
+        "versions"
+    }
+}
+impl ComparableElement for MetadataVersion {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl UpdatableElement for MetadataVersion {
+    /// Nothing to update. The version is the only value
+    fn update_element(&self, _: Element, _: &mut Document) -> Result<(), XMLEditorError> {
+        Ok(())
+    }
+}
+pub(crate) fn parse_standard_time(value: &str) -> Result<NaiveDateTime, InvalidValueError> {
+    standard_time::parse(value).map_err(|err| InvalidValueError::InvalidFormattedValue {
+        error: format!("{} is not a valid timestamp: {}", value, err),
+    })
+}
 
 #[cfg(test)]
 mod tests {
@@ -89,4 +312,46 @@ mod tests {
             vec!["1.0-SNAPSHOT".to_string()]
         );
     }
+    #[test]
+    pub fn newest_versions() {
+        let metadata = DeployMetadata {
+            group_id: "dev.kingtux".to_owned(),
+            artifact_id: "example".to_owned(),
+            versioning: StableVersioning {
+                versions: StableVersions {
+                    version: [
+                        "1.2",
+                        "1.10",
+                        "1.9",
+                        "2.0-rc1",
+                        "2.0-SNAPSHOT",
+                        "1.11-SNAPSHOT",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                },
+                ..Default::default()
+            },
+        };
+        assert_eq!(metadata.newest_release(false), Some("2.0-rc1"));
+        assert_eq!(metadata.newest_release(true), Some("1.10"));
+        assert_eq!(metadata.newest_snapshot(), Some("2.0-SNAPSHOT"));
+        assert_eq!(
+            metadata.get_latest_version().map(String::as_str),
+            Some("2.0-SNAPSHOT")
+        );
+
+        let range: VersionRange = "[1.5,2.0)".parse().unwrap();
+        assert_eq!(
+            metadata.versions_in_range(&range),
+            vec!["1.9", "1.10", "1.11-SNAPSHOT", "2.0-rc1", "2.0-SNAPSHOT"]
+        );
+        assert_eq!(metadata.newest_in_range(&range), Some("2.0-SNAPSHOT"));
+        assert_eq!(
+            metadata.versions_matching(|version| version.numeric_segments().first() == Some(&1)
+                && !version.is_pre_release()),
+            vec!["1.2", "1.9", "1.10"]
+        );
+    }
 }
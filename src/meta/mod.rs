@@ -1,3 +1,4 @@
+pub mod editor;
 mod maven_metadata;
 mod snapshot_metadata;
 pub use maven_metadata::*;
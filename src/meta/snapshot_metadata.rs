@@ -1,7 +1,31 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Timelike};
+use edit_xml::{Document, Element};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    editor::{
+        ChildOfListElement, ComparableElement, ElementConverter, HasElementName, InvalidValueError,
+        UpdatableElement, XMLEditorError,
+        utils::{add_if_present, create_basic_text_element, sync_element},
+    },
+    extension::MavenFileExtension,
+    utils::time::{snapshot_time, standard_time},
+};
+
+use super::maven_metadata::parse_standard_time;
+
+/// The suffix all snapshot versions end with.
+pub static SNAPSHOT_SUFFIX: &str = "-SNAPSHOT";
+
+#[derive(Debug, Error)]
+pub enum SnapshotMetadataError {
+    #[error("Version {0} is not a snapshot version")]
+    NotASnapshotVersion(String),
+    #[error("Existing metadata is for {found} but the deploy is for {expected}")]
+    MismatchedMetadata { expected: String, found: String },
+}
 
-use crate::extension::MavenFileExtension;
 /// The metadata for a snapshot.
 ///
 /// File name: `maven-metadata.xml`
@@ -15,6 +39,121 @@ pub struct SnapshotMetadata {
 }
 
 impl SnapshotMetadata {
+    /// Creates empty metadata for a snapshot version.
+    pub fn new(
+        group_id: impl Into<String>,
+        artifact_id: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        Self {
+            group_id: group_id.into(),
+            artifact_id: artifact_id.into(),
+            version: version.into(),
+            versioning: SnapshotVersioning::default(),
+        }
+    }
+    /// Applies a deploy of `version` to the existing metadata. Or creates new metadata if none exists.
+    ///
+    /// The build number is bumped, the snapshot timestamp is set to `deployed_at` and a `snapshotVersion` entry is
+    /// added or replaced for every classifier and extension uploaded. Files with a hash (checksums) do not get their own entry.
+    ///
+    /// Returns the updated metadata and the timestamped file names the uploaded files should be stored as.
+    ///
+    /// ```rust
+    /// use maven_rs::meta::SnapshotMetadata;
+    /// use maven_rs::extension::MavenFileExtension;
+    /// let deployed_at = chrono::NaiveDate::from_ymd_opt(2022, 9, 14)
+    ///     .unwrap()
+    ///     .and_hms_opt(17, 4, 40)
+    ///     .unwrap();
+    /// let deploy = SnapshotMetadata::deploy(
+    ///     None,
+    ///     "org.kakara",
+    ///     "engine",
+    ///     "1.0-SNAPSHOT",
+    ///     [MavenFileExtension::from("jar"), MavenFileExtension::from("pom")],
+    ///     deployed_at,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(deploy.files[0].file_name, "engine-1.0-20220914.170440-1.jar");
+    /// ```
+    pub fn deploy<I, F>(
+        existing: Option<SnapshotMetadata>,
+        group_id: &str,
+        artifact_id: &str,
+        version: &str,
+        files: I,
+        deployed_at: NaiveDateTime,
+    ) -> Result<SnapshotDeploy, SnapshotMetadataError>
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<MavenFileExtension>,
+    {
+        let Some(base_version) = version.strip_suffix(SNAPSHOT_SUFFIX) else {
+            return Err(SnapshotMetadataError::NotASnapshotVersion(
+                version.to_owned(),
+            ));
+        };
+        let mut metadata = match existing {
+            Some(existing) => {
+                if existing.group_id != group_id
+                    || existing.artifact_id != artifact_id
+                    || existing.version != version
+                {
+                    return Err(SnapshotMetadataError::MismatchedMetadata {
+                        expected: format!("{}:{}:{}", group_id, artifact_id, version),
+                        found: format!(
+                            "{}:{}:{}",
+                            existing.group_id, existing.artifact_id, existing.version
+                        ),
+                    });
+                }
+                existing
+            }
+            None => SnapshotMetadata::new(group_id, artifact_id, version),
+        };
+        // The timestamp formats only have second precision.
+        let deployed_at = deployed_at.with_nanosecond(0).unwrap_or(deployed_at);
+        let build_number = metadata.versioning.next_build_number();
+        let value = format!(
+            "{}-{}-{}",
+            base_version,
+            deployed_at.format(snapshot_time::FORMAT),
+            build_number
+        );
+
+        let versioning = &mut metadata.versioning;
+        versioning.snapshot = Some(Snapshot {
+            timestamp: Some(deployed_at),
+            build_number: build_number.to_string(),
+        });
+        versioning.last_updated = Some(deployed_at);
+        let snapshot_versions = versioning
+            .snapshot_versions
+            .get_or_insert_with(SnapshotVersions::default);
+
+        let mut deployed_files = vec![];
+        for file in files {
+            let extension: MavenFileExtension = file.into();
+            let file_name = format!("{}-{}{}", artifact_id, value, extension);
+            if extension.hash.is_none() {
+                snapshot_versions.add_or_replace(SnapshotVersion {
+                    classifier: extension.classifier.clone(),
+                    extension: extension.file_extension.clone(),
+                    value: value.clone(),
+                    updated: Some(deployed_at),
+                });
+            }
+            deployed_files.push(SnapshotDeployedFile {
+                extension,
+                file_name,
+            });
+        }
+        Ok(SnapshotDeploy {
+            metadata,
+            files: deployed_files,
+        })
+    }
     /// Returns None if the version is not found in the metadata.
     pub fn get_latest_artifact_name(
         &self,
@@ -34,6 +173,22 @@ impl SnapshotMetadata {
     }
 }
 
+/// The result of [SnapshotMetadata::deploy]
+#[derive(Debug, Clone)]
+pub struct SnapshotDeploy {
+    /// The updated metadata
+    pub metadata: SnapshotMetadata,
+    /// The uploaded files in the order they were passed in.
+    pub files: Vec<SnapshotDeployedFile>,
+}
+/// An uploaded file and the timestamped name it should be stored as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotDeployedFile {
+    pub extension: MavenFileExtension,
+    /// Formatted as `{artifact_id}-{base_version}-{timestamp}-{build_number}{extension}`
+    pub file_name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotVersioning {
@@ -42,14 +197,40 @@ pub struct SnapshotVersioning {
     #[serde(with = "crate::utils::time::standard_time")]
     pub last_updated: Option<NaiveDateTime>,
 }
+impl SnapshotVersioning {
+    /// The build number the next deploy should use.
+    ///
+    /// If the current build number is missing or not a number. 1 is returned
+    pub fn next_build_number(&self) -> u64 {
+        self.snapshot
+            .as_ref()
+            .and_then(|snapshot| snapshot.build_number.trim().parse::<u64>().ok())
+            .map(|build_number| build_number + 1)
+            .unwrap_or(1)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SnapshotVersions {
     #[serde(rename = "snapshotVersion")]
     pub snapshot_version: Vec<SnapshotVersion>,
 }
+impl SnapshotVersions {
+    /// Replaces the entry with the same classifier and extension. Or adds it if it does not exist.
+    pub fn add_or_replace(&mut self, version: SnapshotVersion) {
+        if let Some(existing) = self
+            .snapshot_version
+            .iter_mut()
+            .find(|x| x.is_same_file(&version))
+        {
+            *existing = version;
+        } else {
+            self.snapshot_version.push(version);
+        }
+    }
+}
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Snapshot {
     #[serde(with = "crate::utils::time::snapshot_time")]
@@ -57,7 +238,7 @@ pub struct Snapshot {
     pub build_number: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct SnapshotVersion {
     pub classifier: Option<String>,
     #[serde(default)]
@@ -67,6 +248,153 @@ pub struct SnapshotVersion {
     pub updated: Option<NaiveDateTime>,
 }
 
+impl HasElementName for Snapshot {
+    fn element_name() -> &'static str {
+        "snapshot"
+    }
+}
+impl ElementConverter for Snapshot {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut timestamp = None;
+        let mut build_number = None;
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "timestamp" => {
+                    let value = child.text_content(document);
+                    let parsed = snapshot_time::parse(&value).map_err(|err| {
+                        InvalidValueError::InvalidFormattedValue {
+                            error: format!("{} is not a valid snapshot timestamp: {}", value, err),
+                        }
+                    })?;
+                    timestamp = Some(parsed);
+                }
+                "buildNumber" => build_number = Some(child.text_content(document)),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            timestamp,
+            build_number: build_number.unwrap_or_default(),
+        })
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let mut children = vec![];
+        if let Some(timestamp) = self.timestamp {
+            children.push(create_basic_text_element(
+                document,
+                "timestamp",
+                snapshot_time::format(&timestamp),
+            ));
+        }
+        children.push(create_basic_text_element(
+            document,
+            "buildNumber",
+            self.build_number,
+        ));
+        Ok(children)
+    }
+}
+impl UpdatableElement for Snapshot {
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        sync_element(
+            document,
+            element,
+            "timestamp",
+            self.timestamp.as_ref().map(snapshot_time::format),
+        );
+        sync_element(
+            document,
+            element,
+            "buildNumber",
+            Some(self.build_number.as_str()),
+        );
+        Ok(())
+    }
+}
+impl SnapshotVersion {
+    /// Checks if the classifier and extension are the same.
+    pub fn is_same_file(&self, other: &SnapshotVersion) -> bool {
+        self.extension == other.extension && self.classifier == other.classifier
+    }
+}
+impl HasElementName for SnapshotVersion {
+    fn element_name() -> &'static str {
+        "snapshotVersion"
+    }
+}
+impl ElementConverter for SnapshotVersion {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut classifier = None;
+        let mut extension = String::new();
+        let mut value = None;
+        let mut updated = None;
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "classifier" => classifier = Some(child.text_content(document)),
+                "extension" => extension = child.text_content(document),
+                "value" => value = Some(child.text_content(document)),
+                "updated" => {
+                    updated = Some(parse_standard_time(&child.text_content(document))?);
+                }
+                _ => {}
+            }
+        }
+        let value = value.ok_or(crate::editor::utils::MissingElementError("value"))?;
+        Ok(Self {
+            classifier,
+            extension,
+            value,
+            updated,
+        })
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            classifier,
+            extension,
+            value,
+            updated,
+        } = self;
+        let mut children = vec![];
+        add_if_present!(document, children, classifier, "classifier");
+        children.push(create_basic_text_element(document, "extension", extension));
+        children.push(create_basic_text_element(document, "value", value));
+        let updated = updated.as_ref().map(standard_time::format);
+        add_if_present!(document, children, updated, "updated");
+        Ok(children)
+    }
+}
+impl ChildOfListElement for SnapshotVersion {
+    fn parent_element_name() -> &'static str {
+        "snapshotVersions"
+    }
+}
+impl ComparableElement for SnapshotVersion {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.is_same_file(other)
+    }
+}
+impl UpdatableElement for SnapshotVersion {
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        sync_element(document, element, "value", Some(self.value.as_str()));
+        sync_element(
+            document,
+            element,
+            "updated",
+            self.updated.as_ref().map(standard_time::format),
+        );
+        Ok(())
+    }
+}
 impl PartialEq<MavenFileExtension> for SnapshotVersion {
     fn eq(&self, other: &MavenFileExtension) -> bool {
         self.extension.eq(&other.file_extension) && self.classifier.eq(&other.classifier)
@@ -75,8 +403,95 @@ impl PartialEq<MavenFileExtension> for SnapshotVersion {
 
 #[cfg(test)]
 mod test {
+    use chrono::{NaiveDate, NaiveDateTime};
     use pretty_assertions::assert_eq;
 
+    use super::*;
+
+    fn time(hour: u32, min: u32, sec: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2022, 9, 14)
+            .unwrap()
+            .and_hms_opt(hour, min, sec)
+            .unwrap()
+    }
+    #[test]
+    pub fn deploy_new_and_existing() -> anyhow::Result<()> {
+        let first = SnapshotMetadata::deploy(
+            None,
+            "org.kakara",
+            "engine",
+            "1.0-SNAPSHOT",
+            [
+                MavenFileExtension::from("jar"),
+                MavenFileExtension::from("jar").with_hash("sha1"),
+                MavenFileExtension::from("jar").with_classifier("sources"),
+            ],
+            time(17, 4, 40),
+        )?;
+        assert_eq!(
+            first.metadata.versioning.snapshot,
+            Some(Snapshot {
+                timestamp: Some(time(17, 4, 40)),
+                build_number: "1".to_string(),
+            })
+        );
+        let file_names: Vec<_> = first.files.iter().map(|x| x.file_name.as_str()).collect();
+        assert_eq!(
+            file_names,
+            vec![
+                "engine-1.0-20220914.170440-1.jar",
+                "engine-1.0-20220914.170440-1.jar.sha1",
+                "engine-1.0-20220914.170440-1-sources.jar",
+            ]
+        );
+        // Checksums do not get their own entry
+        let versions = &first
+            .metadata
+            .versioning
+            .snapshot_versions
+            .as_ref()
+            .unwrap();
+        assert_eq!(versions.snapshot_version.len(), 2);
+
+        let second = SnapshotMetadata::deploy(
+            Some(first.metadata),
+            "org.kakara",
+            "engine",
+            "1.0-SNAPSHOT",
+            [MavenFileExtension::from("jar")],
+            time(18, 0, 0),
+        )?;
+        let versioning = &second.metadata.versioning;
+        assert_eq!(versioning.next_build_number(), 3);
+        assert_eq!(versioning.last_updated, Some(time(18, 0, 0)));
+        let versions = &versioning
+            .snapshot_versions
+            .as_ref()
+            .unwrap()
+            .snapshot_version;
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].value, "1.0-20220914.180000-2");
+        assert_eq!(versions[0].updated, Some(time(18, 0, 0)));
+        // The sources jar was not redeployed so it is left alone
+        assert_eq!(versions[1].value, "1.0-20220914.170440-1");
+        Ok(())
+    }
+    #[test]
+    pub fn deploy_release_version() {
+        let result = SnapshotMetadata::deploy(
+            None,
+            "org.kakara",
+            "engine",
+            "1.0",
+            ["jar"],
+            time(17, 4, 40),
+        );
+        assert!(matches!(
+            result,
+            Err(SnapshotMetadataError::NotASnapshotVersion(_))
+        ));
+    }
+
     #[test]
     pub fn parse_snapshot() {
         let test = r#"
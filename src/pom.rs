@@ -1,20 +1,28 @@
 //! The structure and functions to work with a pom file.
 use serde::{Deserialize, Serialize};
+mod activation;
 mod build;
 mod depend;
 mod developers;
 mod distribution_management;
 pub mod editor;
+mod modules;
 mod parent;
+mod profiles;
 mod properties;
+mod reactor;
 mod repositories;
 mod scm;
+pub use activation::*;
 pub use build::*;
 pub use depend::*;
 pub use developers::*;
 pub use distribution_management::*;
+pub use modules::*;
 pub use parent::*;
+pub use profiles::*;
 pub use properties::*;
+pub use reactor::*;
 pub use repositories::*;
 pub use scm::*;
 
@@ -54,6 +62,20 @@ pub struct Pom {
     pub description: Option<String>,
     pub url: Option<String>,
     pub scm: Option<Scm>,
+    pub properties: Option<Properties>,
+    #[serde(default)]
+    pub dependencies: Dependencies,
+    #[serde(rename = "dependencyManagement")]
+    pub dependency_management: Option<DependencyManagement>,
+    #[serde(default)]
+    pub repositories: Repositories,
+    #[serde(default, rename = "pluginRepositories")]
+    pub plugin_repositories: PluginRepositories,
+    pub build: Option<Build>,
+    #[serde(default)]
+    pub modules: Modules,
+    #[serde(default)]
+    pub profiles: Profiles,
 }
 impl Pom {
     /// Gets the group id of the pom.
@@ -0,0 +1,638 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use edit_xml::{Document, Element};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    editor::{
+        ElementConverter, HasElementName, PomValue, UpdatableElement, XMLEditorError,
+        utils::{MissingElementError, add_if_present, create_basic_text_element, text_value},
+    },
+    types::{MavenVersion, VersionRange},
+};
+
+/// The conditions for a profile to be activated.
+///
+/// Used by both pom files and settings files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activation {
+    pub active_by_default: Option<bool>,
+    /// A JDK version or range. Such as `1.8` or `[11,)`
+    pub jdk: Option<String>,
+    pub os: Option<ActivationOs>,
+    pub property: Option<ActivationProperty>,
+    pub file: Option<ActivationFile>,
+}
+impl Activation {
+    /// If any condition other than `activeByDefault` is set
+    pub fn has_conditions(&self) -> bool {
+        self.jdk.is_some() || self.os.is_some() || self.property.is_some() || self.file.is_some()
+    }
+    /// Checks the conditions against the context. All conditions that are set must match.
+    ///
+    /// `activeByDefault` is not checked here. It depends on the other profiles. See [crate::pom::Profiles::active_profiles]
+    ///
+    /// Returns false if there are no conditions.
+    pub fn conditions_match(&self, context: &ActivationContext) -> bool {
+        if !self.has_conditions() {
+            return false;
+        }
+        let jdk = self
+            .jdk
+            .as_deref()
+            .is_none_or(|jdk| jdk_matches(jdk, context));
+        let os = self.os.as_ref().is_none_or(|os| os.matches(context));
+        let property = self
+            .property
+            .as_ref()
+            .is_none_or(|property| property.matches(context));
+        let file = self.file.as_ref().is_none_or(|file| file.matches(context));
+        jdk && os && property && file
+    }
+}
+impl HasElementName for Activation {
+    fn element_name() -> &'static str {
+        "activation"
+    }
+}
+impl ElementConverter for Activation {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut activation = Activation::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "activeByDefault" => {
+                    activation.active_by_default = Some(bool::from_element(child, document)?);
+                }
+                "jdk" => activation.jdk = Some(String::from_element(child, document)?),
+                "os" => activation.os = Some(ActivationOs::from_element(child, document)?),
+                "property" => {
+                    activation.property = Some(ActivationProperty::from_element(child, document)?);
+                }
+                "file" => activation.file = Some(ActivationFile::from_element(child, document)?),
+                _ => {}
+            }
+        }
+        Ok(activation)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            active_by_default,
+            jdk,
+            os,
+            property,
+            file,
+        } = self;
+        let mut children = vec![];
+        add_if_present!(document, children, active_by_default, "activeByDefault");
+        add_if_present!(document, children, jdk, "jdk");
+        if let Some(os) = os {
+            children.push(os.into_element(document)?);
+        }
+        if let Some(property) = property {
+            children.push(property.into_element(document)?);
+        }
+        if let Some(file) = file {
+            children.push(file.into_element(document)?);
+        }
+        Ok(children)
+    }
+}
+impl UpdatableElement for Activation {
+    /// Activation is small and the order of its children does not matter. So it is rewritten.
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        self.clone().replace_all_elements(element, document)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationOs {
+    pub name: Option<String>,
+    /// Such as `windows`, `unix` or `mac`. Can be negated with `!`
+    pub family: Option<String>,
+    pub arch: Option<String>,
+    pub version: Option<String>,
+}
+impl ActivationOs {
+    /// Each value that is set must match. Values can be negated with `!`
+    pub fn matches(&self, context: &ActivationContext) -> bool {
+        let os = &context.os;
+        let family = self
+            .family
+            .as_deref()
+            .is_none_or(|family| negatable(family, |family| os.is_family(family)));
+        let name = self
+            .name
+            .as_deref()
+            .is_none_or(|name| negatable(name, |name| equals_ignore_case(&os.name, name)));
+        let arch = self
+            .arch
+            .as_deref()
+            .is_none_or(|arch| negatable(arch, |arch| equals_ignore_case(&os.arch, arch)));
+        let version = self.version.as_deref().is_none_or(|version| {
+            negatable(version, |version| equals_ignore_case(&os.version, version))
+        });
+        family && name && arch && version
+    }
+}
+impl HasElementName for ActivationOs {
+    fn element_name() -> &'static str {
+        "os"
+    }
+}
+impl ElementConverter for ActivationOs {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut os = ActivationOs::default();
+        for child in element.child_elements(document) {
+            let value = Some(text_value(child, document));
+            match child.name(document) {
+                "name" => os.name = value,
+                "family" => os.family = value,
+                "arch" => os.arch = value,
+                "version" => os.version = value,
+                _ => {}
+            }
+        }
+        Ok(os)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            name,
+            family,
+            arch,
+            version,
+        } = self;
+        let mut children = vec![];
+        add_if_present!(document, children, name, "name");
+        add_if_present!(document, children, family, "family");
+        add_if_present!(document, children, arch, "arch");
+        add_if_present!(document, children, version, "version");
+        Ok(children)
+    }
+}
+
+/// Activates a profile based on a system or user property.
+///
+/// - Only a name. The property must be defined.
+/// - A name starting with `!`. The property must not be defined.
+/// - A name and a value. The property must equal the value. A value starting with `!` must not equal it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationProperty {
+    pub name: String,
+    pub value: Option<String>,
+}
+impl ActivationProperty {
+    pub fn matches(&self, context: &ActivationContext) -> bool {
+        let (negated, name) = match self.name.strip_prefix('!') {
+            Some(name) => (true, name),
+            None => (false, self.name.as_str()),
+        };
+        let current = context.properties.get(name.trim());
+        match self.value.as_deref() {
+            // Only the name. So the property must be defined or not defined
+            None => current.is_some() != negated,
+            Some(value) => match value.strip_prefix('!') {
+                Some(value) => current.is_none_or(|current| current != value),
+                None => current.is_some_and(|current| current == value),
+            },
+        }
+    }
+}
+impl HasElementName for ActivationProperty {
+    fn element_name() -> &'static str {
+        "property"
+    }
+}
+impl ElementConverter for ActivationProperty {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let name = element
+            .find(document, "name")
+            .map(|name| text_value(name, document))
+            .ok_or(MissingElementError("name"))?;
+        let value = element
+            .find(document, "value")
+            .map(|value| text_value(value, document));
+        Ok(Self { name, value })
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self { name, value } = self;
+        let mut children = vec![create_basic_text_element(document, "name", name)];
+        add_if_present!(document, children, value, "value");
+        Ok(children)
+    }
+}
+
+/// Activates a profile based on if a file exists or is missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationFile {
+    pub missing: Option<String>,
+    pub exists: Option<String>,
+}
+impl ActivationFile {
+    /// `exists` is checked first like Maven. `${basedir}` and `${project.basedir}` are replaced with [ActivationContext::base_directory]
+    pub fn matches(&self, context: &ActivationContext) -> bool {
+        if let Some(exists) = &self.exists {
+            return context.resolve_file(exists).exists();
+        }
+        if let Some(missing) = &self.missing {
+            return !context.resolve_file(missing).exists();
+        }
+        false
+    }
+}
+impl HasElementName for ActivationFile {
+    fn element_name() -> &'static str {
+        "file"
+    }
+}
+impl ElementConverter for ActivationFile {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut file = ActivationFile::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "missing" => file.missing = Some(text_value(child, document)),
+                "exists" => file.exists = Some(text_value(child, document)),
+                _ => {}
+            }
+        }
+        Ok(file)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self { missing, exists } = self;
+        let mut children = vec![];
+        add_if_present!(document, children, missing, "missing");
+        add_if_present!(document, children, exists, "exists");
+        Ok(children)
+    }
+}
+
+/// The operating system used by [ActivationContext]
+///
+/// Values use the same format as the Java system properties `os.name`, `os.arch` and `os.version`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsInfo {
+    /// Such as `Linux`, `Windows 11` or `Mac OS X`
+    pub name: String,
+    /// Such as `amd64` or `aarch64`
+    pub arch: String,
+    pub version: String,
+}
+impl OsInfo {
+    /// The operating system this was compiled for. The version is left empty
+    pub fn current() -> Self {
+        let name = match std::env::consts::OS {
+            "linux" => "Linux",
+            "windows" => "Windows",
+            "macos" => "Mac OS X",
+            "freebsd" => "FreeBSD",
+            other => other,
+        };
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "amd64",
+            "x86" => "x86",
+            other => other,
+        };
+        Self {
+            name: name.to_owned(),
+            arch: arch.to_owned(),
+            version: String::new(),
+        }
+    }
+    /// Follows the same rules as Maven's `Os.isFamily`
+    pub fn is_family(&self, family: &str) -> bool {
+        let name = self.name.to_ascii_lowercase();
+        let is_windows = name.contains("windows");
+        let is_mac = name.contains("mac");
+        match family.to_ascii_lowercase().as_str() {
+            "windows" => is_windows,
+            "win9x" => {
+                is_windows && (name.contains("95") || name.contains("98") || name.contains("me"))
+            }
+            "winnt" => is_windows && !self.is_family("win9x"),
+            "dos" => is_windows,
+            "mac" => is_mac,
+            // Mac OS X is unix. Classic Mac OS is not
+            "unix" => !is_windows && (!is_mac || name.ends_with('x')),
+            other => name.contains(other),
+        }
+    }
+}
+/// Everything used to decide if a profile is active.
+///
+/// Nothing is read from the current environment unless requested. So the result is the same on every machine.
+///
+/// ```
+/// use maven_rs::pom::{ActivationContext, OsInfo};
+/// let context = ActivationContext::default()
+///     .with_jdk_version("17.0.2")
+///     .with_os(OsInfo {
+///         name: "Linux".to_owned(),
+///         arch: "amd64".to_owned(),
+///         version: "6.1".to_owned(),
+///     })
+///     .with_property("env", "ci")
+///     .with_active_profile("release");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ActivationContext {
+    /// Such as `17.0.2` or `1.8.0_292`
+    pub jdk_version: Option<String>,
+    pub os: OsInfo,
+    /// System and user properties
+    pub properties: HashMap<String, String>,
+    /// The directory of the project. Relative file paths are resolved against it
+    pub base_directory: Option<PathBuf>,
+    /// Profiles activated explicitly. Such as `-P release`
+    pub active_profiles: Vec<String>,
+    /// Profiles deactivated explicitly. Such as `-P !release`
+    pub inactive_profiles: Vec<String>,
+}
+impl ActivationContext {
+    pub fn with_jdk_version(mut self, version: impl Into<String>) -> Self {
+        self.jdk_version = Some(version.into());
+        self
+    }
+    pub fn with_os(mut self, os: OsInfo) -> Self {
+        self.os = os;
+        self
+    }
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+    pub fn with_base_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.base_directory = Some(directory.into());
+        self
+    }
+    pub fn with_active_profile(mut self, id: impl Into<String>) -> Self {
+        self.active_profiles.push(id.into());
+        self
+    }
+    pub fn with_inactive_profile(mut self, id: impl Into<String>) -> Self {
+        self.inactive_profiles.push(id.into());
+        self
+    }
+    /// Parses the value of `-P`. Such as `release,!tests`
+    pub fn with_profiles_argument(mut self, argument: &str) -> Self {
+        for id in argument
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        {
+            match id.strip_prefix('!').or_else(|| id.strip_prefix('-')) {
+                Some(id) => self.inactive_profiles.push(id.to_owned()),
+                None => self
+                    .active_profiles
+                    .push(id.strip_prefix('+').unwrap_or(id).to_owned()),
+            }
+        }
+        self
+    }
+    pub fn is_explicitly_active(&self, id: &str) -> bool {
+        self.active_profiles.iter().any(|active| active == id)
+    }
+    pub fn is_explicitly_inactive(&self, id: &str) -> bool {
+        self.inactive_profiles.iter().any(|inactive| inactive == id)
+    }
+    fn resolve_file(&self, path: &str) -> PathBuf {
+        let mut path = path.trim().to_owned();
+        if let Some(base_directory) = &self.base_directory {
+            let base_directory = base_directory.display().to_string();
+            path = path
+                .replace("${project.basedir}", &base_directory)
+                .replace("${basedir}", &base_directory);
+        }
+        let path = Path::new(&path);
+        match &self.base_directory {
+            Some(base_directory) if path.is_relative() => base_directory.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+}
+/// A value starting with `!` matches if `check` returns false
+fn negatable(value: &str, check: impl FnOnce(&str) -> bool) -> bool {
+    match value.strip_prefix('!') {
+        Some(value) => !check(value.trim()),
+        None => check(value.trim()),
+    }
+}
+fn equals_ignore_case(current: &str, expected: &str) -> bool {
+    current.eq_ignore_ascii_case(expected)
+}
+/// A range such as `[11,)` or a prefix such as `1.8`. A prefix can be negated with `!`
+fn jdk_matches(jdk: &str, context: &ActivationContext) -> bool {
+    let Some(current) = context.jdk_version.as_deref() else {
+        return false;
+    };
+    let jdk = jdk.trim();
+    if jdk.starts_with('[') || jdk.starts_with('(') {
+        let Ok(range) = jdk.parse::<VersionRange>() else {
+            return false;
+        };
+        return range.contains(&MavenVersion::new(current.replace('_', ".")));
+    }
+    negatable(jdk, |prefix| {
+        // 1.8 matches 1.8.0_292 but 1 does not match 17
+        current
+            .strip_prefix(prefix)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{editor::utils::test_utils, utils::test_utils::TempDirectory};
+
+    use super::*;
+    #[test]
+    fn parse_methods() -> anyhow::Result<()> {
+        let xml = r#"
+            <activation>
+                <activeByDefault>false</activeByDefault>
+                <jdk>[11,)</jdk>
+                <os>
+                    <family>unix</family>
+                    <arch>amd64</arch>
+                </os>
+                <property>
+                    <name>env</name>
+                    <value>!prod</value>
+                </property>
+                <file>
+                    <missing>target/generated</missing>
+                </file>
+            </activation>
+        "#;
+        let expected = Activation {
+            active_by_default: Some(false),
+            jdk: Some("[11,)".to_owned()),
+            os: Some(ActivationOs {
+                family: Some("unix".to_owned()),
+                arch: Some("amd64".to_owned()),
+                ..Default::default()
+            }),
+            property: Some(ActivationProperty {
+                name: "env".to_owned(),
+                value: Some("!prod".to_owned()),
+            }),
+            file: Some(ActivationFile {
+                missing: Some("target/generated".to_owned()),
+                exists: None,
+            }),
+        };
+        let via_edit_xml = test_utils::create_xml_to_element::<Activation>(xml)?;
+        let via_serde: Activation = quick_xml::de::from_str(xml)?;
+        assert_eq!(via_edit_xml, expected);
+        assert_eq!(via_serde, expected);
+        Ok(())
+    }
+
+    fn context() -> ActivationContext {
+        ActivationContext::default()
+            .with_jdk_version("17.0.2")
+            .with_os(OsInfo {
+                name: "Linux".to_owned(),
+                arch: "amd64".to_owned(),
+                version: "6.1.0".to_owned(),
+            })
+            .with_property("env", "ci")
+    }
+    fn jdk(jdk: &str) -> Activation {
+        Activation {
+            jdk: Some(jdk.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn jdk_activation() {
+        let context = context();
+        assert!(jdk("17").conditions_match(&context));
+        assert!(!jdk("1").conditions_match(&context));
+        assert!(!jdk("!17").conditions_match(&context));
+        assert!(jdk("[11,)").conditions_match(&context));
+        assert!(!jdk("[1.8,11)").conditions_match(&context));
+        let legacy = ActivationContext::default().with_jdk_version("1.8.0_292");
+        assert!(jdk("1.8").conditions_match(&legacy));
+        assert!(jdk("[1.8,1.9)").conditions_match(&legacy));
+        assert!(!jdk("17").conditions_match(&ActivationContext::default()));
+    }
+
+    #[test]
+    fn os_activation() {
+        let context = context();
+        let os = |os: ActivationOs| Activation {
+            os: Some(os),
+            ..Default::default()
+        };
+        let family = |family: &str| ActivationOs {
+            family: Some(family.to_owned()),
+            ..Default::default()
+        };
+        assert!(os(family("unix")).conditions_match(&context));
+        assert!(os(family("!windows")).conditions_match(&context));
+        assert!(!os(family("mac")).conditions_match(&context));
+        assert!(
+            os(ActivationOs {
+                name: Some("linux".to_owned()),
+                arch: Some("amd64".to_owned()),
+                version: Some("6.1.0".to_owned()),
+                ..Default::default()
+            })
+            .conditions_match(&context)
+        );
+        assert!(
+            !os(ActivationOs {
+                arch: Some("aarch64".to_owned()),
+                ..family("unix")
+            })
+            .conditions_match(&context)
+        );
+        let mac = OsInfo {
+            name: "Mac OS X".to_owned(),
+            ..Default::default()
+        };
+        assert!(mac.is_family("mac"));
+        assert!(mac.is_family("unix"));
+    }
+
+    #[test]
+    fn property_activation() {
+        let context = context();
+        let property = |name: &str, value: Option<&str>| Activation {
+            property: Some(ActivationProperty {
+                name: name.to_owned(),
+                value: value.map(str::to_owned),
+            }),
+            ..Default::default()
+        };
+        assert!(property("env", None).conditions_match(&context));
+        assert!(!property("!env", None).conditions_match(&context));
+        assert!(property("!missing", None).conditions_match(&context));
+        assert!(property("env", Some("ci")).conditions_match(&context));
+        assert!(!property("env", Some("prod")).conditions_match(&context));
+        assert!(property("env", Some("!prod")).conditions_match(&context));
+        assert!(property("missing", Some("!prod")).conditions_match(&context));
+        assert!(!property("missing", Some("prod")).conditions_match(&context));
+    }
+
+    #[test]
+    fn file_activation() -> anyhow::Result<()> {
+        let directory = TempDirectory::new("activation");
+        std::fs::write(directory.join("marker"), "")?;
+        let context = context().with_base_directory(&*directory);
+        let file = |exists: Option<&str>, missing: Option<&str>| Activation {
+            file: Some(ActivationFile {
+                exists: exists.map(str::to_owned),
+                missing: missing.map(str::to_owned),
+            }),
+            ..Default::default()
+        };
+        assert!(file(Some("marker"), None).conditions_match(&context));
+        assert!(file(Some("${basedir}/marker"), None).conditions_match(&context));
+        assert!(!file(Some("other"), None).conditions_match(&context));
+        assert!(file(None, Some("other")).conditions_match(&context));
+        assert!(!file(None, Some("${project.basedir}/marker")).conditions_match(&context));
+        Ok(())
+    }
+
+    #[test]
+    fn all_conditions_must_match() {
+        let context = context();
+        let activation = Activation {
+            jdk: Some("17".to_owned()),
+            property: Some(ActivationProperty {
+                name: "env".to_owned(),
+                value: Some("prod".to_owned()),
+            }),
+            ..Default::default()
+        };
+        assert!(!activation.conditions_match(&context));
+        let only_default = Activation {
+            active_by_default: Some(true),
+            ..Default::default()
+        };
+        assert!(!only_default.conditions_match(&context));
+    }
+
+    #[test]
+    fn profiles_argument() {
+        let context =
+            ActivationContext::default().with_profiles_argument("release, !tests,-docs,+ci");
+        assert_eq!(context.active_profiles, vec!["release", "ci"]);
+        assert_eq!(context.inactive_profiles, vec!["tests", "docs"]);
+    }
+}
@@ -5,19 +5,94 @@ use crate::{
     editor::{
         ChildOfListElement, ComparableElement, ElementConverter, HasElementName, PomValue,
         UpdatableElement, XMLEditorError,
-        utils::{add_if_present, create_basic_text_element, find_element_or_err, sync_element},
+        utils::{
+            add_if_present, add_or_update_item, create_basic_text_element, find_element_or_err,
+            sync_element, text_value,
+        },
     },
     types::Property,
 };
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder)]
+use super::{Dependencies, Dependency};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Builder)]
 pub struct Build {
     #[serde(rename = "sourceDirectory")]
     pub source_directory: Option<String>,
     #[serde(default)]
     pub plugins: Plugins,
+    #[serde(default)]
+    #[builder(default)]
+    pub extensions: Extensions,
 }
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+impl HasElementName for Build {
+    fn element_name() -> &'static str {
+        "build"
+    }
+}
+impl ElementConverter for Build {
+    fn from_element(
+        element: edit_xml::Element,
+        document: &edit_xml::Document,
+    ) -> Result<Self, XMLEditorError> {
+        let source_directory = element
+            .find(document, "sourceDirectory")
+            .map(|source_directory| String::from_element(source_directory, document))
+            .transpose()?;
+        let plugins = match element.find(document, "plugins") {
+            Some(plugins) => plugins
+                .child_elements(document)
+                .into_iter()
+                .map(|plugin| Plugin::from_element(plugin, document))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        let extensions = match element.find(document, "extensions") {
+            Some(extensions) => extensions
+                .child_elements(document)
+                .into_iter()
+                .map(|extension| Extension::from_element(extension, document))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        Ok(Self {
+            source_directory,
+            plugins: Plugins { plugins },
+            extensions: Extensions { extensions },
+        })
+    }
+
+    fn into_children(
+        self,
+        document: &mut edit_xml::Document,
+    ) -> Result<Vec<edit_xml::Element>, XMLEditorError> {
+        let Self {
+            source_directory,
+            plugins,
+            extensions,
+        } = self;
+        let mut result = vec![];
+        add_if_present!(document, result, source_directory, "sourceDirectory");
+        if !plugins.plugins.is_empty() {
+            let plugins_element = edit_xml::Element::new(document, "plugins");
+            for plugin in plugins.plugins {
+                let plugin = plugin.into_element(document)?;
+                plugins_element.push_child(document, plugin)?;
+            }
+            result.push(plugins_element);
+        }
+        if !extensions.extensions.is_empty() {
+            let extensions_element = edit_xml::Element::new(document, "extensions");
+            for extension in extensions.extensions {
+                let extension = extension.into_element(document)?;
+                extensions_element.push_child(document, extension)?;
+            }
+            result.push(extensions_element);
+        }
+        Ok(result)
+    }
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct Plugins {
     #[serde(default, rename = "plugin")]
     pub plugins: Vec<Plugin>,
@@ -29,6 +104,10 @@ pub struct Plugin {
     #[serde(rename = "artifactId")]
     pub artifact_id: String,
     pub version: Option<Property>,
+    /// Extra dependencies of the plugin
+    #[serde(default)]
+    #[builder(default)]
+    pub dependencies: Dependencies,
     // TODO Add configuration
 }
 impl Plugin {
@@ -53,17 +132,28 @@ impl ElementConverter for Plugin {
             .find(document, "groupId")
             .map(|group_id| String::from_element(group_id, document))
             .transpose()?;
-        let artifact_id =
-            find_element_or_err(element, "artifactId", document)?.text_content(document);
+        let artifact_id = text_value(
+            find_element_or_err(element, "artifactId", document)?,
+            document,
+        );
         let version = element
             .find(document, "version")
             .map(|element| Property::from_element(element, document))
             .transpose()?;
+        let dependencies = match element.find(document, "dependencies") {
+            Some(dependencies) => dependencies
+                .child_elements(document)
+                .into_iter()
+                .map(|dependency| Dependency::from_element(dependency, document))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
 
         Ok(Self {
             group_id,
             artifact_id,
             version,
+            dependencies: Dependencies { dependencies },
         })
     }
 
@@ -75,6 +165,7 @@ impl ElementConverter for Plugin {
             group_id,
             artifact_id,
             version,
+            dependencies,
         } = self;
         let mut result = vec![];
         add_if_present!(document, result, group_id, "groupId");
@@ -84,6 +175,14 @@ impl ElementConverter for Plugin {
             artifact_id,
         ));
         add_if_present!(document, result, version, "version");
+        if !dependencies.dependencies.is_empty() {
+            let dependencies_element = edit_xml::Element::new(document, "dependencies");
+            for dependency in dependencies.dependencies {
+                let dependency = dependency.into_element(document)?;
+                dependencies_element.push_child(document, dependency)?;
+            }
+            result.push(dependencies_element);
+        }
 
         Ok(result)
     }
@@ -99,6 +198,9 @@ impl ComparableElement for Plugin {
     }
 }
 impl UpdatableElement for Plugin {
+    /// Dependencies of the plugin are updated in place. Dependencies that are not in [Plugin::dependencies] are removed.
+    ///
+    /// Elements that are not part of [Plugin] such as `<configuration>` are kept.
     fn update_element(
         &self,
         element: edit_xml::Element,
@@ -110,6 +212,86 @@ impl UpdatableElement for Plugin {
             "version",
             self.version.as_ref().map(|v| v.to_string()),
         );
+        if let Some(dependencies) = element.find(document, "dependencies") {
+            for existing in dependencies.child_elements(document) {
+                let existing_dependency = Dependency::from_element(existing, document)?;
+                if !self
+                    .dependencies
+                    .dependencies
+                    .iter()
+                    .any(|dependency| dependency.is_same_item(&existing_dependency))
+                {
+                    existing.detach(document)?;
+                }
+            }
+            if self.dependencies.dependencies.is_empty() {
+                dependencies.detach(document)?;
+            }
+        }
+        for dependency in &self.dependencies.dependencies {
+            let dependencies = element.find(document, "dependencies");
+            add_or_update_item(document, dependencies, element, dependency.clone())?;
+        }
         Ok(())
     }
 }
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Extensions {
+    #[serde(default, rename = "extension")]
+    pub extensions: Vec<Extension>,
+}
+/// A build extension. `<build><extensions><extension>`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Builder)]
+pub struct Extension {
+    #[serde(rename = "groupId")]
+    #[builder(setter(into))]
+    pub group_id: String,
+    #[serde(rename = "artifactId")]
+    #[builder(setter(into))]
+    pub artifact_id: String,
+    #[builder(setter(into, strip_option), default)]
+    pub version: Option<Property>,
+}
+impl HasElementName for Extension {
+    fn element_name() -> &'static str {
+        "extension"
+    }
+}
+impl ElementConverter for Extension {
+    fn from_element(
+        element: edit_xml::Element,
+        document: &edit_xml::Document,
+    ) -> Result<Self, XMLEditorError> {
+        let group_id = text_value(find_element_or_err(element, "groupId", document)?, document);
+        let artifact_id = text_value(
+            find_element_or_err(element, "artifactId", document)?,
+            document,
+        );
+        let version = element
+            .find(document, "version")
+            .map(|element| Property::from_element(element, document))
+            .transpose()?;
+        Ok(Self {
+            group_id,
+            artifact_id,
+            version,
+        })
+    }
+
+    fn into_children(
+        self,
+        document: &mut edit_xml::Document,
+    ) -> Result<Vec<edit_xml::Element>, XMLEditorError> {
+        let Self {
+            group_id,
+            artifact_id,
+            version,
+        } = self;
+        let mut result = vec![
+            create_basic_text_element(document, "groupId", group_id),
+            create_basic_text_element(document, "artifactId", artifact_id),
+        ];
+        add_if_present!(document, result, version, "version");
+        Ok(result)
+    }
+}
@@ -6,8 +6,8 @@ use crate::{
         ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
         XMLEditorError,
         utils::{
-            add_if_present, create_basic_text_element, find_or_create_then_set_text_content,
-            sync_element, typed_from_element_using_builder,
+            add_if_present, create_basic_text_element, sync_element,
+            typed_from_element_using_builder,
         },
     },
     types::Property,
@@ -18,11 +18,17 @@ use edit_xml::{Document, Element};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct Dependencies {
     #[serde(default, rename = "dependency")]
     pub dependencies: Vec<Dependency>,
 }
+/// `<dependencyManagement>`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DependencyManagement {
+    #[serde(default)]
+    pub dependencies: Dependencies,
+}
 #[derive(Debug, Error)]
 pub enum DependencyParseError {
     #[error("Missing artifact id")]
@@ -113,15 +119,9 @@ impl UpdatableElement for Dependency {
             "version",
             self.version.as_ref().map(|v| v.to_string()),
         );
-        if let Some(depend_type) = &self.depend_type {
-            find_or_create_then_set_text_content(document, element, "type", depend_type);
-        }
-        if let Some(scope) = &self.scope {
-            find_or_create_then_set_text_content(document, element, "scope", scope);
-        }
-        if let Some(classifier) = &self.classifier {
-            find_or_create_then_set_text_content(document, element, "classifier", classifier);
-        }
+        sync_element(document, element, "type", self.depend_type.as_deref());
+        sync_element(document, element, "scope", self.scope.as_deref());
+        sync_element(document, element, "classifier", self.classifier.as_deref());
         Ok(())
     }
 }
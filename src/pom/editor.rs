@@ -12,18 +12,37 @@
 
 use std::io::Write;
 
-use edit_xml::{Document, Element, ReadOptions, WriteOptions};
+use edit_xml::{Document, EditXMLError, Element};
+mod artifacts;
 mod build;
+mod canonical;
 mod dependency_management;
+mod diff;
 mod distribution_management;
-use super::{Developer, Parent, Repository, Scm, depend::Dependency};
+mod model;
+mod modules;
+mod plugin_repositories;
+mod profile;
+mod properties;
+mod spans;
+mod update_report;
+mod updates;
+mod validation;
+use super::{Developer, Parent, Profile, Repository, Scm, depend::Dependency};
 use crate::editor::{
-    ElementConverter, UpdatableElement, XMLEditorError,
-    utils::{MissingElementError, add_or_update_item, get_all_children_of_element},
+    ElementConverter, SourceFormat, SourceSpans, UpdatableElement, XMLEditorError,
+    utils::{MissingElementError, add_or_update_item, text_value},
 };
+pub use artifacts::*;
 pub use build::*;
+pub use canonical::*;
 pub use dependency_management::*;
+pub use diff::*;
 pub use distribution_management::*;
+pub use profile::*;
+pub use update_report::*;
+pub use updates::*;
+pub use validation::*;
 /// A struct that allows editing and creating pom files
 /// A pom file is an xml file that follows the maven pom schema
 #[derive(Debug)]
@@ -33,6 +52,10 @@ pub struct PomEditor {
     /// The root element must always be a project element. As this code assumes it exists and without it. Panicking would occur.
     document: Document,
     pub ident_level: usize,
+    /// The formatting of the source file. Used when writing so only edited elements change
+    pub format: SourceFormat,
+    /// Where the elements were in the source file
+    spans: SourceSpans,
 }
 impl Default for PomEditor {
     fn default() -> Self {
@@ -48,6 +71,8 @@ impl Default for PomEditor {
         let mut editor = Self {
             document,
             ident_level: 2,
+            format: SourceFormat::default(),
+            spans: SourceSpans::default(),
         };
 
         editor.set_model_version("4.0.0");
@@ -93,7 +118,7 @@ macro_rules! simple_type_getter_setter {
         pub fn $get(&self) -> Option<String> {
             let root = self.root();
             let element = root.find(&self.document, $name);
-            return element.map(|x| x.text_content(&self.document));
+            return element.map(|x| text_value(x, &self.document));
         }
 
     };
@@ -135,7 +160,10 @@ macro_rules! top_level_structured_type {
         pub fn $get(&self) -> Result<Option<$structured_type>, XMLEditorError> {
             let root = self.root();
             root.find(&self.document, $element_name)
-                .map(|x| $structured_type::from_element(x, &self.document))
+                .map(|x| {
+                    $structured_type::from_element(x, &self.document)
+                        .map_err(|error| self.spans.locate(&self.document, x, error))
+                })
                 .transpose()
         }
         $(#[$set_docs])*
@@ -161,14 +189,68 @@ macro_rules! top_level_structured_type {
             Ok(())
         }
     };
+    (
+        in $element:ident,
+        $(#[$set_docs:meta])*
+        set: $set:ident,
+        $(#[$get_docs:meta])*
+        get: $get:ident,
+        $element_name:literal => $structured_type:ident,
+    ) => {
+        $(#[$get_docs])*
+        pub fn $get(&self) -> Result<Option<$structured_type>, XMLEditorError> {
+            self.$element
+                .find(&self.parent.document, $element_name)
+                .map(|x| {
+                    $structured_type::from_element(x, &self.parent.document)
+                        .map_err(|error| self.parent.spans.locate(&self.parent.document, x, error))
+                })
+                .transpose()
+        }
+        $(#[$set_docs])*
+        pub fn $set<U>(&mut self, value: U) -> Result<(), XMLEditorError>
+        where
+            U: Into<Option<$structured_type>>,
+        {
+            let value: Option<$structured_type> = value.into();
+            let existing_element = self.$element.find(&self.parent.document, $element_name);
+            match (value, existing_element) {
+                (Some(value), Some(element)) => {
+                    value.update_element(element, &mut self.parent.document)?;
+                }
+                (Some(value), None) => {
+                    let new_element = value.into_element(&mut self.parent.document)?;
+                    self.$element
+                        .push_child(&mut self.parent.document, new_element)?;
+                }
+                (None, Some(element)) => {
+                    element.detach(&mut self.parent.document)?;
+                }
+                (None, None) => {}
+            }
+            Ok(())
+        }
+    };
 }
+pub(crate) use top_level_structured_type;
 
-macro_rules! list_item_getter_and_add {
+/// Generates the methods for a list section. Such as `<dependencies>`
+///
+/// Items are matched using [crate::editor::ComparableElement].
+///
+/// By default the list is a child of the project. `in $element` is used by the editors of a section. Such as [BuildEditor] and [ProfileEditor]
+macro_rules! list_item_methods {
     (
         $(#[$get_docs:meta])*
         get: $get:ident,
+        $(#[$find_docs:meta])*
+        find: $find:ident,
         $(#[$add_docs:meta])*
         add: $add:ident,
+        $(#[$modify_docs:meta])*
+        modify: $modify:ident,
+        $(#[$remove_docs:meta])*
+        remove: $remove:ident,
         $(#[$clear_docs:meta])*
         clear: $clear:ident,
         $parent:literal => $list_element:ident
@@ -180,10 +262,22 @@ macro_rules! list_item_getter_and_add {
             else {
                 return Ok(vec![]);
             };
-            let result =
-                get_all_children_of_element::<$list_element>(&self.document, parent_element)?;
+            let result = crate::editor::utils::get_all_children_of_element_located::<$list_element>(
+                &self.document,
+                parent_element,
+                &self.spans,
+            )?;
             Ok(result.into_iter().map(|(v, _)| v).collect())
         }
+        $(#[$find_docs])*
+        pub fn $find(
+            &self,
+            value: &$list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let parent_element = self.root().find(&self.document, $parent);
+            let result = crate::editor::utils::find_item(&self.document, parent_element, value)?;
+            Ok(result.map(|(v, _)| v))
+        }
         $(#[$add_docs])*
         pub fn $add(
             &mut self,
@@ -193,6 +287,26 @@ macro_rules! list_item_getter_and_add {
             let parent_element = root.find(&self.document, $parent);
             add_or_update_item(&mut self.document, parent_element, root, value)
         }
+        $(#[$modify_docs])*
+        pub fn $modify<F>(
+            &mut self,
+            value: &$list_element,
+            modify: F,
+        ) -> Result<Option<$list_element>, XMLEditorError>
+        where
+            F: FnOnce(&mut $list_element),
+        {
+            let parent_element = self.root().find(&self.document, $parent);
+            crate::editor::utils::modify_item(&mut self.document, parent_element, value, modify)
+        }
+        $(#[$remove_docs])*
+        pub fn $remove(
+            &mut self,
+            value: &$list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let parent_element = self.root().find(&self.document, $parent);
+            crate::editor::utils::remove_item(&mut self.document, parent_element, value)
+        }
         $(#[$clear_docs])*
         pub fn  $clear(&mut self)-> Result<(), XMLEditorError> {
             let root = self.root();
@@ -203,7 +317,93 @@ macro_rules! list_item_getter_and_add {
             Ok(())
         }
     };
+    (
+        in $element:ident,
+        $(#[$get_docs:meta])*
+        get: $get:ident,
+        $(#[$find_docs:meta])*
+        find: $find:ident,
+        $(#[$add_docs:meta])*
+        add: $add:ident,
+        $(#[$modify_docs:meta])*
+        modify: $modify:ident,
+        $(#[$remove_docs:meta])*
+        remove: $remove:ident,
+        $(#[$clear_docs:meta])*
+        clear: $clear:ident,
+        $parent:literal => $list_element:ident
+    ) => {
+        $(#[$get_docs])*
+        pub fn $get(&self) -> Result<Vec<$list_element>, XMLEditorError> {
+            let Some(parent_element) = self.$element.find(&self.parent.document, $parent)
+            else {
+                return Ok(vec![]);
+            };
+            let result = crate::editor::utils::get_all_children_of_element_located::<$list_element>(
+                &self.parent.document,
+                parent_element,
+                &self.parent.spans,
+            )?;
+            Ok(result.into_iter().map(|(v, _)| v).collect())
+        }
+        $(#[$find_docs])*
+        pub fn $find(
+            &self,
+            value: &$list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let parent_element = self.$element.find(&self.parent.document, $parent);
+            let result =
+                crate::editor::utils::find_item(&self.parent.document, parent_element, value)?;
+            Ok(result.map(|(v, _)| v))
+        }
+        $(#[$add_docs])*
+        pub fn $add(
+            &mut self,
+            value: $list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let parent_element = self.$element.find(&self.parent.document, $parent);
+            crate::editor::utils::add_or_update_item(
+                &mut self.parent.document,
+                parent_element,
+                self.$element,
+                value,
+            )
+        }
+        $(#[$modify_docs])*
+        pub fn $modify<F>(
+            &mut self,
+            value: &$list_element,
+            modify: F,
+        ) -> Result<Option<$list_element>, XMLEditorError>
+        where
+            F: FnOnce(&mut $list_element),
+        {
+            let parent_element = self.$element.find(&self.parent.document, $parent);
+            crate::editor::utils::modify_item(
+                &mut self.parent.document,
+                parent_element,
+                value,
+                modify,
+            )
+        }
+        $(#[$remove_docs])*
+        pub fn $remove(
+            &mut self,
+            value: &$list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let parent_element = self.$element.find(&self.parent.document, $parent);
+            crate::editor::utils::remove_item(&mut self.parent.document, parent_element, value)
+        }
+        $(#[$clear_docs])*
+        pub fn $clear(&mut self) -> Result<(), XMLEditorError> {
+            if let Some(parent_element) = self.$element.find(&self.parent.document, $parent) {
+                parent_element.clear_children(&mut self.parent.document);
+            }
+            Ok(())
+        }
+    };
 }
+pub(crate) use list_item_methods;
 impl PomEditor {
     /// Creates a new [PomEditor] with the group id and artifact id set
     pub fn new_with_group_and_artifact(group_id: &str, artifact_id: &str) -> Self {
@@ -314,7 +514,7 @@ impl PomEditor {
         }
     ];
 
-    list_item_getter_and_add!(
+    list_item_methods!(
         /// Gets all the repositories in the pom file
         /// ```rust
         /// use maven_rs::pom::editor::PomEditor;
@@ -335,6 +535,8 @@ impl PomEditor {
         /// assert_eq!(repositories.len(), 1);
         /// assert_eq!(repositories[0].id, Some("central".to_string()));
         get: get_repositories,
+        /// Finds the repository with the same name
+        find: find_repository,
         /// Adds or Updates a repository in the pom file
         /// ```rust
         /// use maven_rs::pom::editor::PomEditor;
@@ -351,11 +553,17 @@ impl PomEditor {
         /// assert_eq!(repositories[0].id, Some("central".to_string()));
         /// ```
         add: add_or_update_repository,
+        /// Modifies the repository with the same name where it is in the file
+        ///
+        /// Returns the modified repository. None if it was not found
+        modify: modify_repository,
+        /// Removes the repository with the same name
+        remove: remove_repository,
         /// Clears all the repositories in the pom file
         clear: clear_repositories,
         "repositories" => Repository
     );
-    list_item_getter_and_add!(
+    list_item_methods!(
         /// Gets all the developers in the pom file
         /// ```rust
         /// use maven_rs::pom::editor::PomEditor;
@@ -376,6 +584,8 @@ impl PomEditor {
         /// assert_eq!(developers[0].id, Some("dev.wyatt-herkamp".to_string()));
         /// ```
         get: get_developers,
+        /// Finds the developer with the same id
+        find: find_developer,
         /// Adds or Updates a developer in the pom file
         /// ```rust
         /// use maven_rs::pom::editor::PomEditor;
@@ -391,11 +601,17 @@ impl PomEditor {
         /// assert_eq!(developers[0].id, Some("dev.wyatt-herkamp".to_string()));
         /// ```
         add: add_or_update_developer,
+        /// Modifies the developer with the same id where it is in the file
+        ///
+        /// Returns the modified developer. None if it was not found
+        modify: modify_developer,
+        /// Removes the developer with the same id
+        remove: remove_developer,
         /// Clears all the developers in the pom file
         clear: clear_developers,
         "developers" => Developer
     );
-    list_item_getter_and_add!(
+    list_item_methods!(
         /// Gets all the dependencies in the pom file
         /// ```rust
         /// use maven_rs::pom::editor::PomEditor;
@@ -417,6 +633,18 @@ impl PomEditor {
         /// assert_eq!(dependencies[0].group_id, "com.google.guava".to_string());
         /// ```
         get: get_dependencies,
+        /// Finds the dependency with the same group id and artifact id
+        ///
+        /// ```rust
+        /// use maven_rs::pom::editor::PomEditor;
+        /// use maven_rs::pom::Dependency;
+        /// let mut editor = PomEditor::default();
+        /// editor.add_or_update_dependency("com.google.guava:guava:30.1-jre".parse().unwrap()).unwrap();
+        /// // The version is not used to match
+        /// let found = editor.find_dependency(&"com.google.guava:guava:33.0.0-jre".parse().unwrap()).unwrap();
+        /// assert_eq!(found.unwrap().version.unwrap().to_string(), "30.1-jre");
+        /// ```
+        find: find_dependency,
         /// Adds or Updates a dependency in the pom file
         ///
         /// ```rust
@@ -434,42 +662,73 @@ impl PomEditor {
         /// assert_eq!(dependencies[0].group_id, "com.google.guava".to_string());
         /// ```
         add: add_or_update_dependency,
+        /// Modifies the dependency with the same group id and artifact id where it is in the file
+        ///
+        /// Returns the modified dependency. None if it was not found
+        ///
+        /// ```rust
+        /// use maven_rs::pom::editor::PomEditor;
+        /// use maven_rs::pom::Dependency;
+        /// let mut editor = PomEditor::default();
+        /// editor.add_or_update_dependency("com.google.guava:guava:30.1-jre".parse().unwrap()).unwrap();
+        /// let key: Dependency = "com.google.guava:guava:30.1-jre".parse().unwrap();
+        /// editor.modify_dependency(&key, |dependency| {
+        ///     dependency.scope = Some("test".to_string());
+        /// }).unwrap();
+        /// let dependency = editor.find_dependency(&key).unwrap().unwrap();
+        /// assert_eq!(dependency.scope, Some("test".to_string()));
+        /// ```
+        modify: modify_dependency,
+        /// Removes the dependency with the same group id and artifact id
+        remove: remove_dependency,
         /// Clears all the dependencies in the pom file
         clear: clear_dependencies,
         "dependencies" => Dependency
     );
-    // TODO:  pluginRepositories
-    // Loads a pom from a string
-    pub fn load_from_str(value: &str) -> Result<Self, XMLEditorError> {
-        let document = Document::parse_str_with_opts(
-            value,
-            ReadOptions {
-                require_decl: false,
-                ..Default::default()
-            },
+    /// Gets all the profiles in the pom file
+    ///
+    /// Use [crate::pom::Pom::active_profiles] to find out which are active
+    pub fn get_profiles(&self) -> Result<Vec<Profile>, XMLEditorError> {
+        let root = self.root();
+        let Some(profiles) = root.find(&self.document, "profiles") else {
+            return Ok(vec![]);
+        };
+        let result = crate::editor::utils::get_all_children_of_element_located::<Profile>(
+            &self.document,
+            profiles,
+            &self.spans,
         )?;
+        Ok(result.into_iter().map(|(profile, _)| profile).collect())
+    }
+    /// Loads a pom from a string
+    ///
+    /// The whitespace between elements, the start tags and the xml declaration are kept when the pom is written.
+    /// Only the elements that were edited change.
+    pub fn load_from_str(value: &str) -> Result<Self, XMLEditorError> {
+        let document = Document::parse_str_with_opts(value, SourceFormat::read_options())?;
         Self::assert_requirements_for_pom(&document)?;
         Ok(Self {
+            spans: SourceSpans::new(value.as_bytes(), &document),
             document,
-            ident_level: 2,
+            ident_level: SourceFormat::detect_indent_size(value).unwrap_or(2),
+            format: SourceFormat::detect(value),
         })
     }
     /// Loads a pom from a reader
     ///
     /// # Errors
     /// If the xml is not a valid pom file
-    pub fn load_from_reader<R: std::io::Read>(reader: R) -> Result<Self, XMLEditorError> {
-        let document = Document::parse_reader_with_opts(
-            reader,
-            ReadOptions {
-                require_decl: false,
-                ..Default::default()
-            },
-        )?;
+    pub fn load_from_reader<R: std::io::Read>(mut reader: R) -> Result<Self, XMLEditorError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(EditXMLError::from)?;
+        let document = Document::parse_reader_with_opts(&bytes[..], SourceFormat::read_options())?;
         Self::assert_requirements_for_pom(&document)?;
+        let source = String::from_utf8_lossy(&bytes);
         Ok(Self {
+            spans: SourceSpans::new(&bytes, &document),
             document,
-            ident_level: 2,
+            ident_level: SourceFormat::detect_indent_size(&source).unwrap_or(2),
+            format: SourceFormat::detect(&source),
         })
     }
 
@@ -491,25 +750,17 @@ impl PomEditor {
         self.document.root_element().unwrap()
     }
     pub fn write_to_str(&self) -> Result<String, XMLEditorError> {
-        self.document
-            .write_str_with_opts(WriteOptions {
-                write_decl: true,
-                indent_size: self.ident_level,
-                ..Default::default()
-            })
-            .map_err(XMLEditorError::from)
+        Ok(self
+            .format
+            .write_document(&self.document, &self.spans, self.ident_level))
     }
+    /// Writes the pom in the encoding of its xml declaration
     pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), XMLEditorError> {
-        self.document
-            .write_with_opts(
-                writer,
-                WriteOptions {
-                    write_decl: true,
-                    indent_size: self.ident_level,
-                    ..Default::default()
-                },
-            )
-            .map_err(XMLEditorError::from)
+        let written = self.write_to_str()?;
+        writer
+            .write_all(&self.format.encode(&written))
+            .map_err(EditXMLError::from)?;
+        Ok(())
     }
 }
 
@@ -591,4 +842,122 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn find_modify_and_remove() -> anyhow::Result<()> {
+        let xml = r#"
+            <project>
+                <dependencies>
+                    <dependency>
+                        <groupId>com.google.guava</groupId>
+                        <artifactId>guava</artifactId>
+                        <version>30.1-jre</version>
+                    </dependency>
+                    <!-- Testing -->
+                    <dependency>
+                        <groupId>org.junit.jupiter</groupId>
+                        <artifactId>junit-jupiter</artifactId>
+                        <version>5.10.0</version>
+                    </dependency>
+                    <dependency>
+                        <groupId>org.slf4j</groupId>
+                        <artifactId>slf4j-api</artifactId>
+                        <version>2.0.16</version>
+                    </dependency>
+                </dependencies>
+            </project>
+        "#;
+        let mut editor = PomEditor::load_from_str(xml)?;
+        let junit: Dependency = "org.junit.jupiter:junit-jupiter:5.11.0".parse()?;
+        let found = editor
+            .find_dependency(&junit)?
+            .expect("junit should be found");
+        assert_eq!(found.version, Some("5.10.0".parse().unwrap()));
+        assert!(
+            editor
+                .find_dependency(&"org.example:missing:1".parse()?)?
+                .is_none()
+        );
+
+        let modified = editor.modify_dependency(&junit, |dependency| {
+            dependency.version = Some("5.11.0".parse().unwrap());
+            dependency.scope = Some("test".to_owned());
+        })?;
+        assert_eq!(modified.and_then(|d| d.scope), Some("test".to_owned()));
+
+        let removed = editor.remove_dependency(&"com.google.guava:guava:1".parse()?)?;
+        assert_eq!(removed.map(|d| d.artifact_id), Some("guava".to_owned()));
+        assert!(
+            editor
+                .remove_dependency(&"com.google.guava:guava:1".parse()?)?
+                .is_none()
+        );
+
+        let value = editor.write_to_str()?;
+        assert!(value.contains("<!-- Testing -->"));
+        let artifacts: Vec<_> = PomEditor::load_from_str(&value)?
+            .get_dependencies()?
+            .into_iter()
+            .map(|d| d.artifact_id)
+            .collect();
+        // junit stays where it was
+        assert_eq!(artifacts, vec!["junit-jupiter", "slf4j-api"]);
+        Ok(())
+    }
+
+    #[test]
+    pub fn keep_source_format() -> anyhow::Result<()> {
+        let xml = "<?xml version=\"1.0\" encoding=\"ISO-8859-1\" standalone=\"yes\"?>\r\n<project>\r\n\t<modelVersion>4.0.0</modelVersion>\r\n\t<properties>\r\n\t\t<java.version>17</java.version>\r\n\t</properties>\r\n</project>";
+        let mut editor = PomEditor::load_from_str(xml)?;
+        assert_eq!(editor.ident_level, 1);
+        assert_eq!(editor.write_to_str()?, xml);
+
+        editor.set_property("java.version", "21")?;
+        editor.set_property("encoding", "UTF-8")?;
+        assert_eq!(
+            editor.write_to_str()?,
+            "<?xml version=\"1.0\" encoding=\"ISO-8859-1\" standalone=\"yes\"?>\r\n<project>\r\n\t<modelVersion>4.0.0</modelVersion>\r\n\t<properties>\r\n\t\t<java.version>21</java.version>\r\n\t\t<encoding>UTF-8</encoding>\r\n\t</properties>\r\n</project>"
+        );
+        // The bytes are written in the declared encoding
+        editor.set_property("name", "caf\u{e9} \u{2603}")?;
+        let mut written = Vec::new();
+        editor.write(&mut written)?;
+        let expected = b"<name>caf\xE9 &#x2603;</name>";
+        assert!(
+            written
+                .windows(expected.len())
+                .any(|window| window == expected)
+        );
+        Ok(())
+    }
+    #[test]
+    pub fn keep_byte_order_mark() -> anyhow::Result<()> {
+        let xml = "\u{feff}<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<project>\n  <modelVersion>4.0.0</modelVersion>\n</project>\n";
+        let editor = PomEditor::load_from_str(xml)?;
+        assert!(editor.format.byte_order_mark);
+        let mut written = Vec::new();
+        editor.write(&mut written)?;
+        assert_eq!(written, xml.as_bytes());
+        Ok(())
+    }
+    #[test]
+    pub fn keep_indent_size() -> anyhow::Result<()> {
+        let xml = "<project>\n    <modelVersion>4.0.0</modelVersion>\n    <properties>\n        <java.version>17</java.version>\n    </properties>\n</project>\n";
+        let mut editor = PomEditor::load_from_str(xml)?;
+        assert_eq!(editor.ident_level, 4);
+        editor.set_property("encoding", "UTF-8")?;
+        let mut written = Vec::new();
+        editor.write(&mut written)?;
+        assert_eq!(
+            String::from_utf8(written)?,
+            "<project>\n    <modelVersion>4.0.0</modelVersion>\n    <properties>\n        <java.version>17</java.version>\n        <encoding>UTF-8</encoding>\n    </properties>\n</project>\n"
+        );
+        // A new pom still gets the default declaration
+        assert!(
+            PomEditor::default()
+                .write_to_str()?
+                .starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>")
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,315 @@
+use edit_xml::{Document, Element};
+use serde::Serialize;
+
+use crate::editor::{XMLEditorError, utils::text_value};
+
+use super::PomEditor;
+
+/// The section of a pom an [ArtifactReference] is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum ArtifactLocation {
+    /// `<parent>`
+    Parent,
+    /// `<dependencies>`
+    Dependency,
+    /// `<dependencyManagement><dependencies>`
+    ManagedDependency,
+    /// `<build><plugins>`
+    Plugin,
+    /// `<build><pluginManagement><plugins>`
+    ManagedPlugin,
+    /// `<dependencies>` of a plugin in `<build><plugins>`
+    PluginDependency,
+    /// `<build><extensions>`
+    Extension,
+}
+/// The paths of each location relative to the project or a profile
+const LOCATIONS: [(ArtifactLocation, &[&str]); 6] = [
+    (
+        ArtifactLocation::Dependency,
+        &["dependencies", "dependency"],
+    ),
+    (
+        ArtifactLocation::ManagedDependency,
+        &["dependencyManagement", "dependencies", "dependency"],
+    ),
+    (ArtifactLocation::Plugin, &["build", "plugins", "plugin"]),
+    (
+        ArtifactLocation::ManagedPlugin,
+        &["build", "pluginManagement", "plugins", "plugin"],
+    ),
+    (
+        ArtifactLocation::PluginDependency,
+        &["build", "plugins", "plugin", "dependencies", "dependency"],
+    ),
+    (
+        ArtifactLocation::Extension,
+        &["build", "extensions", "extension"],
+    ),
+];
+/// A reference to another artifact inside of a pom.
+///
+/// Values are as written in the pom. Properties are not resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactReference {
+    pub location: ArtifactLocation,
+    /// The id of the profile the reference is in. None if it is outside of a profile
+    pub profile: Option<String>,
+    pub group_id: Option<String>,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    element: Element,
+}
+impl PomEditor {
+    /// Resolves the expressions that are commonly used for the group id of an artifact
+    ///
+    /// `${project.groupId}`, `${pom.groupId}` and `${groupId}` are the group id of the project. Which falls back to the group id of the `<parent>`.
+    /// `${project.parent.groupId}` is the group id of the `<parent>` and any other `${name}` is looked up in the properties.
+    ///
+    /// None if the group id contains an expression that can not be resolved
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let editor = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <parent>
+    ///     <groupId>dev.example</groupId>
+    ///     <artifactId>parent</artifactId>
+    ///   </parent>
+    ///   <properties>
+    ///     <tools.group>dev.example.tools</tools.group>
+    ///   </properties>
+    /// </project>"#).unwrap();
+    /// assert_eq!(editor.resolve_group_id("${pom.groupId}").as_deref(), Some("dev.example"));
+    /// assert_eq!(editor.resolve_group_id("${tools.group}").as_deref(), Some("dev.example.tools"));
+    /// assert_eq!(editor.resolve_group_id("${missing}"), None);
+    /// assert_eq!(editor.resolve_group_id("org.slf4j").as_deref(), Some("org.slf4j"));
+    /// ```
+    pub fn resolve_group_id(&self, group_id: &str) -> Option<String> {
+        let parent_group_id = || {
+            self.get_parent()
+                .ok()
+                .flatten()
+                .and_then(|parent| parent.group_id)
+        };
+        let resolved = match group_id {
+            "${project.groupId}" | "${pom.groupId}" | "${groupId}" => {
+                self.get_group_id().or_else(parent_group_id)?
+            }
+            "${project.parent.groupId}" => parent_group_id()?,
+            _ => match group_id
+                .strip_prefix("${")
+                .and_then(|name| name.strip_suffix('}'))
+            {
+                Some(name) => self.get_property(name)?,
+                None => group_id.to_owned(),
+            },
+        };
+        Some(resolved.trim().to_owned())
+    }
+    /// Every artifact the pom references. Including the ones inside of profiles
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::{ArtifactLocation, PomEditor};
+    /// let xml = r#"
+    /// <project>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>33.0.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>
+    /// "#;
+    /// let editor = PomEditor::load_from_str(xml).unwrap();
+    /// let references = editor.get_artifact_references();
+    /// assert_eq!(references[0].location, ArtifactLocation::Dependency);
+    /// assert_eq!(references[0].version.as_deref(), Some("33.0.0-jre"));
+    /// ```
+    pub fn get_artifact_references(&self) -> Vec<ArtifactReference> {
+        let root = self.root();
+        let mut result = Vec::new();
+        if let Some(parent) = root.find(&self.document, "parent") {
+            result.extend(reference(
+                &self.document,
+                parent,
+                ArtifactLocation::Parent,
+                None,
+            ));
+        }
+        collect_references(&self.document, root, None, &mut result);
+        if let Some(profiles) = root.find(&self.document, "profiles") {
+            for profile in profiles.child_elements(&self.document) {
+                let id = profile
+                    .find(&self.document, "id")
+                    .map(|id| text_value(id, &self.document));
+                collect_references(&self.document, profile, id, &mut result);
+            }
+        }
+        result
+    }
+    /// Sets the version of a reference returned by [PomEditor::get_artifact_references].
+    ///
+    /// Only the text of the `<version>` is changed. If the reference has no version one is added.
+    pub fn set_artifact_reference_version(
+        &mut self,
+        reference: &ArtifactReference,
+        version: impl Into<String>,
+    ) -> Result<(), XMLEditorError> {
+        let version = version.into();
+        match reference.element.find(&self.document, "version") {
+            Some(element) => {
+                if text_value(element, &self.document) != version {
+                    element.set_text_content(&mut self.document, version);
+                }
+            }
+            None => {
+                let element = Element::new(&mut self.document, "version");
+                element.set_text_content(&mut self.document, version);
+                reference.element.push_child(&mut self.document, element)?;
+            }
+        }
+        Ok(())
+    }
+}
+fn collect_references(
+    document: &Document,
+    container: Element,
+    profile: Option<String>,
+    result: &mut Vec<ArtifactReference>,
+) {
+    for (location, path) in LOCATIONS {
+        let mut elements = vec![container];
+        for name in path {
+            elements = elements
+                .into_iter()
+                .flat_map(|element| element.find_all(document, name))
+                .collect();
+        }
+        result.extend(
+            elements
+                .into_iter()
+                .filter_map(|element| reference(document, element, location, profile.clone())),
+        );
+    }
+}
+fn reference(
+    document: &Document,
+    element: Element,
+    location: ArtifactLocation,
+    profile: Option<String>,
+) -> Option<ArtifactReference> {
+    let text = |name: &str| {
+        element
+            .find(document, name)
+            .map(|child| text_value(child, document))
+    };
+    Some(ArtifactReference {
+        location,
+        profile,
+        group_id: text("groupId"),
+        artifact_id: text("artifactId")?,
+        version: text("version"),
+        element,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn references() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(
+            r#"<project>
+    <parent>
+        <groupId>com.example</groupId>
+        <artifactId>parent</artifactId>
+        <version>1.0</version>
+    </parent>
+    <dependencyManagement>
+        <dependencies>
+            <dependency>
+                <groupId>com.example</groupId>
+                <artifactId>bom</artifactId>
+                <version>2.0</version>
+            </dependency>
+        </dependencies>
+    </dependencyManagement>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-jar-plugin</artifactId>
+                <dependencies>
+                    <dependency>
+                        <groupId>com.example</groupId>
+                        <artifactId>support</artifactId>
+                        <!-- Managed by the parent -->
+                    </dependency>
+                </dependencies>
+            </plugin>
+        </plugins>
+    </build>
+    <profiles>
+        <profile>
+            <id>extra</id>
+            <dependencies>
+                <dependency>
+                    <groupId>com.example</groupId>
+                    <artifactId>extra</artifactId>
+                    <version>${extra.version}</version>
+                </dependency>
+            </dependencies>
+        </profile>
+    </profiles>
+</project>"#,
+        )?;
+        let references = editor.get_artifact_references();
+        let summary: Vec<_> = references
+            .iter()
+            .map(|reference| {
+                (
+                    reference.location,
+                    reference.profile.as_deref(),
+                    reference.artifact_id.as_str(),
+                    reference.version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ArtifactLocation::Parent, None, "parent", Some("1.0")),
+                (
+                    ArtifactLocation::ManagedDependency,
+                    None,
+                    "bom",
+                    Some("2.0")
+                ),
+                (ArtifactLocation::Plugin, None, "maven-jar-plugin", None),
+                (ArtifactLocation::PluginDependency, None, "support", None),
+                (
+                    ArtifactLocation::Dependency,
+                    Some("extra"),
+                    "extra",
+                    Some("${extra.version}")
+                ),
+            ]
+        );
+
+        editor.set_artifact_reference_version(&references[1], "2.1")?;
+        editor.set_artifact_reference_version(&references[3], "1.1")?;
+        let references = editor.get_artifact_references();
+        assert_eq!(references[1].version.as_deref(), Some("2.1"));
+        assert_eq!(references[3].version.as_deref(), Some("1.1"));
+        assert!(
+            editor
+                .write_to_str()?
+                .contains("<!-- Managed by the parent -->")
+        );
+        Ok(())
+    }
+}
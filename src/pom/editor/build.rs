@@ -1,12 +1,11 @@
 use edit_xml::Element;
 
 use crate::{
-    editor::XMLEditorError,
-    editor::utils::{add_or_update_item, get_all_children_of_element},
-    pom::build::Plugin,
+    editor::{ElementConverter, XMLEditorError, utils::text_value},
+    pom::build::{Build, Plugin},
 };
 
-use super::PomEditor;
+use super::{PomEditor, list_item_methods};
 impl PomEditor {
     /// Creates a new build editor
     ///
@@ -26,6 +25,16 @@ impl PomEditor {
         }
         None
     }
+    /// Gets the build section of the pom file
+    pub fn get_build(&self) -> Result<Option<Build>, XMLEditorError> {
+        self.root()
+            .find(&self.document, "build")
+            .map(|element| {
+                Build::from_element(element, &self.document)
+                    .map_err(|error| self.spans.locate(&self.document, element, error))
+            })
+            .transpose()
+    }
     pub fn has_build(&self) -> bool {
         let root = self.root();
         root.find(&self.document, "build").is_some()
@@ -61,7 +70,7 @@ macro_rules! top_level_getter_setter {
         }
         pub fn $get(&self) -> Option<String> {
             let element = self.build_element.find(&self.parent.document, $name);
-            return element.map(|x| x.text_content(&self.parent.document));
+            return element.map(|x| text_value(x, &self.parent.document));
         }
     };
 }
@@ -77,37 +86,38 @@ impl<'a> BuildEditor<'a> {
 
     pub(super) fn new(parent: &'a mut PomEditor) -> Self {
         let root = parent.root();
+        Self::new_in(parent, root)
+    }
+    /// `container` is either the project or a profile
+    pub(super) fn new_in(parent: &'a mut PomEditor, container: Element) -> Self {
         let build_element = crate::editor::utils::get_or_create_top_level_element(
             "build",
             &mut parent.document,
-            root,
+            container,
         );
         Self {
             parent,
             build_element,
         }
     }
-    /// Gets all the plugins in the build section
-    pub fn get_plugins(&self) -> Result<Vec<Plugin>, XMLEditorError> {
-        let Some(plugins) = self.build_element.find(&self.parent.document, "plugins") else {
-            return Ok(vec![]);
-        };
-        let result = get_all_children_of_element::<Plugin>(&self.parent.document, plugins)?;
-        Ok(result.into_iter().map(|(depend, _)| depend).collect())
-    }
-    /// Adds or updates a plugin in the build section
-    pub fn add_or_update_plugin(
-        &mut self,
-        plugin: Plugin,
-    ) -> Result<Option<Plugin>, XMLEditorError> {
-        let plugins = self.build_element.find(&self.parent.document, "plugins");
-        add_or_update_item(
-            &mut self.parent.document,
-            plugins,
-            self.build_element,
-            plugin,
-        )
-    }
+    list_item_methods!(
+        in build_element,
+        /// Gets all the plugins in the build section
+        get: get_plugins,
+        /// Finds the plugin with the same group id and artifact id
+        find: find_plugin,
+        /// Adds or updates a plugin in the build section
+        add: add_or_update_plugin,
+        /// Modifies the plugin with the same group id and artifact id where it is in the file
+        ///
+        /// Returns the modified plugin. None if it was not found
+        modify: modify_plugin,
+        /// Removes the plugin with the same group id and artifact id
+        remove: remove_plugin,
+        /// Clears all the plugins in the build section
+        clear: clear_plugins,
+        "plugins" => Plugin
+    );
 }
 
 #[cfg(test)]
@@ -128,11 +138,23 @@ mod tests {
                 group_id: Some("org.apache.maven.plugins".to_string()),
                 artifact_id: "maven-compiler-plugin".to_string(),
                 version: Some(Property::Literal("3.8.1".to_string())),
+                ..Default::default()
             };
             build_editor.add_or_update_plugin(plugin.clone())?;
             let plugins = build_editor.get_plugins()?;
             assert_eq!(plugins.len(), 1);
             assert_eq!(plugins[0], plugin);
+
+            let modified = build_editor.modify_plugin(&plugin, |plugin| {
+                plugin.version = Some(Property::Literal("3.13.0".to_string()));
+            })?;
+            assert_eq!(
+                modified.and_then(|plugin| plugin.version),
+                Some(Property::Literal("3.13.0".to_string()))
+            );
+            assert!(build_editor.find_plugin(&plugin)?.is_some());
+            assert!(build_editor.remove_plugin(&plugin)?.is_some());
+            assert!(build_editor.find_plugin(&plugin)?.is_none());
         }
         let value = editor.write_to_str()?;
         println!("{}", value);
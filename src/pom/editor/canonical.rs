@@ -0,0 +1,434 @@
+use edit_xml::{Document, Element, Node};
+
+use crate::{
+    editor::{XMLEditorError, utils::text_value},
+    pom::DEFAULT_PLUGIN_GROUP_ID,
+};
+
+use super::PomEditor;
+
+/// The order of the top level elements recommended by the [Maven POM Code Convention](https://maven.apache.org/developers/conventions/code.html#pom-code-convention)
+///
+/// Elements that are not in the list are placed at the end in the order they were found.
+pub static CANONICAL_ELEMENT_ORDER: [&str; 29] = [
+    "modelVersion",
+    "parent",
+    "groupId",
+    "artifactId",
+    "version",
+    "packaging",
+    "name",
+    "description",
+    "url",
+    "inceptionYear",
+    "organization",
+    "licenses",
+    "developers",
+    "contributors",
+    "mailingLists",
+    "prerequisites",
+    "modules",
+    "scm",
+    "issueManagement",
+    "ciManagement",
+    "distributionManagement",
+    "properties",
+    "dependencyManagement",
+    "dependencies",
+    "repositories",
+    "pluginRepositories",
+    "build",
+    "reporting",
+    "profiles",
+];
+/// The order dependencies are sorted in by scope. A dependency without a scope is `compile`
+pub static CANONICAL_SCOPE_ORDER: [&str; 6] =
+    ["import", "compile", "provided", "runtime", "system", "test"];
+/// What [PomEditor::canonicalize] changes besides the order of the top level elements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalizeOptions {
+    /// Sort dependencies by scope then `groupId:artifactId`.
+    ///
+    /// Includes `<dependencyManagement>` and the dependencies of profiles
+    pub sort_dependencies: bool,
+    /// Sort plugins by `groupId:artifactId`.
+    ///
+    /// Includes `<pluginManagement>` and the plugins of profiles
+    pub sort_plugins: bool,
+}
+/// The key a list of elements is sorted by
+type SortKey = (usize, String, String);
+type SortBy = fn(&Document, Element) -> SortKey;
+impl PomEditor {
+    /// Reorders the pom into its canonical form.
+    ///
+    /// Comments move with the element that follows them. The indentation is normalized to the `ident_level` of the editor.
+    /// Blank lines between elements are kept.
+    ///
+    /// Returns true if anything was moved or re-indented
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::{CanonicalizeOptions, PomEditor};
+    /// let mut editor = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <version>1.0</version>
+    ///   <!-- The id -->
+    ///   <artifactId>example</artifactId>
+    /// </project>"#).unwrap();
+    /// assert!(!editor.is_canonical(CanonicalizeOptions::default()));
+    /// assert!(editor.canonicalize(CanonicalizeOptions::default()).unwrap());
+    /// assert!(editor.is_canonical(CanonicalizeOptions::default()));
+    /// let written = editor.write_to_str().unwrap();
+    /// assert!(written.find("<!-- The id -->").unwrap() < written.find("<version>").unwrap());
+    /// ```
+    pub fn canonicalize(&mut self, options: CanonicalizeOptions) -> Result<bool, XMLEditorError> {
+        let mut changed = false;
+        for (element, sort) in self.canonical_targets(options) {
+            changed |= reorder_children(&mut self.document, element, sort)?;
+        }
+        let indent = (self.format.indent_char as char)
+            .to_string()
+            .repeat(self.ident_level);
+        let root = self.root();
+        changed |= reindent(&mut self.document, root, "", &indent)?;
+        Ok(changed)
+    }
+    /// Checks if [PomEditor::canonicalize] would not move anything. The indentation is not checked
+    pub fn is_canonical(&self, options: CanonicalizeOptions) -> bool {
+        self.canonical_targets(options)
+            .into_iter()
+            .all(|(element, sort)| {
+                let keys: Vec<_> = groups(&self.document, element)
+                    .0
+                    .iter()
+                    .map(|(element, _)| sort(&self.document, *element))
+                    .collect();
+                keys.is_sorted()
+            })
+    }
+    /// Checks if the file is already canonical. Including the indentation.
+    ///
+    /// Nothing is written. Use this to fail a build when a pom is not canonical
+    pub fn check_canonical_str(
+        content: &str,
+        options: CanonicalizeOptions,
+    ) -> Result<bool, XMLEditorError> {
+        let mut editor = Self::load_from_str(content)?;
+        if editor.canonicalize(options)? {
+            return Ok(false);
+        }
+        Ok(editor.write_to_str()? == content)
+    }
+    /// The elements that have their children sorted and how they are sorted
+    fn canonical_targets(&self, options: CanonicalizeOptions) -> Vec<(Element, SortBy)> {
+        let root = self.root();
+        let mut result: Vec<(Element, SortBy)> = vec![(root, top_level_key)];
+        let mut containers = vec![root];
+        if let Some(profiles) = root.find(&self.document, "profiles") {
+            containers.extend(profiles.child_elements(&self.document));
+        }
+        let find_path = |element: Element, path: &[&str]| {
+            path.iter()
+                .try_fold(element, |element, name| element.find(&self.document, name))
+        };
+        for container in containers {
+            if options.sort_dependencies {
+                for path in [
+                    &["dependencies"][..],
+                    &["dependencyManagement", "dependencies"],
+                ] {
+                    if let Some(element) = find_path(container, path) {
+                        result.push((element, dependency_key));
+                    }
+                }
+            }
+            if options.sort_plugins {
+                for path in [
+                    &["build", "plugins"][..],
+                    &["build", "pluginManagement", "plugins"],
+                ] {
+                    if let Some(element) = find_path(container, path) {
+                        result.push((element, plugin_key));
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+fn top_level_key(document: &Document, element: Element) -> SortKey {
+    let name = element.name(document);
+    let position = CANONICAL_ELEMENT_ORDER
+        .iter()
+        .position(|known| *known == name)
+        .unwrap_or(CANONICAL_ELEMENT_ORDER.len());
+    // Unknown elements keep their order as the sort is stable
+    (position, String::new(), String::new())
+}
+fn dependency_key(document: &Document, element: Element) -> SortKey {
+    let text = |name: &str| {
+        element
+            .find(document, name)
+            .map(|child| text_value(child, document))
+    };
+    let scope = text("scope").unwrap_or_else(|| "compile".to_owned());
+    let position = CANONICAL_SCOPE_ORDER
+        .iter()
+        .position(|known| *known == scope)
+        .unwrap_or(CANONICAL_SCOPE_ORDER.len());
+    (
+        position,
+        text("groupId").unwrap_or_default(),
+        text("artifactId").unwrap_or_default(),
+    )
+}
+fn plugin_key(document: &Document, element: Element) -> SortKey {
+    let text = |name: &str| {
+        element
+            .find(document, name)
+            .map(|child| text_value(child, document))
+    };
+    (
+        0,
+        text("groupId").unwrap_or_else(|| DEFAULT_PLUGIN_GROUP_ID.to_owned()),
+        text("artifactId").unwrap_or_default(),
+    )
+}
+/// Splits the children of `element` into each child element and the nodes before it.
+///
+/// The nodes after the last element are returned separately
+fn groups(document: &Document, element: Element) -> (Vec<(Element, Vec<Node>)>, Vec<Node>) {
+    let mut result = Vec::new();
+    let mut pending = Vec::new();
+    for node in element.children(document) {
+        match node {
+            Node::Element(child) => {
+                pending.push(node.clone());
+                result.push((*child, std::mem::take(&mut pending)));
+            }
+            other => pending.push(other.clone()),
+        }
+    }
+    (result, pending)
+}
+/// Replaces the whitespace between the children of `element` with a line break and `indent` for each level.
+///
+/// Elements that only contain text or contain text next to elements are not changed. Returns true if anything changed
+fn reindent(
+    document: &mut Document,
+    element: Element,
+    current: &str,
+    indent: &str,
+) -> Result<bool, XMLEditorError> {
+    let children = element.children(document).clone();
+    let is_whitespace = |node: &Node| matches!(node, Node::Text(text) if text.trim().is_empty());
+    let has_text = children
+        .iter()
+        .any(|node| matches!(node, Node::Text(_) | Node::CData(_)) && !is_whitespace(node));
+    let mut changed = false;
+    let child_indent = format!("{current}{indent}");
+    if !has_text && !children.is_empty() {
+        let line_breaks = |whitespace: &[&Node]| {
+            let count = whitespace
+                .iter()
+                .map(|node| node.text_content(document).matches('\n').count())
+                .sum::<usize>();
+            "\n".repeat(count.max(1))
+        };
+        let mut result = Vec::with_capacity(children.len() * 2 + 1);
+        let mut whitespace = Vec::new();
+        for node in &children {
+            if is_whitespace(node) {
+                whitespace.push(node);
+                continue;
+            }
+            let breaks = line_breaks(&whitespace);
+            result.push(Node::Text(format!("{breaks}{child_indent}")));
+            result.push(node.clone());
+            whitespace.clear();
+        }
+        result.push(Node::Text(format!("\n{current}")));
+        if result != children {
+            element.clear_children(document);
+            for node in result {
+                element.push_child(document, node)?;
+            }
+            changed = true;
+        }
+    }
+    for child in element.child_elements(document) {
+        changed |= reindent(document, child, &child_indent, indent)?;
+    }
+    Ok(changed)
+}
+/// Stable sorts the child elements of `element`. Returns true if the order changed
+fn reorder_children(
+    document: &mut Document,
+    element: Element,
+    sort: SortBy,
+) -> Result<bool, XMLEditorError> {
+    let (groups, trailing) = groups(document, element);
+    let mut keyed: Vec<_> = groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, (child, nodes))| (sort(document, child), index, nodes))
+        .collect();
+    keyed.sort_by(|(left, _, _), (right, _, _)| left.cmp(right));
+    if keyed
+        .iter()
+        .enumerate()
+        .all(|(index, (_, original, _))| index == *original)
+    {
+        return Ok(false);
+    }
+    element.clear_children(document);
+    for node in keyed
+        .into_iter()
+        .flat_map(|(_, _, nodes)| nodes)
+        .chain(trailing)
+    {
+        element.push_child(document, node)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const POM: &str = r#"<project>
+    <dependencies>
+        <dependency>
+            <groupId>org.junit.jupiter</groupId>
+            <artifactId>junit-jupiter</artifactId>
+            <scope>test</scope>
+        </dependency>
+        <!-- Logging -->
+        <dependency>
+            <groupId>org.slf4j</groupId>
+            <artifactId>slf4j-api</artifactId>
+        </dependency>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+        </dependency>
+    </dependencies>
+    <version>1.0</version>
+    <!-- Identity -->
+    <artifactId>example</artifactId>
+    <groupId>com.example</groupId>
+    <modelVersion>4.0.0</modelVersion>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-surefire-plugin</artifactId>
+            </plugin>
+            <plugin>
+                <groupId>com.diffplug.spotless</groupId>
+                <artifactId>spotless-maven-plugin</artifactId>
+            </plugin>
+        </plugins>
+    </build>
+    <custom>kept last</custom>
+</project>"#;
+
+    #[test]
+    fn reindent() -> anyhow::Result<()> {
+        let source = "<project>\n<modelVersion>4.0.0</modelVersion>\n\n        <artifactId>x</artifactId>\n  <build><plugins>\n<plugin><artifactId>p</artifactId></plugin></plugins>\n    </build>\n</project>\n";
+        let expected = "<project>\n    <modelVersion>4.0.0</modelVersion>\n\n    <artifactId>x</artifactId>\n    <build>\n        <plugins>\n            <plugin>\n                <artifactId>p</artifactId>\n            </plugin>\n        </plugins>\n    </build>\n</project>\n";
+        let options = CanonicalizeOptions::default();
+        assert!(!PomEditor::check_canonical_str(source, options)?);
+
+        let mut editor = PomEditor::load_from_str(source)?;
+        assert_eq!(editor.ident_level, 4);
+        assert!(editor.is_canonical(options));
+        assert!(editor.canonicalize(options)?);
+        assert_eq!(editor.write_to_str()?, expected);
+        assert!(PomEditor::check_canonical_str(expected, options)?);
+        Ok(())
+    }
+
+    fn element_names(editor: &PomEditor, element: Element) -> Vec<String> {
+        element
+            .child_elements(&editor.document)
+            .into_iter()
+            .map(|child| {
+                child
+                    .find(&editor.document, "artifactId")
+                    .map(|id| text_value(id, &editor.document))
+                    .unwrap_or_else(|| child.name(&editor.document).to_owned())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn top_level_only() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        assert!(editor.canonicalize(CanonicalizeOptions::default())?);
+        assert_eq!(
+            element_names(&editor, editor.root()),
+            vec![
+                "modelVersion",
+                "groupId",
+                "artifactId",
+                "version",
+                "dependencies",
+                "build",
+                "custom"
+            ]
+        );
+        let dependencies = editor
+            .root()
+            .find(&editor.document, "dependencies")
+            .unwrap();
+        assert_eq!(
+            element_names(&editor, dependencies),
+            vec!["junit-jupiter", "slf4j-api", "guava"]
+        );
+        let written = editor.write_to_str()?;
+        let comment = written.find("<!-- Identity -->").unwrap();
+        assert!(
+            written[comment..]
+                .trim_start_matches("<!-- Identity -->")
+                .trim_start()
+                .starts_with("<artifactId>example")
+        );
+        assert!(!editor.canonicalize(CanonicalizeOptions::default())?);
+        Ok(())
+    }
+
+    #[test]
+    fn sort_lists() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let options = CanonicalizeOptions {
+            sort_dependencies: true,
+            sort_plugins: true,
+        };
+        assert!(!PomEditor::check_canonical_str(POM, options)?);
+        editor.canonicalize(options)?;
+        let root = editor.root();
+        let dependencies = root.find(&editor.document, "dependencies").unwrap();
+        assert_eq!(
+            element_names(&editor, dependencies),
+            vec!["guava", "slf4j-api", "junit-jupiter"]
+        );
+        let plugins = root
+            .find(&editor.document, "build")
+            .and_then(|build| build.find(&editor.document, "plugins"))
+            .unwrap();
+        assert_eq!(
+            element_names(&editor, plugins),
+            vec!["spotless-maven-plugin", "maven-surefire-plugin"]
+        );
+        let written = editor.write_to_str()?;
+        let comment = written.find("<!-- Logging -->").unwrap();
+        let next_group = written[comment..].find("<groupId>").unwrap() + comment;
+        assert!(written[next_group..].starts_with("<groupId>org.slf4j</groupId>"));
+
+        assert!(editor.is_canonical(options));
+        assert!(PomEditor::check_canonical_str(&written, options)?);
+        Ok(())
+    }
+}
@@ -1,14 +1,8 @@
 use edit_xml::Element;
 
-use crate::{
-    editor::{
-        XMLEditorError,
-        utils::{add_or_update_item, get_all_children_of_element},
-    },
-    pom::Dependency,
-};
+use crate::{editor::XMLEditorError, pom::Dependency};
 
-use super::PomEditor;
+use super::{PomEditor, list_item_methods};
 impl PomEditor {
     /// Creates a new [DependencyManagementEditor]
     ///
@@ -27,7 +21,7 @@ impl PomEditor {
     pub fn get_dependency_management_element_or_none(
         &mut self,
     ) -> Option<DependencyManagementEditor<'_>> {
-        if self.has_build() {
+        if self.has_dependency_management() {
             return Some(DependencyManagementEditor::new(self));
         }
         None
@@ -62,40 +56,38 @@ pub struct DependencyManagementEditor<'a> {
 impl<'a> DependencyManagementEditor<'a> {
     pub(super) fn new(parent: &'a mut PomEditor) -> Self {
         let root = parent.root();
+        Self::new_in(parent, root)
+    }
+    /// `container` is either the project or a profile
+    pub(super) fn new_in(parent: &'a mut PomEditor, container: Element) -> Self {
         let build_element = crate::editor::utils::get_or_create_top_level_element(
             "dependencyManagement",
             &mut parent.document,
-            root,
+            container,
         );
         Self {
             parent,
             dependency_management_element: build_element,
         }
     }
-    fn dependencies_element(&self) -> Option<Element> {
-        self.dependency_management_element
-            .find(&self.parent.document, "dependencies")
-    }
-    pub fn get_dependencies(&self) -> Result<Vec<Dependency>, XMLEditorError> {
-        let Some(dependencies_element) = self.dependencies_element() else {
-            return Ok(vec![]);
-        };
-        let result =
-            get_all_children_of_element::<Dependency>(&self.parent.document, dependencies_element)?;
-        Ok(result.into_iter().map(|(depend, _)| depend).collect())
-    }
-    pub fn add_or_update_dependency(
-        &mut self,
-        dependency: Dependency,
-    ) -> Result<Option<Dependency>, XMLEditorError> {
-        let dependencies_element = self.dependencies_element();
-        add_or_update_item(
-            &mut self.parent.document,
-            dependencies_element,
-            self.dependency_management_element,
-            dependency,
-        )
-    }
+    list_item_methods!(
+        in dependency_management_element,
+        /// Gets all the managed dependencies
+        get: get_dependencies,
+        /// Finds the managed dependency with the same group id and artifact id
+        find: find_dependency,
+        /// Adds or updates a managed dependency
+        add: add_or_update_dependency,
+        /// Modifies the managed dependency with the same group id and artifact id where it is in the file
+        ///
+        /// Returns the modified dependency. None if it was not found
+        modify: modify_dependency,
+        /// Removes the managed dependency with the same group id and artifact id
+        remove: remove_dependency,
+        /// Clears all the managed dependencies
+        clear: clear_dependencies,
+        "dependencies" => Dependency
+    );
 }
 
 #[cfg(test)]
@@ -144,6 +136,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn edit_managed_dependencies() -> anyhow::Result<()> {
+        let xml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data/test_poms/lwjgl-bom-3.3.4.pom");
+        let file = std::fs::read_to_string(xml_path)?;
+        let mut editor = PomEditor::load_from_str(&file)?;
+        let mut dependency_management = editor
+            .get_dependency_management_element_or_none()
+            .expect("Should have dependency management");
+        let count = dependency_management.get_dependencies()?.len();
+        let first = dependency_management.get_dependencies()?.remove(0);
+
+        let found = dependency_management.find_dependency(&first)?;
+        assert_eq!(found.as_ref(), Some(&first));
+        let modified = dependency_management.modify_dependency(&first, |dependency| {
+            dependency.scope = Some("import".to_owned());
+        })?;
+        assert_eq!(modified.and_then(|d| d.scope), Some("import".to_owned()));
+        assert_eq!(
+            dependency_management.get_dependencies()?[0].scope,
+            Some("import".to_owned())
+        );
+
+        assert!(dependency_management.remove_dependency(&first)?.is_some());
+        assert_eq!(dependency_management.get_dependencies()?.len(), count - 1);
+        dependency_management.clear_dependencies()?;
+        assert!(dependency_management.get_dependencies()?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn delete_dependency_management() -> anyhow::Result<()> {
         let xml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
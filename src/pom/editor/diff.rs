@@ -0,0 +1,438 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    editor::XMLEditorError,
+    pom::{DEFAULT_PLUGIN_GROUP_ID, Dependency, Parent},
+};
+
+use super::PomEditor;
+
+/// How a single value differs between two poms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ValueChange<T> {
+    Added { new: T },
+    Removed { old: T },
+    Changed { old: T, new: T },
+}
+impl<T: PartialEq> ValueChange<T> {
+    /// None if both are the same
+    pub fn between(old: Option<T>, new: Option<T>) -> Option<Self> {
+        match (old, new) {
+            (None, None) => None,
+            (None, Some(new)) => Some(Self::Added { new }),
+            (Some(old), None) => Some(Self::Removed { old }),
+            (Some(old), Some(new)) if old == new => None,
+            (Some(old), Some(new)) => Some(Self::Changed { old, new }),
+        }
+    }
+}
+impl<T: fmt::Display> fmt::Display for ValueChange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueChange::Added { new } => write!(f, "added {new}"),
+            ValueChange::Removed { old } => write!(f, "removed {old}"),
+            ValueChange::Changed { old, new } => write!(f, "{old} → {new}"),
+        }
+    }
+}
+/// The parts of a dependency that are compared
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyVersion {
+    pub version: Option<String>,
+    pub scope: Option<String>,
+}
+impl From<&Dependency> for DependencyVersion {
+    fn from(dependency: &Dependency) -> Self {
+        Self {
+            version: dependency.version.as_ref().map(ToString::to_string),
+            scope: dependency.scope.clone(),
+        }
+    }
+}
+impl fmt::Display for DependencyVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.version.as_deref().unwrap_or("(managed)"))?;
+        if let Some(scope) = &self.scope {
+            write!(f, " ({scope})")?;
+        }
+        Ok(())
+    }
+}
+/// The version of a plugin. None if it is managed by the parent or `<pluginManagement>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginVersion {
+    pub version: Option<String>,
+}
+impl fmt::Display for PluginVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.version.as_deref().unwrap_or("(managed)"))
+    }
+}
+/// A dependency or plugin that was added, removed or changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArtifactDiff<T> {
+    pub group_id: String,
+    pub artifact_id: String,
+    #[serde(flatten)]
+    pub change: ValueChange<T>,
+}
+/// A property or repository that was added, removed or changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NamedDiff {
+    pub name: String,
+    #[serde(flatten)]
+    pub change: ValueChange<String>,
+}
+/// The semantic difference between two poms. Created with [PomEditor::diff]
+///
+/// The [fmt::Display] implementation writes one line per change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PomDiff {
+    /// The parent as `groupId:artifactId:version`
+    pub parent: Option<ValueChange<String>>,
+    pub packaging: Option<ValueChange<String>>,
+    pub dependencies: Vec<ArtifactDiff<DependencyVersion>>,
+    /// Plugins in `<build><plugins>`
+    pub plugins: Vec<ArtifactDiff<PluginVersion>>,
+    pub properties: Vec<NamedDiff>,
+    /// Repositories by id. The value is the url of the repository
+    pub repositories: Vec<NamedDiff>,
+}
+impl PomDiff {
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_none()
+            && self.packaging.is_none()
+            && self.dependencies.is_empty()
+            && self.plugins.is_empty()
+            && self.properties.is_empty()
+            && self.repositories.is_empty()
+    }
+}
+impl fmt::Display for PomDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(parent) = &self.parent {
+            writeln!(f, "parent: {parent}")?;
+        }
+        if let Some(packaging) = &self.packaging {
+            writeln!(f, "packaging: {packaging}")?;
+        }
+        for dependency in &self.dependencies {
+            writeln!(
+                f,
+                "dependency {}:{}: {}",
+                dependency.group_id, dependency.artifact_id, dependency.change
+            )?;
+        }
+        for plugin in &self.plugins {
+            writeln!(
+                f,
+                "plugin {}:{}: {}",
+                plugin.group_id, plugin.artifact_id, plugin.change
+            )?;
+        }
+        for property in &self.properties {
+            writeln!(f, "property {}: {}", property.name, property.change)?;
+        }
+        for repository in &self.repositories {
+            writeln!(f, "repository {}: {}", repository.name, repository.change)?;
+        }
+        Ok(())
+    }
+}
+impl PomEditor {
+    /// Compares this pom (the old one) with `new`.
+    ///
+    /// Dependencies and plugins are matched by `groupId:artifactId`. Properties by name and repositories by id.
+    /// Values are compared as written. Properties are not resolved.
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let old = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>32.0.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#).unwrap();
+    /// let new = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>33.0.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#).unwrap();
+    /// let diff = old.diff(&new).unwrap();
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "dependency com.google.guava:guava: 32.0.0-jre → 33.0.0-jre\n"
+    /// );
+    /// ```
+    pub fn diff(&self, new: &PomEditor) -> Result<PomDiff, XMLEditorError> {
+        let parent = ValueChange::between(
+            self.get_parent()?.map(|parent| parent_coordinates(&parent)),
+            new.get_parent()?.map(|parent| parent_coordinates(&parent)),
+        );
+        let packaging = ValueChange::between(self.get_packaging(), new.get_packaging());
+
+        let dependencies = |editor: &PomEditor| -> Result<_, XMLEditorError> {
+            Ok(editor
+                .get_dependencies()?
+                .iter()
+                .map(|dependency| {
+                    (
+                        (dependency.group_id.clone(), dependency.artifact_id.clone()),
+                        DependencyVersion::from(dependency),
+                    )
+                })
+                .collect())
+        };
+        let dependencies = diff_lists(dependencies(self)?, dependencies(new)?)
+            .into_iter()
+            .map(|((group_id, artifact_id), change)| ArtifactDiff {
+                group_id,
+                artifact_id,
+                change,
+            })
+            .collect();
+
+        let plugins = |editor: &PomEditor| -> Result<_, XMLEditorError> {
+            let plugins = editor
+                .get_build()?
+                .map(|build| build.plugins.plugins)
+                .unwrap_or_default();
+            Ok(plugins
+                .into_iter()
+                .map(|plugin| {
+                    let group_id = plugin
+                        .group_id
+                        .unwrap_or_else(|| DEFAULT_PLUGIN_GROUP_ID.to_owned());
+                    let version = PluginVersion {
+                        version: plugin.version.map(|version| version.to_string()),
+                    };
+                    ((group_id, plugin.artifact_id), version)
+                })
+                .collect())
+        };
+        let plugins = diff_lists(plugins(self)?, plugins(new)?)
+            .into_iter()
+            .map(|((group_id, artifact_id), change)| ArtifactDiff {
+                group_id,
+                artifact_id,
+                change,
+            })
+            .collect();
+
+        let properties = |editor: &PomEditor| -> Result<_, XMLEditorError> {
+            Ok(editor.get_properties()?.0.into_iter().collect())
+        };
+        let properties = diff_lists(properties(self)?, properties(new)?)
+            .into_iter()
+            .map(|(name, change)| NamedDiff { name, change })
+            .collect();
+
+        let repositories = |editor: &PomEditor| -> Result<_, XMLEditorError> {
+            Ok(editor
+                .get_repositories()?
+                .into_iter()
+                .map(|repository| {
+                    let id = repository.id.unwrap_or_else(|| repository.url.clone());
+                    (id, repository.url)
+                })
+                .collect())
+        };
+        let repositories = diff_lists(repositories(self)?, repositories(new)?)
+            .into_iter()
+            .map(|(name, change)| NamedDiff { name, change })
+            .collect();
+
+        Ok(PomDiff {
+            parent,
+            packaging,
+            dependencies,
+            plugins,
+            properties,
+            repositories,
+        })
+    }
+}
+fn parent_coordinates(parent: &Parent) -> String {
+    format!(
+        "{}:{}:{}",
+        parent.group_id.as_deref().unwrap_or_default(),
+        parent.artifact_id.as_deref().unwrap_or_default(),
+        parent.version.as_deref().unwrap_or_default()
+    )
+}
+/// Compares two lists of keyed values. Changed and removed entries are in the order of `old`. Added entries follow in the order of `new`
+fn diff_lists<K: PartialEq, V: PartialEq>(
+    old: Vec<(K, V)>,
+    new: Vec<(K, V)>,
+) -> Vec<(K, ValueChange<V>)> {
+    let mut new = first_of_each(new);
+    let mut result = Vec::new();
+    for (key, old_value) in first_of_each(old) {
+        let new_value = new
+            .iter()
+            .position(|(new_key, _)| *new_key == key)
+            .map(|index| new.remove(index).1);
+        if let Some(change) = ValueChange::between(Some(old_value), new_value) {
+            result.push((key, change));
+        }
+    }
+    result.extend(
+        new.into_iter()
+            .map(|(key, new)| (key, ValueChange::Added { new })),
+    );
+    result
+}
+/// If a key is in the list multiple times only the first is kept
+fn first_of_each<K: PartialEq, V>(list: Vec<(K, V)>) -> Vec<(K, V)> {
+    let mut result: Vec<(K, V)> = Vec::new();
+    for (key, value) in list {
+        if !result.iter().any(|(existing, _)| *existing == key) {
+            result.push((key, value));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const OLD: &str = r#"<project>
+    <parent>
+        <groupId>com.example</groupId>
+        <artifactId>parent</artifactId>
+        <version>1.0</version>
+    </parent>
+    <artifactId>app</artifactId>
+    <packaging>jar</packaging>
+    <properties>
+        <java.version>17</java.version>
+        <encoding>UTF-8</encoding>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>32.0.0-jre</version>
+        </dependency>
+        <dependency>
+            <groupId>junit</groupId>
+            <artifactId>junit</artifactId>
+            <version>4.13.2</version>
+            <scope>test</scope>
+        </dependency>
+    </dependencies>
+    <repositories>
+        <repository>
+            <id>central</id>
+            <url>https://repo1.maven.org/maven2</url>
+        </repository>
+    </repositories>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.11.0</version>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+    const NEW: &str = r#"<project>
+    <parent>
+        <groupId>com.example</groupId>
+        <artifactId>parent</artifactId>
+        <version>2.0</version>
+    </parent>
+    <artifactId>app</artifactId>
+    <packaging>war</packaging>
+    <properties>
+        <java.version>21</java.version>
+        <encoding>UTF-8</encoding>
+        <skipTests>false</skipTests>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>33.0.0-jre</version>
+        </dependency>
+        <dependency>
+            <groupId>org.junit.jupiter</groupId>
+            <artifactId>junit-jupiter</artifactId>
+            <version>5.11.0</version>
+            <scope>test</scope>
+        </dependency>
+    </dependencies>
+    <repositories>
+        <repository>
+            <id>central</id>
+            <url>https://repo.maven.apache.org/maven2</url>
+        </repository>
+    </repositories>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.13.0</version>
+            </plugin>
+            <plugin>
+                <artifactId>maven-surefire-plugin</artifactId>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+
+    #[test]
+    fn diff() -> anyhow::Result<()> {
+        let old = PomEditor::load_from_str(OLD)?;
+        let new = PomEditor::load_from_str(NEW)?;
+        let diff = old.diff(&new)?;
+        assert_eq!(
+            diff.dependencies[0],
+            ArtifactDiff {
+                group_id: "com.google.guava".to_owned(),
+                artifact_id: "guava".to_owned(),
+                change: ValueChange::Changed {
+                    old: DependencyVersion {
+                        version: Some("32.0.0-jre".to_owned()),
+                        scope: None,
+                    },
+                    new: DependencyVersion {
+                        version: Some("33.0.0-jre".to_owned()),
+                        scope: None,
+                    },
+                },
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "parent: com.example:parent:1.0 → com.example:parent:2.0
+packaging: jar → war
+dependency com.google.guava:guava: 32.0.0-jre → 33.0.0-jre
+dependency junit:junit: removed 4.13.2 (test)
+dependency org.junit.jupiter:junit-jupiter: added 5.11.0 (test)
+plugin org.apache.maven.plugins:maven-compiler-plugin: 3.11.0 → 3.13.0
+plugin org.apache.maven.plugins:maven-surefire-plugin: added (managed)
+property java.version: 17 → 21
+property skipTests: added false
+repository central: https://repo1.maven.org/maven2 → https://repo.maven.apache.org/maven2
+"
+        );
+        assert!(old.diff(&PomEditor::load_from_str(OLD)?)?.is_empty());
+        Ok(())
+    }
+}
@@ -60,7 +60,10 @@ macro_rules! top_level_structured_type {
         $(#[$get_docs])*
         pub fn $get(&self) -> Result<Option<$structured_type>, XMLEditorError> {
             self.element.find(&self.parent.document, $element_name)
-                .map(|x| $structured_type::from_element(x, &self.parent.document))
+                .map(|x| {
+                    $structured_type::from_element(x, &self.parent.document)
+                        .map_err(|error| self.parent.spans.locate(&self.parent.document, x, error))
+                })
                 .transpose()
         }
         $(#[$set_docs])*
@@ -0,0 +1,609 @@
+use edit_xml::{Document, Element};
+
+use crate::{
+    editor::{
+        ComparableElement, ElementConverter, HasElementName, UpdatableElement, XMLEditorError,
+        utils::{
+            MissingElementError, create_basic_text_element, get_all_children_of_element,
+            get_all_children_of_element_located, get_or_create_top_level_element, sync_element,
+            text_value,
+        },
+    },
+    pom::{
+        Activation, Build, Dependencies, DependencyManagement, Extension, Modules, Plugin,
+        PluginRepositories, PluginRepository, Plugins, Pom, Profile, Profiles, Properties,
+        Repositories,
+    },
+};
+
+use super::{PomEditor, modules};
+
+impl PomEditor {
+    /// Reads the whole document into a [Pom]
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let editor = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <groupId>com.example</groupId>
+    ///   <artifactId>example</artifactId>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>33.0.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#).unwrap();
+    /// let pom = editor.to_model().unwrap();
+    /// assert_eq!(pom.artifact_id, "example");
+    /// assert_eq!(pom.dependencies.dependencies[0].artifact_id, "guava");
+    /// ```
+    pub fn to_model(&self) -> Result<Pom, XMLEditorError> {
+        let root = self.root();
+        let artifact_id = root
+            .find(&self.document, "artifactId")
+            .map(|element| text_value(element, &self.document))
+            .ok_or_else(|| {
+                self.spans.locate(
+                    &self.document,
+                    root,
+                    MissingElementError("artifactId").into(),
+                )
+            })?;
+        let dependency_management = match root.find(&self.document, "dependencyManagement") {
+            Some(element) => Some(DependencyManagement {
+                dependencies: Dependencies {
+                    dependencies: self.read_list(element, "dependencies")?,
+                },
+            }),
+            None => None,
+        };
+        let properties = match root.find(&self.document, Properties::element_name()) {
+            Some(_) => Some(self.get_properties()?),
+            None => None,
+        };
+        Ok(Pom {
+            group_id: self.get_group_id(),
+            artifact_id,
+            parent: self.get_parent()?,
+            version: self.get_version(),
+            name: self.get_name(),
+            description: self.get_description(),
+            url: self.get_url(),
+            scm: self.get_scm()?,
+            properties,
+            dependencies: Dependencies {
+                dependencies: self.read_list(root, "dependencies")?,
+            },
+            dependency_management,
+            repositories: Repositories {
+                repositories: self.read_list(root, "repositories")?,
+            },
+            plugin_repositories: PluginRepositories {
+                repositories: self.get_plugin_repositories()?,
+            },
+            build: self.get_build()?,
+            modules: Modules {
+                modules: self.get_modules(),
+            },
+            profiles: Profiles {
+                profiles: self.read_list(root, "profiles")?,
+            },
+        })
+    }
+    /// Changes the document to match the model.
+    ///
+    /// Only what is different is touched. Items in lists are matched with [ComparableElement] and updated in place
+    /// with [UpdatableElement]. So comments, the order of items and the formatting of everything else is kept.
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let mut editor = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <artifactId>example</artifactId>
+    ///   <dependencies>
+    ///     <!-- Pinned for Java 8 -->
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>31.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#).unwrap();
+    /// let mut pom = editor.to_model().unwrap();
+    /// pom.dependencies.dependencies[0].version = Some("31.1-jre".parse().unwrap());
+    /// editor.apply_model(&pom).unwrap();
+    /// let written = editor.write_to_str().unwrap();
+    /// assert!(written.contains("<!-- Pinned for Java 8 -->"));
+    /// assert!(written.contains("<version>31.1-jre</version>"));
+    /// ```
+    pub fn apply_model(&mut self, pom: &Pom) -> Result<(), XMLEditorError> {
+        let root = self.root();
+        let document = &mut self.document;
+        for (name, value) in [
+            ("groupId", pom.group_id.as_deref()),
+            ("artifactId", Some(pom.artifact_id.as_str())),
+            ("version", pom.version.as_deref()),
+            ("name", pom.name.as_deref()),
+            ("description", pom.description.as_deref()),
+            ("url", pom.url.as_deref()),
+        ] {
+            sync_text(document, root, name, value);
+        }
+        sync_optional(document, root, pom.parent.as_ref())?;
+        sync_optional(document, root, pom.scm.as_ref())?;
+        sync_sections(
+            document,
+            root,
+            Sections {
+                properties: pom.properties.as_ref(),
+                dependencies: &pom.dependencies,
+                dependency_management: pom.dependency_management.as_ref(),
+                repositories: &pom.repositories,
+                plugin_repositories: &pom.plugin_repositories,
+                build: pom.build.as_ref(),
+                modules: &pom.modules,
+            },
+        )?;
+        sync_profiles(document, root, &pom.profiles.profiles)
+    }
+    fn read_list<E>(&self, container: Element, name: &str) -> Result<Vec<E>, XMLEditorError>
+    where
+        E: ElementConverter + HasElementName,
+    {
+        let Some(list) = container.find(&self.document, name) else {
+            return Ok(vec![]);
+        };
+        let items = get_all_children_of_element_located(&self.document, list, &self.spans)?;
+        Ok(items.into_iter().map(|(value, _)| value).collect())
+    }
+}
+/// The parts of a [Pom] that a [Profile] also has
+struct Sections<'a> {
+    properties: Option<&'a Properties>,
+    dependencies: &'a Dependencies,
+    dependency_management: Option<&'a DependencyManagement>,
+    repositories: &'a Repositories,
+    plugin_repositories: &'a PluginRepositories,
+    build: Option<&'a Build>,
+    modules: &'a Modules,
+}
+/// `container` is either the project or a profile
+fn sync_sections(
+    document: &mut Document,
+    container: Element,
+    sections: Sections<'_>,
+) -> Result<(), XMLEditorError> {
+    sync_optional(document, container, sections.properties)?;
+    sync_list(
+        document,
+        container,
+        "dependencies",
+        &sections.dependencies.dependencies,
+    )?;
+    match sections.dependency_management {
+        Some(management) => {
+            let element =
+                get_or_create_top_level_element("dependencyManagement", document, container);
+            sync_list(
+                document,
+                element,
+                "dependencies",
+                &management.dependencies.dependencies,
+            )?;
+        }
+        None => detach_child(document, container, "dependencyManagement")?,
+    }
+    sync_list(
+        document,
+        container,
+        "repositories",
+        &sections.repositories.repositories,
+    )?;
+    let plugin_repositories: Vec<_> = sections
+        .plugin_repositories
+        .repositories
+        .iter()
+        .cloned()
+        .map(PluginRepository)
+        .collect();
+    sync_list(
+        document,
+        container,
+        "pluginRepositories",
+        &plugin_repositories,
+    )?;
+    match sections.build {
+        Some(build) => sync_build(document, container, build)?,
+        None => detach_child(document, container, Build::element_name())?,
+    }
+    sync_modules(document, container, sections.modules)
+}
+fn sync_build(
+    document: &mut Document,
+    container: Element,
+    build: &Build,
+) -> Result<(), XMLEditorError> {
+    let element = get_or_create_top_level_element(Build::element_name(), document, container);
+    if Build::from_element(element, document)? == *build {
+        return Ok(());
+    }
+    let Build {
+        source_directory,
+        plugins: Plugins { plugins },
+        extensions,
+    } = build;
+    sync_text(
+        document,
+        element,
+        "sourceDirectory",
+        source_directory.as_deref(),
+    );
+    sync_list::<Plugin>(document, element, "plugins", plugins)?;
+    // Extensions can not be updated in place so the list is replaced when it changed
+    let current = match element.find(document, "extensions") {
+        Some(list) => get_all_children_of_element::<Extension>(document, list)?
+            .into_iter()
+            .map(|(extension, _)| extension)
+            .collect(),
+        None => vec![],
+    };
+    if current != extensions.extensions {
+        detach_child(document, element, "extensions")?;
+        if !extensions.extensions.is_empty() {
+            let list = Element::new(document, "extensions");
+            for extension in extensions.extensions.iter().cloned() {
+                let child = extension.into_element(document)?;
+                list.push_child(document, child)?;
+            }
+            element.push_child(document, list)?;
+        }
+    }
+    Ok(())
+}
+fn sync_modules(
+    document: &mut Document,
+    container: Element,
+    expected: &Modules,
+) -> Result<(), XMLEditorError> {
+    let current = modules::get_modules(document, container);
+    if current == expected.modules {
+        return Ok(());
+    }
+    for module in current {
+        if !expected.modules.contains(&module) {
+            modules::remove_module(document, container, &module)?;
+        }
+    }
+    for module in &expected.modules {
+        modules::add_module(document, container, module.clone())?;
+    }
+    if modules::get_modules(document, container) != expected.modules {
+        // Only the order is different
+        modules::clear_modules(document, container);
+        for module in &expected.modules {
+            modules::add_module(document, container, module.clone())?;
+        }
+    }
+    if expected.modules.is_empty() {
+        detach_child(document, container, Modules::element_name())?;
+    }
+    Ok(())
+}
+fn sync_profiles(
+    document: &mut Document,
+    root: Element,
+    profiles: &[Profile],
+) -> Result<(), XMLEditorError> {
+    if profiles.is_empty() {
+        return detach_child(document, root, "profiles");
+    }
+    let list = get_or_create_top_level_element("profiles", document, root);
+    let current = get_all_children_of_element::<Profile>(document, list)?;
+    for (profile, element) in &current {
+        if !profiles.iter().any(|other| other.is_same_item(profile)) {
+            element.detach(document)?;
+        }
+    }
+    for profile in profiles {
+        let element = match current
+            .iter()
+            .find(|(other, _)| other.is_same_item(profile))
+        {
+            Some((existing, _)) if existing == profile => continue,
+            Some((_, element)) => *element,
+            None => {
+                let element = Element::new(document, Profile::element_name());
+                let id = create_basic_text_element(document, "id", profile.id.clone());
+                element.push_child(document, id)?;
+                list.push_child(document, element)?;
+                element
+            }
+        };
+        sync_optional::<Activation>(document, element, profile.activation.as_ref())?;
+        sync_sections(
+            document,
+            element,
+            Sections {
+                properties: profile.properties.as_ref(),
+                dependencies: &profile.dependencies,
+                dependency_management: profile.dependency_management.as_ref(),
+                repositories: &profile.repositories,
+                plugin_repositories: &profile.plugin_repositories,
+                build: profile.build.as_ref(),
+                modules: &profile.modules,
+            },
+        )?;
+    }
+    Ok(())
+}
+/// Sets the text of the child `name`. Nothing is changed if the text is already the same
+fn sync_text(document: &mut Document, container: Element, name: &'static str, value: Option<&str>) {
+    let current = container
+        .find(document, name)
+        .map(|element| text_value(element, document));
+    if current.as_deref() != value {
+        sync_element(document, container, name, value);
+    }
+}
+fn detach_child(
+    document: &mut Document,
+    container: Element,
+    name: &str,
+) -> Result<(), XMLEditorError> {
+    if let Some(element) = container.find(document, name) {
+        element.detach(document)?;
+    }
+    Ok(())
+}
+/// Updates `element` in place to be `value`. Children that are not part of the model are kept
+fn update_if_changed<I>(
+    document: &mut Document,
+    element: Element,
+    current: &I,
+    value: &I,
+) -> Result<(), XMLEditorError>
+where
+    I: UpdatableElement + PartialEq + Clone,
+{
+    if current == value {
+        return Ok(());
+    }
+    value.update_element(element, document)
+}
+/// Syncs a single optional child such as `<parent>` or `<properties>`
+fn sync_optional<I>(
+    document: &mut Document,
+    container: Element,
+    value: Option<&I>,
+) -> Result<(), XMLEditorError>
+where
+    I: UpdatableElement + HasElementName + PartialEq + Clone,
+{
+    let existing = container.find(document, I::element_name());
+    match (existing, value) {
+        (Some(element), Some(value)) => {
+            let current = I::from_element(element, document)?;
+            update_if_changed(document, element, &current, value)
+        }
+        (Some(element), None) => {
+            element.detach(document)?;
+            Ok(())
+        }
+        (None, Some(value)) => {
+            let element = value.clone().into_element(document)?;
+            container.push_child(document, element)?;
+            Ok(())
+        }
+        (None, None) => Ok(()),
+    }
+}
+/// Syncs the items of a list such as `<dependencies>`.
+///
+/// Items that are not in `items` are removed, existing items are updated in place and new items are added to the end
+fn sync_list<I>(
+    document: &mut Document,
+    container: Element,
+    list_name: &str,
+    items: &[I],
+) -> Result<(), XMLEditorError>
+where
+    I: UpdatableElement + HasElementName + ComparableElement + PartialEq + Clone,
+{
+    if items.is_empty() {
+        return detach_child(document, container, list_name);
+    }
+    let list = match container.find(document, list_name) {
+        Some(list) => list,
+        None => {
+            let list = Element::new(document, list_name);
+            container.push_child(document, list)?;
+            list
+        }
+    };
+    let current = get_all_children_of_element::<I>(document, list)?;
+    for (value, element) in &current {
+        if !items.iter().any(|item| item.is_same_item(value)) {
+            element.detach(document)?;
+        }
+    }
+    for item in items {
+        match current.iter().find(|(value, _)| value.is_same_item(item)) {
+            Some((value, element)) => update_if_changed(document, *element, value, item)?,
+            None => {
+                let element = item.clone().into_element(document)?;
+                list.push_child(document, element)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const POM: &str = r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>example</artifactId>
+    <version>1.0.0</version>
+    <properties>
+        <!-- The release to compile for -->
+        <java.version>17</java.version>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>31.0-jre</version>
+            <scope>compile</scope>
+        </dependency>
+        <!-- Logging -->
+        <dependency>
+            <groupId>org.slf4j</groupId>
+            <artifactId>slf4j-api</artifactId>
+            <version>2.0.16</version>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.11.0</version>
+            </plugin>
+        </plugins>
+    </build>
+    <profiles>
+        <profile>
+            <id>release</id>
+            <properties>
+                <skipTests>true</skipTests>
+            </properties>
+        </profile>
+    </profiles>
+</project>"#;
+
+    #[test]
+    fn round_trip() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let pom = editor.to_model()?;
+        assert_eq!(pom.version.as_deref(), Some("1.0.0"));
+        assert_eq!(pom.dependencies.dependencies.len(), 2);
+        assert_eq!(pom.build.as_ref().unwrap().plugins.plugins.len(), 1);
+        assert_eq!(pom.profiles.profiles[0].id, "release");
+
+        editor.apply_model(&pom)?;
+        assert_eq!(editor.write_to_str()?, POM);
+        Ok(())
+    }
+
+    #[test]
+    fn apply_changes() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let mut pom = editor.to_model()?;
+        pom.version = Some("1.1.0".to_owned());
+        pom.description = Some("An example".to_owned());
+        let guava = &mut pom.dependencies.dependencies[0];
+        guava.version = Some("33.0.0-jre".parse().unwrap());
+        guava.scope = None;
+        pom.dependencies.dependencies.remove(1);
+        pom.dependencies
+            .dependencies
+            .push("org.junit.jupiter:junit-jupiter:5.11.0".parse()?);
+        pom.properties
+            .as_mut()
+            .unwrap()
+            .0
+            .insert("encoding".to_owned(), "UTF-8".to_owned());
+        pom.build = None;
+        pom.profiles.profiles[0]
+            .modules
+            .modules
+            .push("docs".to_owned());
+
+        editor.apply_model(&pom)?;
+        let written = editor.write_to_str()?;
+        assert!(written.contains("<!-- The release to compile for -->"));
+        assert!(!written.contains("slf4j-api"));
+        assert!(!written.contains("<build>"));
+        assert!(!written.contains("<scope>"));
+        assert_eq!(editor.to_model()?.dependencies, pom.dependencies);
+        let reloaded = PomEditor::load_from_str(&written)?.to_model()?;
+        assert_eq!(reloaded.version.as_deref(), Some("1.1.0"));
+        assert_eq!(reloaded.description.as_deref(), Some("An example"));
+        assert_eq!(reloaded.properties, pom.properties);
+        assert_eq!(reloaded.profiles, pom.profiles);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_unmodeled_children() -> anyhow::Result<()> {
+        let source = r#"<project>
+    <artifactId>example</artifactId>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>31.0-jre</version>
+            <scope>compile</scope>
+            <exclusions>
+                <!-- Provided by the runtime -->
+                <exclusion>
+                    <groupId>com.google.code.findbugs</groupId>
+                    <artifactId>jsr305</artifactId>
+                </exclusion>
+            </exclusions>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.11.0</version>
+                <configuration>
+                    <release>17</release>
+                </configuration>
+                <dependencies>
+                    <dependency>
+                        <groupId>org.ow2.asm</groupId>
+                        <artifactId>asm</artifactId>
+                        <version>9.7</version>
+                    </dependency>
+                </dependencies>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+        let mut editor = PomEditor::load_from_str(source)?;
+        let mut pom = editor.to_model()?;
+        pom.dependencies.dependencies[0].scope = None;
+        pom.build.as_mut().unwrap().plugins.plugins[0]
+            .dependencies
+            .dependencies
+            .clear();
+        editor.apply_model(&pom)?;
+
+        let expected = source
+            .replace("            <scope>compile</scope>\n", "")
+            .replace(
+                r#"
+                <dependencies>
+                    <dependency>
+                        <groupId>org.ow2.asm</groupId>
+                        <artifactId>asm</artifactId>
+                        <version>9.7</version>
+                    </dependency>
+                </dependencies>"#,
+                "",
+            );
+        assert_eq!(editor.write_to_str()?, expected);
+        let model = editor.to_model()?;
+        assert_eq!(model.dependencies, pom.dependencies);
+        assert_eq!(model.build, pom.build);
+        Ok(())
+    }
+}
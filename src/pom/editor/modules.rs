@@ -0,0 +1,151 @@
+use edit_xml::{Document, Element};
+
+use crate::editor::{
+    XMLEditorError,
+    utils::{create_basic_text_element, get_or_create_top_level_element, text_value},
+};
+
+use super::PomEditor;
+
+impl PomEditor {
+    /// Gets the modules of the pom file. In the order they are declared
+    ///
+    /// Modules declared inside of profiles are not included. See [super::ProfileEditor::get_modules]
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let xml = r#"
+    /// <project>
+    ///   <packaging>pom</packaging>
+    ///   <modules>
+    ///     <module>core</module>
+    ///     <module>cli</module>
+    ///   </modules>
+    /// </project>
+    /// "#;
+    /// let editor = PomEditor::load_from_str(xml).unwrap();
+    /// assert_eq!(editor.get_modules(), vec!["core", "cli"]);
+    /// ```
+    pub fn get_modules(&self) -> Vec<String> {
+        get_modules(&self.document, self.root())
+    }
+    /// Adds a module to the end of `<modules>`
+    ///
+    /// Returns false if the module was already present
+    pub fn add_module(&mut self, module: impl Into<String>) -> Result<bool, XMLEditorError> {
+        let root = self.root();
+        add_module(&mut self.document, root, module.into())
+    }
+    /// Removes a module
+    ///
+    /// Returns false if the module was not present
+    pub fn remove_module(&mut self, module: &str) -> Result<bool, XMLEditorError> {
+        let root = self.root();
+        remove_module(&mut self.document, root, module)
+    }
+    /// Clears all the modules in the pom file
+    pub fn clear_modules(&mut self) -> Result<(), XMLEditorError> {
+        let root = self.root();
+        clear_modules(&mut self.document, root);
+        Ok(())
+    }
+}
+/// `container` is either the project or a profile
+pub(super) fn get_modules(document: &Document, container: Element) -> Vec<String> {
+    module_elements(document, container)
+        .into_iter()
+        .map(|element| text_value(element, document))
+        .collect()
+}
+pub(super) fn add_module(
+    document: &mut Document,
+    container: Element,
+    module: String,
+) -> Result<bool, XMLEditorError> {
+    if get_modules(document, container).contains(&module) {
+        return Ok(false);
+    }
+    let modules = get_or_create_top_level_element("modules", document, container);
+    let element = create_basic_text_element(document, "module", module);
+    modules.push_child(document, element)?;
+    Ok(true)
+}
+pub(super) fn remove_module(
+    document: &mut Document,
+    container: Element,
+    module: &str,
+) -> Result<bool, XMLEditorError> {
+    let Some(element) = module_elements(document, container)
+        .into_iter()
+        .find(|element| text_value(*element, document) == module)
+    else {
+        return Ok(false);
+    };
+    element.detach(document)?;
+    Ok(true)
+}
+pub(super) fn clear_modules(document: &mut Document, container: Element) {
+    if let Some(modules) = container.find(document, "modules") {
+        modules.clear_children(document);
+    }
+}
+fn module_elements(document: &Document, container: Element) -> Vec<Element> {
+    let Some(modules) = container.find(document, "modules") else {
+        return vec![];
+    };
+    modules
+        .child_elements(document)
+        .into_iter()
+        .filter(|element| element.name(document) == "module")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn modules() -> anyhow::Result<()> {
+        let xml = r#"
+            <project>
+                <modules>
+                    <module>core</module>
+                    <!-- Command line interface -->
+                    <module>cli</module>
+                </modules>
+                <profiles>
+                    <profile>
+                        <id>docs</id>
+                        <modules>
+                            <module>docs</module>
+                        </modules>
+                    </profile>
+                </profiles>
+            </project>
+        "#;
+        let mut editor = PomEditor::load_from_str(xml)?;
+        assert_eq!(editor.get_modules(), vec!["core", "cli"]);
+        assert!(!editor.add_module("core")?);
+        assert!(editor.add_module("server")?);
+        assert!(editor.remove_module("core")?);
+        assert!(!editor.remove_module("core")?);
+        assert_eq!(editor.get_modules(), vec!["cli", "server"]);
+        assert!(
+            editor
+                .write_to_str()?
+                .contains("<!-- Command line interface -->")
+        );
+        {
+            let mut profile = editor.get_profile_or_none("docs").expect("docs profile");
+            assert_eq!(profile.get_modules(), vec!["docs"]);
+            profile.add_module("site")?;
+        }
+        let profiles = editor.get_profiles()?;
+        assert_eq!(profiles[0].modules.modules, vec!["docs", "site"]);
+
+        editor.clear_modules()?;
+        assert!(editor.get_modules().is_empty());
+        Ok(())
+    }
+}
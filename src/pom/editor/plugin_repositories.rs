@@ -0,0 +1,309 @@
+use edit_xml::{Document, Element};
+
+use crate::{
+    editor::{
+        ChildOfListElement, XMLEditorError,
+        utils::{
+            MissingElementError, add_or_update_item, find_item, get_all_children_of_element,
+            modify_item, remove_item,
+        },
+    },
+    pom::{PluginRepository, Repository},
+};
+
+use super::PomEditor;
+
+impl PomEditor {
+    /// Gets all the plugin repositories in the pom file
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let xml = r#"
+    /// <project>
+    ///   <pluginRepositories>
+    ///     <pluginRepository>
+    ///       <id>central</id>
+    ///       <url>https://repo.maven.apache.org/maven2</url>
+    ///     </pluginRepository>
+    ///   </pluginRepositories>
+    /// </project>
+    /// "#;
+    /// let editor = PomEditor::load_from_str(xml).unwrap();
+    /// let repositories = editor.get_plugin_repositories().unwrap();
+    /// assert_eq!(repositories.len(), 1);
+    /// assert_eq!(repositories[0].id, Some("central".to_string()));
+    /// ```
+    pub fn get_plugin_repositories(&self) -> Result<Vec<Repository>, XMLEditorError> {
+        get_plugin_repositories(&self.document, self.root())
+    }
+    /// Finds the plugin repository with the same name
+    pub fn find_plugin_repository(
+        &self,
+        repository: &Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        find_plugin_repository(&self.document, self.root(), repository)
+    }
+    /// Adds or Updates a plugin repository in the pom file
+    ///
+    /// Repositories are matched the same way as [PomEditor::add_or_update_repository]
+    pub fn add_or_update_plugin_repository(
+        &mut self,
+        repository: Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        let root = self.root();
+        add_or_update_plugin_repository(&mut self.document, root, repository)
+    }
+    /// Modifies the plugin repository with the same name where it is in the file
+    ///
+    /// Returns the modified repository. None if it was not found
+    pub fn modify_plugin_repository<F>(
+        &mut self,
+        repository: &Repository,
+        modify: F,
+    ) -> Result<Option<Repository>, XMLEditorError>
+    where
+        F: FnOnce(&mut Repository),
+    {
+        let root = self.root();
+        modify_plugin_repository(&mut self.document, root, repository, modify)
+    }
+    /// Removes a plugin repository from the pom file
+    ///
+    /// Returns the removed repository
+    pub fn remove_plugin_repository(
+        &mut self,
+        repository: &Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        let root = self.root();
+        remove_plugin_repository(&mut self.document, root, repository)
+    }
+    /// Clears all the plugin repositories in the pom file
+    pub fn clear_plugin_repositories(&mut self) -> Result<(), XMLEditorError> {
+        let root = self.root();
+        clear_plugin_repositories(&mut self.document, root);
+        Ok(())
+    }
+    /// Gets the plugin repositories inside of the profile with the id
+    ///
+    /// Returns an empty list if the profile does not exist
+    pub fn get_profile_plugin_repositories(
+        &self,
+        profile_id: &str,
+    ) -> Result<Vec<Repository>, XMLEditorError> {
+        match self.find_profile_element(profile_id) {
+            Some(profile) => get_plugin_repositories(&self.document, profile),
+            None => Ok(vec![]),
+        }
+    }
+    /// Adds or Updates a plugin repository inside of the profile with the id
+    ///
+    /// # Errors
+    /// If the profile does not exist
+    pub fn add_or_update_profile_plugin_repository(
+        &mut self,
+        profile_id: &str,
+        repository: Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        let profile = self
+            .find_profile_element(profile_id)
+            .ok_or(MissingElementError("profile"))?;
+        add_or_update_plugin_repository(&mut self.document, profile, repository)
+    }
+    /// Removes a plugin repository from the profile with the id
+    ///
+    /// Returns None if the profile or repository does not exist
+    pub fn remove_profile_plugin_repository(
+        &mut self,
+        profile_id: &str,
+        repository: &Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        let Some(profile) = self.find_profile_element(profile_id) else {
+            return Ok(None);
+        };
+        remove_plugin_repository(&mut self.document, profile, repository)
+    }
+    /// Clears the plugin repositories inside of the profile with the id
+    pub fn clear_profile_plugin_repositories(
+        &mut self,
+        profile_id: &str,
+    ) -> Result<(), XMLEditorError> {
+        if let Some(profile) = self.find_profile_element(profile_id) {
+            clear_plugin_repositories(&mut self.document, profile);
+        }
+        Ok(())
+    }
+}
+/// `container` is either the project or a profile
+pub(super) fn get_plugin_repositories(
+    document: &Document,
+    container: Element,
+) -> Result<Vec<Repository>, XMLEditorError> {
+    let Some(parent) = container.find(document, PluginRepository::parent_element_name()) else {
+        return Ok(vec![]);
+    };
+    let result = get_all_children_of_element::<PluginRepository>(document, parent)?;
+    Ok(result
+        .into_iter()
+        .map(|(PluginRepository(repository), _)| repository)
+        .collect())
+}
+pub(super) fn find_plugin_repository(
+    document: &Document,
+    container: Element,
+    repository: &Repository,
+) -> Result<Option<Repository>, XMLEditorError> {
+    let parent = container.find(document, PluginRepository::parent_element_name());
+    let found = find_item(document, parent, &PluginRepository(repository.clone()))?;
+    Ok(found.map(|(PluginRepository(repository), _)| repository))
+}
+pub(super) fn add_or_update_plugin_repository(
+    document: &mut Document,
+    container: Element,
+    repository: Repository,
+) -> Result<Option<Repository>, XMLEditorError> {
+    let parent = container.find(document, PluginRepository::parent_element_name());
+    let existing = add_or_update_item(document, parent, container, PluginRepository(repository))?;
+    Ok(existing.map(|PluginRepository(repository)| repository))
+}
+pub(super) fn modify_plugin_repository<F>(
+    document: &mut Document,
+    container: Element,
+    repository: &Repository,
+    modify: F,
+) -> Result<Option<Repository>, XMLEditorError>
+where
+    F: FnOnce(&mut Repository),
+{
+    let parent = container.find(document, PluginRepository::parent_element_name());
+    let modified = modify_item(
+        document,
+        parent,
+        &PluginRepository(repository.clone()),
+        |PluginRepository(repository)| modify(repository),
+    )?;
+    Ok(modified.map(|PluginRepository(repository)| repository))
+}
+pub(super) fn remove_plugin_repository(
+    document: &mut Document,
+    container: Element,
+    repository: &Repository,
+) -> Result<Option<Repository>, XMLEditorError> {
+    let parent = container.find(document, PluginRepository::parent_element_name());
+    let removed = remove_item(document, parent, &PluginRepository(repository.clone()))?;
+    Ok(removed.map(|PluginRepository(repository)| repository))
+}
+pub(super) fn clear_plugin_repositories(document: &mut Document, container: Element) {
+    if let Some(parent) = container.find(document, PluginRepository::parent_element_name()) {
+        parent.clear_children(document);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn repository(name: &str) -> Repository {
+        Repository {
+            id: Some(name.to_owned()),
+            name: Some(name.to_owned()),
+            url: format!("https://repo.example.com/{name}"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plugin_repositories() -> anyhow::Result<()> {
+        let mut editor = PomEditor::new_with_group_and_artifact("dev.wyatt-herkamp", "test");
+        editor.add_or_update_repository(repository("releases"))?;
+        editor.add_or_update_plugin_repository(repository("plugins"))?;
+        editor.add_or_update_plugin_repository(repository("other"))?;
+
+        let mut updated = repository("plugins");
+        updated.url = "https://plugins.example.com".to_owned();
+        let previous = editor.add_or_update_plugin_repository(updated.clone())?;
+        assert_eq!(previous, Some(repository("plugins")));
+
+        let value = editor.write_to_str()?;
+        assert!(value.contains("<pluginRepository>"));
+        let mut editor = PomEditor::load_from_str(&value)?;
+        assert_eq!(
+            editor.get_plugin_repositories()?,
+            vec![updated, repository("other")]
+        );
+        assert_eq!(editor.get_repositories()?, vec![repository("releases")]);
+
+        assert_eq!(
+            editor.find_plugin_repository(&repository("other"))?,
+            Some(repository("other"))
+        );
+        editor.modify_plugin_repository(&repository("other"), |repository| {
+            repository.id = Some("renamed".to_owned());
+        })?;
+        let mut expected = repository("other");
+        expected.id = Some("renamed".to_owned());
+        let removed = editor.remove_plugin_repository(&repository("other"))?;
+        assert_eq!(removed, Some(expected));
+        assert_eq!(editor.get_plugin_repositories()?.len(), 1);
+
+        editor.clear_plugin_repositories()?;
+        assert!(editor.get_plugin_repositories()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn profile_plugin_repositories() -> anyhow::Result<()> {
+        let xml = r#"
+            <project>
+                <profiles>
+                    <profile>
+                        <id>release</id>
+                        <!-- Plugins used only for releases -->
+                        <pluginRepositories>
+                            <pluginRepository>
+                                <id>plugins</id>
+                                <name>plugins</name>
+                                <url>https://repo.example.com/plugins</url>
+                            </pluginRepository>
+                        </pluginRepositories>
+                    </profile>
+                </profiles>
+            </project>
+        "#;
+        let mut editor = PomEditor::load_from_str(xml)?;
+        assert!(editor.get_plugin_repositories()?.is_empty());
+        assert_eq!(
+            editor.get_profile_plugin_repositories("release")?,
+            vec![repository("plugins")]
+        );
+        editor.add_or_update_profile_plugin_repository("release", repository("other"))?;
+        assert!(
+            editor
+                .add_or_update_profile_plugin_repository("missing", repository("other"))
+                .is_err()
+        );
+        let profiles = editor.get_profiles()?;
+        assert_eq!(
+            profiles[0].plugin_repositories.repositories,
+            vec![repository("plugins"), repository("other")]
+        );
+        editor.remove_profile_plugin_repository("release", &repository("plugins"))?;
+        assert_eq!(
+            editor.get_profile_plugin_repositories("release")?,
+            vec![repository("other")]
+        );
+        assert!(
+            editor
+                .write_to_str()?
+                .contains("<!-- Plugins used only for releases -->")
+        );
+        editor.clear_profile_plugin_repositories("release")?;
+        assert!(
+            editor
+                .get_profile_plugin_repositories("release")?
+                .is_empty()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,470 @@
+use edit_xml::Element;
+
+use crate::{
+    editor::{ElementConverter, UpdatableElement, XMLEditorError, utils::text_value},
+    pom::{Activation, Dependency, Properties, Repository},
+};
+
+use super::{
+    BuildEditor, DependencyManagementEditor, PomEditor, list_item_methods, modules,
+    plugin_repositories, properties, top_level_structured_type,
+};
+
+impl PomEditor {
+    /// Creates a new [ProfileEditor] for the profile with the id
+    ///
+    /// If the profile does not exist it will be created
+    /// # Note.
+    /// This function will hold a mutable reference to the PomEditor.
+    /// I would recommend using this function within a scope. To prevent borrowing issues.
+    ///
+    /// ```rust
+    /// use maven_rs::pom::{editor::PomEditor, Activation};
+    /// let mut editor = PomEditor::default();
+    /// {
+    ///     let mut profile = editor.get_or_create_profile("release");
+    ///     profile.set_activation(Activation {
+    ///         active_by_default: Some(false),
+    ///         ..Default::default()
+    ///     }).unwrap();
+    ///     profile.set_property("skipTests", "true").unwrap();
+    ///     profile.add_or_update_dependency("com.google.guava:guava:33.0.0-jre".parse().unwrap()).unwrap();
+    /// }
+    /// let profiles = editor.get_profiles().unwrap();
+    /// assert_eq!(profiles[0].id, "release");
+    /// assert_eq!(profiles[0].dependencies.dependencies.len(), 1);
+    /// ```
+    pub fn get_or_create_profile(&mut self, profile_id: &str) -> ProfileEditor<'_> {
+        if let Some(profile_element) = self.find_profile_element(profile_id) {
+            return ProfileEditor::new(self, profile_element);
+        }
+        let root = self.root();
+        let profiles = crate::editor::utils::get_or_create_top_level_element(
+            "profiles",
+            &mut self.document,
+            root,
+        );
+        let profile_element = Element::new(&mut self.document, "profile");
+        let id = crate::editor::utils::create_basic_text_element(
+            &mut self.document,
+            "id",
+            profile_id.to_owned(),
+        );
+        profile_element
+            .push_child(&mut self.document, id)
+            .expect("The id element is new");
+        profiles
+            .push_child(&mut self.document, profile_element)
+            .expect("The profile element is new");
+        ProfileEditor::new(self, profile_element)
+    }
+    /// Checks if the profile is present in the pom file
+    ///
+    /// If the profile is present, it will return Some(ProfileEditor) else it will return None
+    pub fn get_profile_or_none(&mut self, profile_id: &str) -> Option<ProfileEditor<'_>> {
+        let profile_element = self.find_profile_element(profile_id)?;
+        Some(ProfileEditor::new(self, profile_element))
+    }
+    pub fn has_profile(&self, profile_id: &str) -> bool {
+        self.find_profile_element(profile_id).is_some()
+    }
+    /// Deletes the profile with the id
+    ///
+    /// If the profile is present, it will delete it and return true else it will return false
+    pub fn delete_profile(&mut self, profile_id: &str) -> Result<bool, XMLEditorError> {
+        let Some(profile_element) = self.find_profile_element(profile_id) else {
+            return Ok(false);
+        };
+        profile_element.detach(&mut self.document)?;
+        Ok(true)
+    }
+    /// Finds the `<profile>` element with the id
+    pub(crate) fn find_profile_element(&self, profile_id: &str) -> Option<Element> {
+        let profiles = self.root().find(&self.document, "profiles")?;
+        profiles
+            .child_elements(&self.document)
+            .into_iter()
+            .find(|profile| {
+                profile
+                    .find(&self.document, "id")
+                    .is_some_and(|id| text_value(id, &self.document) == profile_id)
+            })
+    }
+}
+/// Allows for editing a single `<profile>` of a pom file
+///
+/// Sections inside of the profile use the same editors as the project. Such as [BuildEditor] and [DependencyManagementEditor]
+#[derive(Debug)]
+pub struct ProfileEditor<'a> {
+    parent: &'a mut PomEditor,
+    profile_element: Element,
+}
+impl<'a> ProfileEditor<'a> {
+    pub(super) fn new(parent: &'a mut PomEditor, profile_element: Element) -> Self {
+        Self {
+            parent,
+            profile_element,
+        }
+    }
+    /// The id of the profile
+    pub fn id(&self) -> String {
+        self.profile_element
+            .find(&self.parent.document, "id")
+            .map(|id| text_value(id, &self.parent.document))
+            .unwrap_or_default()
+    }
+    top_level_structured_type!(
+        in profile_element,
+        /// Sets the activation of the profile
+        ///
+        /// If [None] is passed in. The activation element will be removed
+        set: set_activation,
+        /// Gets the activation of the profile
+        get: get_activation,
+        "activation" => Activation,
+    );
+    list_item_methods!(
+        in profile_element,
+        /// Gets all the dependencies in the profile
+        get: get_dependencies,
+        /// Finds the dependency with the same group id and artifact id
+        find: find_dependency,
+        /// Adds or Updates a dependency in the profile
+        add: add_or_update_dependency,
+        /// Modifies the dependency with the same group id and artifact id where it is in the file
+        ///
+        /// Returns the modified dependency. None if it was not found
+        modify: modify_dependency,
+        /// Removes the dependency with the same group id and artifact id
+        remove: remove_dependency,
+        /// Clears all the dependencies in the profile
+        clear: clear_dependencies,
+        "dependencies" => Dependency
+    );
+    list_item_methods!(
+        in profile_element,
+        /// Gets all the repositories in the profile
+        get: get_repositories,
+        /// Finds the repository with the same name
+        find: find_repository,
+        /// Adds or Updates a repository in the profile
+        add: add_or_update_repository,
+        /// Modifies the repository with the same name where it is in the file
+        ///
+        /// Returns the modified repository. None if it was not found
+        modify: modify_repository,
+        /// Removes the repository with the same name
+        remove: remove_repository,
+        /// Clears all the repositories in the profile
+        clear: clear_repositories,
+        "repositories" => Repository
+    );
+    /// Gets all the plugin repositories in the profile
+    pub fn get_plugin_repositories(&self) -> Result<Vec<Repository>, XMLEditorError> {
+        plugin_repositories::get_plugin_repositories(&self.parent.document, self.profile_element)
+    }
+    /// Finds the plugin repository with the same name
+    pub fn find_plugin_repository(
+        &self,
+        repository: &Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        plugin_repositories::find_plugin_repository(
+            &self.parent.document,
+            self.profile_element,
+            repository,
+        )
+    }
+    /// Adds or Updates a plugin repository in the profile
+    pub fn add_or_update_plugin_repository(
+        &mut self,
+        repository: Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        plugin_repositories::add_or_update_plugin_repository(
+            &mut self.parent.document,
+            self.profile_element,
+            repository,
+        )
+    }
+    /// Modifies the plugin repository with the same name where it is in the file
+    ///
+    /// Returns the modified repository. None if it was not found
+    pub fn modify_plugin_repository<F>(
+        &mut self,
+        repository: &Repository,
+        modify: F,
+    ) -> Result<Option<Repository>, XMLEditorError>
+    where
+        F: FnOnce(&mut Repository),
+    {
+        plugin_repositories::modify_plugin_repository(
+            &mut self.parent.document,
+            self.profile_element,
+            repository,
+            modify,
+        )
+    }
+    /// Removes the plugin repository with the same name
+    pub fn remove_plugin_repository(
+        &mut self,
+        repository: &Repository,
+    ) -> Result<Option<Repository>, XMLEditorError> {
+        plugin_repositories::remove_plugin_repository(
+            &mut self.parent.document,
+            self.profile_element,
+            repository,
+        )
+    }
+    /// Clears all the plugin repositories in the profile
+    pub fn clear_plugin_repositories(&mut self) -> Result<(), XMLEditorError> {
+        plugin_repositories::clear_plugin_repositories(
+            &mut self.parent.document,
+            self.profile_element,
+        );
+        Ok(())
+    }
+    /// Gets the properties of the profile in the order they appear in the file
+    pub fn get_properties(&self) -> Result<Properties, XMLEditorError> {
+        properties::get_properties(&self.parent.document, self.profile_element)
+    }
+    /// Gets the value of a single property in the profile
+    pub fn get_property(&self, name: &str) -> Option<String> {
+        properties::get_property(&self.parent.document, self.profile_element, name)
+    }
+    /// Sets a single property in the profile. See [PomEditor::set_property]
+    ///
+    /// Returns the previous value
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        value: impl Into<String>,
+    ) -> Result<Option<String>, XMLEditorError> {
+        properties::set_property(
+            &mut self.parent.document,
+            self.profile_element,
+            name,
+            value.into(),
+        )
+    }
+    /// Removes a single property from the profile
+    ///
+    /// Returns the removed value
+    pub fn remove_property(&mut self, name: &str) -> Result<Option<String>, XMLEditorError> {
+        properties::remove_property(&mut self.parent.document, self.profile_element, name)
+    }
+    /// Replaces all the properties of the profile. See [PomEditor::set_properties]
+    pub fn set_properties(&mut self, properties: Properties) -> Result<(), XMLEditorError> {
+        properties::set_properties(&mut self.parent.document, self.profile_element, properties)
+    }
+    /// Gets the modules declared in the profile
+    pub fn get_modules(&self) -> Vec<String> {
+        modules::get_modules(&self.parent.document, self.profile_element)
+    }
+    /// Adds a module to the profile
+    ///
+    /// Returns false if the module was already present
+    pub fn add_module(&mut self, module: impl Into<String>) -> Result<bool, XMLEditorError> {
+        modules::add_module(
+            &mut self.parent.document,
+            self.profile_element,
+            module.into(),
+        )
+    }
+    /// Removes a module from the profile
+    ///
+    /// Returns false if the module was not present
+    pub fn remove_module(&mut self, module: &str) -> Result<bool, XMLEditorError> {
+        modules::remove_module(&mut self.parent.document, self.profile_element, module)
+    }
+    /// Clears all the modules in the profile
+    pub fn clear_modules(&mut self) -> Result<(), XMLEditorError> {
+        modules::clear_modules(&mut self.parent.document, self.profile_element);
+        Ok(())
+    }
+    /// Creates a [BuildEditor] for the build section of the profile
+    ///
+    /// If no build element is present, it will create one
+    pub fn get_or_create_build_element(&mut self) -> BuildEditor<'_> {
+        BuildEditor::new_in(self.parent, self.profile_element)
+    }
+    /// If the build element is present, it will return Some(BuildEditor) else it will return None
+    pub fn get_build_element_or_none(&mut self) -> Option<BuildEditor<'_>> {
+        if self.has_build() {
+            return Some(self.get_or_create_build_element());
+        }
+        None
+    }
+    pub fn has_build(&self) -> bool {
+        self.profile_element
+            .find(&self.parent.document, "build")
+            .is_some()
+    }
+    pub fn delete_build(&mut self) -> Result<bool, XMLEditorError> {
+        self.delete_child("build")
+    }
+    /// Creates a [DependencyManagementEditor] for the `dependencyManagement` section of the profile
+    ///
+    /// If no `dependencyManagement` element is present, it will create one
+    pub fn get_or_create_dependency_management_element(
+        &mut self,
+    ) -> DependencyManagementEditor<'_> {
+        DependencyManagementEditor::new_in(self.parent, self.profile_element)
+    }
+    /// If the `dependencyManagement` element is present, it will return Some(DependencyManagementEditor) else it will return None
+    pub fn get_dependency_management_element_or_none(
+        &mut self,
+    ) -> Option<DependencyManagementEditor<'_>> {
+        if self.has_dependency_management() {
+            return Some(self.get_or_create_dependency_management_element());
+        }
+        None
+    }
+    pub fn has_dependency_management(&self) -> bool {
+        self.profile_element
+            .find(&self.parent.document, "dependencyManagement")
+            .is_some()
+    }
+    pub fn delete_dependency_management(&mut self) -> Result<bool, XMLEditorError> {
+        self.delete_child("dependencyManagement")
+    }
+    fn delete_child(&mut self, name: &str) -> Result<bool, XMLEditorError> {
+        let Some(element) = self.profile_element.find(&self.parent.document, name) else {
+            return Ok(false);
+        };
+        element.detach(&mut self.parent.document)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        pom::{ActivationProperty, build::Plugin},
+        types::Property,
+    };
+
+    use super::*;
+
+    #[test]
+    fn create_profile() -> anyhow::Result<()> {
+        let mut editor = PomEditor::new_with_group_and_artifact("dev.wyatt-herkamp", "test");
+        let activation = Activation {
+            property: Some(ActivationProperty {
+                name: "release".to_owned(),
+                value: None,
+            }),
+            ..Default::default()
+        };
+        let plugin = Plugin {
+            group_id: Some("org.apache.maven.plugins".to_owned()),
+            artifact_id: "maven-gpg-plugin".to_owned(),
+            version: Some(Property::Literal("3.2.4".to_owned())),
+            ..Default::default()
+        };
+        let repository = Repository {
+            id: Some("staging".to_owned()),
+            name: Some("staging".to_owned()),
+            url: "https://staging.example.com".to_owned(),
+            ..Default::default()
+        };
+        {
+            let mut profile = editor.get_or_create_profile("release");
+            assert_eq!(profile.id(), "release");
+            profile.set_activation(activation.clone())?;
+            profile.set_property("gpg.skip", "false")?;
+            profile.add_or_update_dependency("com.google.guava:guava:33.0.0-jre".parse()?)?;
+            profile.add_or_update_repository(repository.clone())?;
+            profile.add_or_update_plugin_repository(repository.clone())?;
+            profile
+                .get_or_create_build_element()
+                .add_or_update_plugin(plugin.clone())?;
+            profile
+                .get_or_create_dependency_management_element()
+                .add_or_update_dependency("org.junit:junit-bom:5.11.0".parse()?)?;
+        }
+        // The same profile is edited again
+        editor
+            .get_or_create_profile("release")
+            .set_property("skipTests", "true")?;
+        editor.get_or_create_profile("other");
+
+        let value = editor.write_to_str()?;
+        let editor = PomEditor::load_from_str(&value)?;
+        assert!(editor.get_dependencies()?.is_empty());
+        assert!(editor.get_repositories()?.is_empty());
+        let profiles = editor.get_profiles()?;
+        assert_eq!(profiles.len(), 2);
+        let release = &profiles[0];
+        assert_eq!(release.activation, Some(activation));
+        let properties = release.properties.clone().unwrap_or_default();
+        assert_eq!(properties.get("gpg.skip"), Some("false"));
+        assert_eq!(properties.get("skipTests"), Some("true"));
+        assert_eq!(release.dependencies.dependencies.len(), 1);
+        assert_eq!(release.repositories.repositories, vec![repository.clone()]);
+        assert_eq!(release.plugin_repositories.repositories, vec![repository]);
+        let build = release.build.clone().unwrap_or_default();
+        assert_eq!(build.plugins.plugins, vec![plugin]);
+        let dependency_management = release.dependency_management.clone().unwrap_or_default();
+        assert_eq!(
+            dependency_management.dependencies.dependencies[0].artifact_id,
+            "junit-bom"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn edit_existing_profile() -> anyhow::Result<()> {
+        let xml = r#"
+            <project>
+                <profiles>
+                    <profile>
+                        <id>ci</id>
+                        <!-- Only on the build server -->
+                        <activation>
+                            <property>
+                                <name>env.CI</name>
+                            </property>
+                        </activation>
+                        <dependencies>
+                            <dependency>
+                                <groupId>com.google.guava</groupId>
+                                <artifactId>guava</artifactId>
+                                <version>30.1-jre</version>
+                            </dependency>
+                        </dependencies>
+                    </profile>
+                </profiles>
+            </project>
+        "#;
+        let mut editor = PomEditor::load_from_str(xml)?;
+        assert!(editor.has_profile("ci"));
+        assert!(editor.get_profile_or_none("missing").is_none());
+        {
+            let mut profile = editor.get_profile_or_none("ci").expect("ci profile");
+            assert!(!profile.has_build());
+            let activation = profile.get_activation()?.expect("activation");
+            assert_eq!(
+                activation.property.map(|p| p.name),
+                Some("env.CI".to_owned())
+            );
+            let guava: Dependency = "com.google.guava:guava:1".parse()?;
+            profile.modify_dependency(&guava, |dependency| {
+                dependency.scope = Some("test".to_owned());
+            })?;
+            assert_eq!(
+                profile.find_dependency(&guava)?.and_then(|d| d.scope),
+                Some("test".to_owned())
+            );
+            profile.set_activation(None)?;
+        }
+        let value = editor.write_to_str()?;
+        assert!(value.contains("<!-- Only on the build server -->"));
+        assert!(!value.contains("<activation>"));
+
+        assert!(editor.delete_profile("ci")?);
+        assert!(!editor.delete_profile("ci")?);
+        assert!(editor.get_profiles()?.is_empty());
+        Ok(())
+    }
+}
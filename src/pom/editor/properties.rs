@@ -0,0 +1,199 @@
+use edit_xml::{Document, Element};
+
+use crate::{
+    editor::{
+        ElementConverter, HasElementName, UpdatableElement, XMLEditorError, utils::text_value,
+    },
+    pom::Properties,
+};
+
+use super::PomEditor;
+
+impl PomEditor {
+    /// Gets the properties in the order they appear in the file
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let xml = r#"
+    /// <project>
+    ///   <properties>
+    ///     <java.version>17</java.version>
+    ///     <guava.version>33.0.0-jre</guava.version>
+    ///   </properties>
+    /// </project>
+    /// "#;
+    /// let editor = PomEditor::load_from_str(xml).unwrap();
+    /// let properties = editor.get_properties().unwrap();
+    /// let keys: Vec<_> = properties.0.keys().collect();
+    /// assert_eq!(keys, vec!["java.version", "guava.version"]);
+    /// ```
+    pub fn get_properties(&self) -> Result<Properties, XMLEditorError> {
+        get_properties(&self.document, self.root())
+    }
+    /// Gets the value of a single property
+    pub fn get_property(&self, name: &str) -> Option<String> {
+        get_property(&self.document, self.root(), name)
+    }
+    /// Sets a single property.
+    ///
+    /// Only the element of the property is changed. New properties are added to the end of `<properties>`
+    ///
+    /// Returns the previous value
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let mut editor = PomEditor::default();
+    /// editor.set_property("java.version", "17").unwrap();
+    /// let previous = editor.set_property("java.version", "21").unwrap();
+    /// assert_eq!(previous, Some("17".to_string()));
+    /// assert_eq!(editor.get_property("java.version"), Some("21".to_string()));
+    /// ```
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        value: impl Into<String>,
+    ) -> Result<Option<String>, XMLEditorError> {
+        let root = self.root();
+        set_property(&mut self.document, root, name, value.into())
+    }
+    /// Removes a single property. The rest of `<properties>` is left untouched
+    ///
+    /// Returns the removed value
+    pub fn remove_property(&mut self, name: &str) -> Result<Option<String>, XMLEditorError> {
+        let root = self.root();
+        remove_property(&mut self.document, root, name)
+    }
+    /// Replaces all the properties.
+    ///
+    /// Properties that did not change keep their position and comments. See [Properties::update_element]
+    pub fn set_properties(&mut self, properties: Properties) -> Result<(), XMLEditorError> {
+        let root = self.root();
+        set_properties(&mut self.document, root, properties)
+    }
+}
+/// `container` is either the project or a profile
+pub(super) fn get_properties(
+    document: &Document,
+    container: Element,
+) -> Result<Properties, XMLEditorError> {
+    match container.find(document, Properties::element_name()) {
+        Some(element) => Properties::from_element(element, document),
+        None => Ok(Properties::default()),
+    }
+}
+pub(super) fn get_property(document: &Document, container: Element, name: &str) -> Option<String> {
+    container
+        .find(document, Properties::element_name())?
+        .find(document, name)
+        .map(|element| text_value(element, document))
+}
+pub(super) fn set_property(
+    document: &mut Document,
+    container: Element,
+    name: &str,
+    value: String,
+) -> Result<Option<String>, XMLEditorError> {
+    let properties = crate::editor::utils::get_or_create_top_level_element(
+        Properties::element_name(),
+        document,
+        container,
+    );
+    if let Some(existing) = properties.find(document, name) {
+        let previous = text_value(existing, document);
+        if previous != value {
+            existing.set_text_content(document, value);
+        }
+        return Ok(Some(previous));
+    }
+    let element = Element::new(document, name);
+    element.set_text_content(document, value);
+    properties.push_child(document, element)?;
+    Ok(None)
+}
+pub(super) fn remove_property(
+    document: &mut Document,
+    container: Element,
+    name: &str,
+) -> Result<Option<String>, XMLEditorError> {
+    let Some(existing) = container
+        .find(document, Properties::element_name())
+        .and_then(|properties| properties.find(document, name))
+    else {
+        return Ok(None);
+    };
+    let previous = text_value(existing, document);
+    existing.detach(document)?;
+    Ok(Some(previous))
+}
+pub(super) fn set_properties(
+    document: &mut Document,
+    container: Element,
+    properties: Properties,
+) -> Result<(), XMLEditorError> {
+    match container.find(document, Properties::element_name()) {
+        Some(element) => properties.update_element(element, document),
+        None => {
+            let element = properties.into_element(document)?;
+            container.push_child(document, element)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const POM: &str = r#"<project>
+    <artifactId>test</artifactId>
+    <properties>
+        <!-- Java -->
+        <java.version>17</java.version>
+        <guava.version>33.0.0-jre</guava.version>
+        <!-- Testing -->
+        <junit.version>5.10.0</junit.version>
+    </properties>
+</project>"#;
+
+    #[test]
+    fn single_property() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        assert_eq!(
+            editor.get_property("guava.version"),
+            Some("33.0.0-jre".to_owned())
+        );
+        assert_eq!(
+            editor.set_property("java.version", "21")?,
+            Some("17".to_owned())
+        );
+        assert_eq!(editor.set_property("slf4j.version", "2.0.16")?, None);
+        assert_eq!(
+            editor.remove_property("guava.version")?,
+            Some("33.0.0-jre".to_owned())
+        );
+        assert_eq!(editor.remove_property("missing")?, None);
+
+        let written = editor.write_to_str()?;
+        assert!(written.contains("<!-- Java -->"));
+        assert!(written.contains("<!-- Testing -->"));
+        let keys: Vec<String> = editor.get_properties()?.0.keys().cloned().collect();
+        assert_eq!(keys, vec!["java.version", "junit.version", "slf4j.version"]);
+        Ok(())
+    }
+
+    #[test]
+    fn replace_all() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let mut properties = editor.get_properties()?;
+        properties.insert("junit.version", "5.11.0");
+        editor.set_properties(properties.clone())?;
+        assert_eq!(editor.get_properties()?, properties);
+        assert!(editor.write_to_str()?.contains("<!-- Testing -->"));
+
+        let mut editor = PomEditor::default();
+        editor.set_property("java.version", "17")?;
+        assert_eq!(editor.get_property("java.version"), Some("17".to_owned()));
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    editor::{
+        ElementConverter, HasElementName, Spanned, XMLEditorError,
+        utils::{get_all_children_of_element_located, text_value},
+    },
+    pom::{Dependency, Plugin, Properties},
+};
+
+use super::PomEditor;
+
+impl PomEditor {
+    /// Gets the dependencies with where they are in the source file
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::PomEditor;
+    /// let xml = r#"<project>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#;
+    /// let editor = PomEditor::load_from_str(xml).unwrap();
+    /// let dependencies = editor.get_spanned_dependencies().unwrap();
+    /// assert_eq!(dependencies[0].artifact_id, "guava");
+    /// assert_eq!(dependencies[0].span.unwrap().to_string(), "3:5");
+    /// ```
+    pub fn get_spanned_dependencies(&self) -> Result<Vec<Spanned<Dependency>>, XMLEditorError> {
+        self.get_spanned_list(&["dependencies"])
+    }
+    /// Gets the plugins of the build section with where they are in the source file
+    pub fn get_spanned_plugins(&self) -> Result<Vec<Spanned<Plugin>>, XMLEditorError> {
+        self.get_spanned_list(&["build", "plugins"])
+    }
+    /// Gets the properties as `(name, value)` with where they are in the source file. In the order they appear in the file
+    pub fn get_spanned_properties(&self) -> Vec<Spanned<(String, String)>> {
+        let Some(properties) = self.root().find(&self.document, Properties::element_name()) else {
+            return vec![];
+        };
+        properties
+            .child_elements(&self.document)
+            .into_iter()
+            .map(|element| {
+                let property = (
+                    element.name(&self.document).to_owned(),
+                    text_value(element, &self.document),
+                );
+                self.spans.spanned(element, property)
+            })
+            .collect()
+    }
+    fn get_spanned_list<E>(&self, path: &[&str]) -> Result<Vec<Spanned<E>>, XMLEditorError>
+    where
+        E: ElementConverter + HasElementName,
+    {
+        let Some(list) = path.iter().try_fold(self.root(), |element, name| {
+            element.find(&self.document, name)
+        }) else {
+            return Ok(vec![]);
+        };
+        let items = get_all_children_of_element_located(&self.document, list, &self.spans)?;
+        Ok(items
+            .into_iter()
+            .map(|(value, element)| self.spans.spanned(element, value))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::editor::{SourcePosition, utils::MissingElementError};
+
+    const POM: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+    <properties>
+        <java.version>17</java.version>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+        </dependency>
+        <dependency>
+            <groupId>org.slf4j</groupId>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin><artifactId>maven-compiler-plugin</artifactId></plugin>
+        </plugins>
+    </build>
+</project>
+"#;
+
+    #[test]
+    fn spans() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let properties = editor.get_spanned_properties();
+        assert_eq!(properties[0].value, ("java.version".into(), "17".into()));
+        assert_eq!(properties[0].span.unwrap().to_string(), "4:9");
+
+        let plugins = editor.get_spanned_plugins()?;
+        let span = plugins[0].span.unwrap();
+        assert_eq!(span.start.line, 17);
+        assert_eq!(span.end.line, 17);
+        assert_eq!(
+            &POM[span.start.offset..span.end.offset],
+            "<plugin><artifactId>maven-compiler-plugin</artifactId></plugin>"
+        );
+
+        // New elements do not have a span
+        editor.set_property("encoding", "UTF-8")?;
+        let properties = editor.get_spanned_properties();
+        assert!(properties[1].span.is_none());
+        assert!(properties[0].span.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn located_errors() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let error = editor.get_spanned_dependencies().unwrap_err();
+        assert_eq!(
+            error.position(),
+            Some(SourcePosition {
+                line: 11,
+                column: 9,
+                offset: POM
+                    .find("<dependency>\n            <groupId>org.slf4j")
+                    .unwrap(),
+            })
+        );
+        assert!(matches!(
+            error.without_position(),
+            XMLEditorError::MissingElement(MissingElementError("artifactId"))
+        ));
+        assert_eq!(error.to_string(), "11:9: dependency missing artifactId");
+        assert_eq!(
+            editor.get_dependencies().unwrap_err().to_string(),
+            "11:9: dependency missing artifactId"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{meta::DeployMetadata, types::MavenVersion};
+
+use super::{ArtifactLocation, PomEditor, UpdatePolicy, VersionSource};
+
+/// How big of a change moving between two versions is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum UpdateKind {
+    /// Only the third number or the qualifier changes. `1.2.3` to `1.2.4`
+    Incremental,
+    /// The second number changes. `1.2.3` to `1.3.0`
+    Minor,
+    /// The first number changes. `1.2.3` to `2.0.0`
+    Major,
+}
+impl UpdateKind {
+    /// Classifies the change from `current` to `candidate`
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::UpdateKind;
+    /// use maven_rs::types::MavenVersion;
+    /// let current = MavenVersion::from("1.2.3");
+    /// assert_eq!(UpdateKind::between(&current, &"1.2.4".into()), UpdateKind::Incremental);
+    /// assert_eq!(UpdateKind::between(&current, &"1.3".into()), UpdateKind::Minor);
+    /// assert_eq!(UpdateKind::between(&current, &"2.0.0".into()), UpdateKind::Major);
+    /// ```
+    pub fn between(current: &MavenVersion, candidate: &MavenVersion) -> Self {
+        let segment = |version: &MavenVersion, index: usize| {
+            version
+                .numeric_segments()
+                .get(index)
+                .copied()
+                .unwrap_or_default()
+        };
+        if segment(current, 0) != segment(candidate, 0) {
+            UpdateKind::Major
+        } else if segment(current, 1) != segment(candidate, 1) {
+            UpdateKind::Minor
+        } else {
+            UpdateKind::Incremental
+        }
+    }
+}
+/// The newer versions of a single artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AvailableUpdates {
+    pub location: ArtifactLocation,
+    pub group_id: String,
+    pub artifact_id: String,
+    pub current: String,
+    pub source: VersionSource,
+    /// The newest version with the same major and minor version
+    pub incremental: Option<String>,
+    /// The newest version with the same major version
+    pub minor: Option<String>,
+    /// The newest version with a different major version
+    pub major: Option<String>,
+}
+impl AvailableUpdates {
+    pub fn has_updates(&self) -> bool {
+        self.newest().is_some()
+    }
+    /// The newest of [AvailableUpdates::major], [AvailableUpdates::minor] and [AvailableUpdates::incremental]
+    pub fn newest(&self) -> Option<&str> {
+        self.major
+            .as_deref()
+            .or(self.minor.as_deref())
+            .or(self.incremental.as_deref())
+    }
+    /// The newest version of the kind
+    pub fn get(&self, kind: UpdateKind) -> Option<&str> {
+        match kind {
+            UpdateKind::Incremental => self.incremental.as_deref(),
+            UpdateKind::Minor => self.minor.as_deref(),
+            UpdateKind::Major => self.major.as_deref(),
+        }
+    }
+}
+/// Same as `mvn versions:display-dependency-updates` and `versions:display-plugin-updates`
+///
+/// The [fmt::Display] implementation writes the report as text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UpdatesReport {
+    pub artifacts: Vec<AvailableUpdates>,
+}
+impl UpdatesReport {
+    /// The artifacts that have a newer version
+    pub fn outdated(&self) -> impl Iterator<Item = &AvailableUpdates> {
+        self.artifacts
+            .iter()
+            .filter(|artifact| artifact.has_updates())
+    }
+}
+/// The sections of the text report
+const SECTIONS: [(&str, ArtifactLocation); 5] = [
+    ("Parent", ArtifactLocation::Parent),
+    ("Dependencies", ArtifactLocation::Dependency),
+    ("Dependency Management", ArtifactLocation::ManagedDependency),
+    ("Plugins", ArtifactLocation::Plugin),
+    ("Plugin Management", ArtifactLocation::ManagedPlugin),
+];
+impl fmt::Display for UpdatesReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .artifacts
+            .iter()
+            .map(|artifact| artifact.group_id.len() + artifact.artifact_id.len() + 1)
+            .max()
+            .unwrap_or_default();
+        for (title, location) in SECTIONS {
+            let mut artifacts = self
+                .artifacts
+                .iter()
+                .filter(|artifact| artifact.location == location)
+                .peekable();
+            if artifacts.peek().is_none() {
+                continue;
+            }
+            writeln!(f, "{title}:")?;
+            for artifact in artifacts {
+                let name = format!("{}:{}", artifact.group_id, artifact.artifact_id);
+                write!(f, "  {name:<width$}  {}", artifact.current)?;
+                let Some(newest) = artifact.newest() else {
+                    writeln!(f, " (up to date)")?;
+                    continue;
+                };
+                let available: Vec<String> = [
+                    ("incremental", UpdateKind::Incremental),
+                    ("minor", UpdateKind::Minor),
+                    ("major", UpdateKind::Major),
+                ]
+                .into_iter()
+                .filter_map(|(name, kind)| {
+                    artifact
+                        .get(kind)
+                        .map(|version| format!("{name}: {version}"))
+                })
+                .collect();
+                writeln!(f, " -> {newest} ({})", available.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+impl PomEditor {
+    /// Lists the newer versions of every artifact returned by [PomEditor::get_updatable_artifacts]. Nothing is changed.
+    ///
+    /// `policy` decides which versions are included. [UpdatePolicy::allow_major_updates] is ignored as major versions are listed separately.
+    pub fn updates_report_with<F>(&self, policy: &UpdatePolicy, mut metadata: F) -> UpdatesReport
+    where
+        F: FnMut(&str, &str) -> Option<DeployMetadata>,
+    {
+        let policy = UpdatePolicy {
+            allow_major_updates: true,
+            ..policy.clone()
+        };
+        let artifacts = self
+            .get_updatable_artifacts()
+            .into_iter()
+            .map(|artifact| {
+                let current = MavenVersion::from(artifact.version.as_str());
+                let mut updates = AvailableUpdates {
+                    location: artifact.reference.location,
+                    group_id: artifact.group_id,
+                    artifact_id: artifact.artifact_id,
+                    current: artifact.version,
+                    source: artifact.source,
+                    incremental: None,
+                    minor: None,
+                    major: None,
+                };
+                let Some(metadata) = metadata(&updates.group_id, &updates.artifact_id) else {
+                    return updates;
+                };
+                for (kind, slot) in [
+                    (UpdateKind::Incremental, &mut updates.incremental),
+                    (UpdateKind::Minor, &mut updates.minor),
+                    (UpdateKind::Major, &mut updates.major),
+                ] {
+                    *slot = metadata
+                        .newest_matching(|candidate| {
+                            policy.allows(&current, candidate)
+                                && UpdateKind::between(&current, candidate) == kind
+                        })
+                        .map(str::to_owned);
+                }
+                updates
+            })
+            .collect();
+        UpdatesReport { artifacts }
+    }
+    /// Fetches the `maven-metadata.xml` of every artifact from the repositories and creates a report with [PomEditor::updates_report_with]
+    #[cfg(feature = "resolver")]
+    pub async fn updates_report<R: crate::resolver::ResolvableRepository>(
+        &self,
+        policy: &UpdatePolicy,
+        repositories: &[R],
+        client: &reqwest::Client,
+    ) -> Result<UpdatesReport, super::UpdateError> {
+        let metadata = self.fetch_metadata(repositories, client).await?;
+        Ok(self.updates_report_with(policy, |group_id, artifact_id| {
+            metadata
+                .get(&(group_id.to_owned(), artifact_id.to_owned()))
+                .cloned()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::pom::editor::updates::tests::{POM, metadata};
+
+    #[test]
+    fn report() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let report = editor.updates_report_with(&UpdatePolicy::default(), metadata);
+        let guava = &report.artifacts[1];
+        assert_eq!(guava.artifact_id, "guava");
+        assert_eq!(guava.incremental, None);
+        assert_eq!(guava.minor.as_deref(), Some("31.1-jre"));
+        assert_eq!(guava.major.as_deref(), Some("33.1.0-jre"));
+        assert_eq!(report.outdated().count(), 5);
+
+        let engine = &report.artifacts[3];
+        assert_eq!(
+            engine.source,
+            VersionSource::Property("junit.version".into())
+        );
+        assert_eq!(engine.newest(), Some("5.10.0"));
+
+        assert_eq!(
+            report.to_string(),
+            "Parent:
+  com.example:parent                              1.0 -> 2.0 (minor: 1.1, major: 2.0)
+Dependencies:
+  com.google.guava:guava                          31.0-jre -> 33.1.0-jre (minor: 31.1-jre, major: 33.1.0-jre)
+  org.junit.jupiter:junit-jupiter-api             5.9.0 -> 5.11.0 (minor: 5.11.0)
+  org.junit.jupiter:junit-jupiter-engine          5.9.0 -> 5.10.0 (minor: 5.10.0)
+Plugins:
+  org.apache.maven.plugins:maven-compiler-plugin  3.11.0 -> 3.13.0 (minor: 3.13.0)
+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn up_to_date() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let report = editor.updates_report_with(&UpdatePolicy::default(), |_, _| None);
+        assert_eq!(report.outdated().count(), 0);
+        assert!(
+            report
+                .to_string()
+                .contains("com.google.guava:guava                          31.0-jre (up to date)")
+        );
+        Ok(())
+    }
+}
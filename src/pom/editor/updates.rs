@@ -0,0 +1,485 @@
+#[cfg(feature = "resolver")]
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::Serialize;
+#[cfg(feature = "resolver")]
+use thiserror::Error;
+
+use crate::{
+    editor::XMLEditorError, meta::DeployMetadata, pom::DEFAULT_PLUGIN_GROUP_ID, types::MavenVersion,
+};
+
+use super::{ArtifactLocation, ArtifactReference, PomEditor};
+
+/// Which versions an artifact is allowed to be updated to
+#[derive(Debug, Clone)]
+pub struct UpdatePolicy {
+    /// Allow updating to `-SNAPSHOT` versions
+    pub allow_snapshots: bool,
+    /// Allow updating to versions such as `2.0-rc1` or `2.0-beta`
+    pub allow_pre_releases: bool,
+    /// Allow updating to a version with a different first number. `1.9` to `2.0`
+    pub allow_major_updates: bool,
+    /// Versions that match any of these are never used. The whole version must match
+    pub ignored_versions: Vec<Regex>,
+}
+impl Default for UpdatePolicy {
+    fn default() -> Self {
+        Self {
+            allow_snapshots: false,
+            allow_pre_releases: false,
+            allow_major_updates: true,
+            ignored_versions: vec![],
+        }
+    }
+}
+impl UpdatePolicy {
+    /// Checks if an artifact at `current` can be updated to `candidate`
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::UpdatePolicy;
+    /// use maven_rs::types::MavenVersion;
+    /// let policy = UpdatePolicy {
+    ///     allow_major_updates: false,
+    ///     ignored_versions: vec![regex::Regex::new(r".*-M\d+").unwrap()],
+    ///     ..Default::default()
+    /// };
+    /// let current = MavenVersion::from("1.2");
+    /// assert!(policy.allows(&current, &MavenVersion::from("1.3")));
+    /// assert!(!policy.allows(&current, &MavenVersion::from("2.0")));
+    /// assert!(!policy.allows(&current, &MavenVersion::from("1.3-SNAPSHOT")));
+    /// assert!(!policy.allows(&current, &MavenVersion::from("1.4-M1")));
+    /// ```
+    pub fn allows(&self, current: &MavenVersion, candidate: &MavenVersion) -> bool {
+        if candidate <= current {
+            return false;
+        }
+        if candidate.is_snapshot() {
+            if !self.allow_snapshots {
+                return false;
+            }
+        } else if candidate.is_pre_release() && !self.allow_pre_releases {
+            return false;
+        }
+        if !self.allow_major_updates && major(candidate) != major(current) {
+            return false;
+        }
+        !self.ignored_versions.iter().any(|regex| {
+            regex
+                .find(candidate.as_str())
+                .is_some_and(|found| found.start() == 0 && found.end() == candidate.as_str().len())
+        })
+    }
+    /// The newest version in the metadata that `current` can be updated to
+    pub fn newest_version<'a>(
+        &self,
+        current: &str,
+        metadata: &'a DeployMetadata,
+    ) -> Option<&'a str> {
+        let current = MavenVersion::from(current);
+        metadata.newest_matching(|candidate| self.allows(&current, candidate))
+    }
+}
+/// The first number of the version
+fn major(version: &MavenVersion) -> u64 {
+    version
+        .numeric_segments()
+        .first()
+        .copied()
+        .unwrap_or_default()
+}
+/// Where the version of an [UpdatableArtifact] is written
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum VersionSource {
+    /// The version is written in the `<version>` of the reference
+    Literal,
+    /// The `<version>` is `${name}` and the property is defined in the pom
+    Property(String),
+}
+/// An artifact referenced by the pom with a version that can be changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdatableArtifact {
+    pub reference: ArtifactReference,
+    /// The group id. Plugins without a group id use [DEFAULT_PLUGIN_GROUP_ID]
+    pub group_id: String,
+    pub artifact_id: String,
+    /// The current version. With the property resolved
+    pub version: String,
+    pub source: VersionSource,
+}
+/// A change made by [PomEditor::use_latest_releases]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactUpdate {
+    pub location: ArtifactLocation,
+    pub group_id: String,
+    pub artifact_id: String,
+    pub previous: String,
+    pub version: String,
+    pub source: VersionSource,
+}
+#[cfg(feature = "resolver")]
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    #[error(transparent)]
+    Resolver(#[from] crate::pom::resolve::DependencyResolverError),
+    #[error(transparent)]
+    XMLEditorError(#[from] XMLEditorError),
+}
+impl PomEditor {
+    /// The dependencies, managed dependencies, plugins, managed plugins and parent that have a version that can be updated
+    ///
+    /// References with a version range, a version made of multiple parts or a property that is not defined in this pom are skipped.
+    pub fn get_updatable_artifacts(&self) -> Vec<UpdatableArtifact> {
+        self.get_artifact_references()
+            .into_iter()
+            .filter_map(|reference| {
+                let is_plugin = match reference.location {
+                    ArtifactLocation::Plugin | ArtifactLocation::ManagedPlugin => true,
+                    ArtifactLocation::Parent
+                    | ArtifactLocation::Dependency
+                    | ArtifactLocation::ManagedDependency => false,
+                    ArtifactLocation::PluginDependency | ArtifactLocation::Extension => {
+                        return None;
+                    }
+                };
+                let group_id = match reference.group_id.as_deref() {
+                    Some(group_id) => self.resolve_group_id(group_id)?,
+                    None if is_plugin => DEFAULT_PLUGIN_GROUP_ID.to_owned(),
+                    None => return None,
+                };
+                let written = reference.version.clone()?;
+                let (version, source) = match written
+                    .strip_prefix("${")
+                    .and_then(|name| name.strip_suffix('}'))
+                {
+                    Some(name) => (
+                        self.get_property(name)?.trim().to_owned(),
+                        VersionSource::Property(name.to_owned()),
+                    ),
+                    None => (written, VersionSource::Literal),
+                };
+                if version.is_empty() || version.contains("${") || version.starts_with(['[', '(']) {
+                    return None;
+                }
+                Some(UpdatableArtifact {
+                    group_id,
+                    artifact_id: reference.artifact_id.clone(),
+                    version,
+                    source,
+                    reference,
+                })
+            })
+            .collect()
+    }
+    /// Updates every artifact to the newest version allowed by `policy`. `metadata` is called with the group id and artifact id.
+    ///
+    /// Literal versions are changed in place. If the version comes from a property the property is updated instead.
+    /// A property used by multiple artifacts is only updated if all of them can be updated. It is set to the lowest of their newest versions.
+    ///
+    /// ```rust
+    /// use maven_rs::meta::{DeployMetadata, StableVersioning, StableVersions};
+    /// use maven_rs::pom::editor::{PomEditor, UpdatePolicy};
+    /// let mut editor = PomEditor::load_from_str(r#"
+    /// <project>
+    ///   <dependencies>
+    ///     <dependency>
+    ///       <groupId>com.google.guava</groupId>
+    ///       <artifactId>guava</artifactId>
+    ///       <version>32.0.0-jre</version>
+    ///     </dependency>
+    ///   </dependencies>
+    /// </project>"#).unwrap();
+    /// let updates = editor
+    ///     .use_latest_releases_with(&UpdatePolicy::default(), |group_id, artifact_id| {
+    ///         Some(DeployMetadata {
+    ///             group_id: group_id.to_owned(),
+    ///             artifact_id: artifact_id.to_owned(),
+    ///             versioning: StableVersioning {
+    ///                 versions: StableVersions {
+    ///                     version: vec!["32.0.0-jre".to_owned(), "33.0.0-jre".to_owned()],
+    ///                 },
+    ///                 ..Default::default()
+    ///             },
+    ///         })
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(updates[0].version, "33.0.0-jre");
+    /// ```
+    pub fn use_latest_releases_with<F>(
+        &mut self,
+        policy: &UpdatePolicy,
+        mut metadata: F,
+    ) -> Result<Vec<ArtifactUpdate>, XMLEditorError>
+    where
+        F: FnMut(&str, &str) -> Option<DeployMetadata>,
+    {
+        let mut updates = Vec::new();
+        let mut properties: Vec<(String, Vec<PropertyUser>)> = Vec::new();
+        for artifact in self.get_updatable_artifacts() {
+            let newest = metadata(&artifact.group_id, &artifact.artifact_id).and_then(|metadata| {
+                policy
+                    .newest_version(&artifact.version, &metadata)
+                    .map(str::to_owned)
+            });
+            match &artifact.source {
+                VersionSource::Literal => {
+                    if let Some(newest) = newest {
+                        self.set_artifact_reference_version(&artifact.reference, newest.as_str())?;
+                        updates.push(artifact_update(artifact, newest));
+                    }
+                }
+                VersionSource::Property(name) => {
+                    match properties.iter_mut().find(|(existing, _)| existing == name) {
+                        Some((_, artifacts)) => artifacts.push((artifact, newest)),
+                        None => properties.push((name.clone(), vec![(artifact, newest)])),
+                    }
+                }
+            }
+        }
+        for (name, artifacts) in properties {
+            let Some(newest) = artifacts
+                .iter()
+                .map(|(_, newest)| newest.as_deref().map(MavenVersion::from))
+                .collect::<Option<Vec<_>>>()
+                .and_then(|versions| versions.into_iter().min())
+            else {
+                continue;
+            };
+            let newest = newest.as_str().to_owned();
+            self.set_property(&name, newest.as_str())?;
+            updates.extend(
+                artifacts
+                    .into_iter()
+                    .map(|(artifact, _)| artifact_update(artifact, newest.clone())),
+            );
+        }
+        Ok(updates)
+    }
+    /// Same as `mvn versions:use-latest-releases`. Fetches the `maven-metadata.xml` of every artifact from the repositories
+    /// and updates them with [PomEditor::use_latest_releases_with]
+    ///
+    /// The versions from all repositories are combined.
+    #[cfg(feature = "resolver")]
+    pub async fn use_latest_releases<R: crate::resolver::ResolvableRepository>(
+        &mut self,
+        policy: &UpdatePolicy,
+        repositories: &[R],
+        client: &reqwest::Client,
+    ) -> Result<Vec<ArtifactUpdate>, UpdateError> {
+        let metadata = self.fetch_metadata(repositories, client).await?;
+        let updates = self.use_latest_releases_with(policy, |group_id, artifact_id| {
+            metadata
+                .get(&(group_id.to_owned(), artifact_id.to_owned()))
+                .cloned()
+        })?;
+        Ok(updates)
+    }
+    /// Fetches the metadata of every [UpdatableArtifact] and combines the versions from all repositories
+    #[cfg(feature = "resolver")]
+    pub(crate) async fn fetch_metadata<R: crate::resolver::ResolvableRepository>(
+        &self,
+        repositories: &[R],
+        client: &reqwest::Client,
+    ) -> Result<HashMap<(String, String), DeployMetadata>, UpdateError> {
+        let mut result: HashMap<(String, String), DeployMetadata> = HashMap::new();
+        for artifact in self.get_updatable_artifacts() {
+            let key = (artifact.group_id.clone(), artifact.artifact_id.clone());
+            if result.contains_key(&key) {
+                continue;
+            }
+            let dependency = crate::pom::Dependency {
+                group_id: artifact.group_id,
+                artifact_id: artifact.artifact_id,
+                ..Default::default()
+            };
+            for repository in repositories {
+                let Some(metadata) = dependency
+                    .resolve_deploy_meta_data(repository, client)
+                    .await?
+                else {
+                    continue;
+                };
+                match result.get_mut(&key) {
+                    Some(existing) => {
+                        let versions = &mut existing.versioning.versions.version;
+                        for version in metadata.versioning.versions.version {
+                            if !versions.contains(&version) {
+                                versions.push(version);
+                            }
+                        }
+                    }
+                    None => {
+                        result.insert(key.clone(), metadata);
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+/// An artifact that reads its version from a property and the newest version it can be updated to
+type PropertyUser = (UpdatableArtifact, Option<String>);
+fn artifact_update(artifact: UpdatableArtifact, version: String) -> ArtifactUpdate {
+    ArtifactUpdate {
+        location: artifact.reference.location,
+        group_id: artifact.group_id,
+        artifact_id: artifact.artifact_id,
+        previous: artifact.version,
+        version,
+        source: artifact.source,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::meta::{StableVersioning, StableVersions};
+
+    pub(crate) const POM: &str = r#"<project>
+    <groupId>com.example</groupId>
+    <artifactId>app</artifactId>
+    <version>1.0</version>
+    <parent>
+        <groupId>com.example</groupId>
+        <artifactId>parent</artifactId>
+        <version>1.0</version>
+    </parent>
+    <properties>
+        <!-- Shared by all of junit -->
+        <junit.version>5.9.0</junit.version>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>31.0-jre</version>
+        </dependency>
+        <dependency>
+            <groupId>org.junit.jupiter</groupId>
+            <artifactId>junit-jupiter-api</artifactId>
+            <version>${junit.version}</version>
+        </dependency>
+        <dependency>
+            <groupId>org.junit.jupiter</groupId>
+            <artifactId>junit-jupiter-engine</artifactId>
+            <version>${junit.version}</version>
+        </dependency>
+        <dependency>
+            <groupId>com.example</groupId>
+            <artifactId>ranged</artifactId>
+            <version>[1.0,2.0)</version>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.11.0</version>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+
+    pub(crate) fn metadata(group_id: &str, artifact_id: &str) -> Option<DeployMetadata> {
+        let versions: &[&str] = match artifact_id {
+            "parent" => &["1.0", "1.1", "2.0"],
+            "guava" => &[
+                "31.0-jre",
+                "31.1-jre",
+                "32.0.0-jre",
+                "33.0.0-jre",
+                "33.1.0-jre",
+            ],
+            "junit-jupiter-api" => &["5.9.0", "5.10.0", "5.11.0", "6.0.0-M1"],
+            "junit-jupiter-engine" => &["5.9.0", "5.10.0"],
+            "maven-compiler-plugin" => &["3.11.0", "3.13.0", "4.0.0-beta-1"],
+            _ => return None,
+        };
+        Some(DeployMetadata {
+            group_id: group_id.to_owned(),
+            artifact_id: artifact_id.to_owned(),
+            versioning: StableVersioning {
+                versions: StableVersions {
+                    version: versions.iter().map(|version| version.to_string()).collect(),
+                },
+                ..Default::default()
+            },
+        })
+    }
+
+    #[test]
+    fn updatable_artifacts() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let artifacts: Vec<_> = editor
+            .get_updatable_artifacts()
+            .into_iter()
+            .map(|artifact| (artifact.group_id, artifact.artifact_id, artifact.version))
+            .collect();
+        let expected = [
+            ("com.example", "parent", "1.0"),
+            ("com.google.guava", "guava", "31.0-jre"),
+            ("org.junit.jupiter", "junit-jupiter-api", "5.9.0"),
+            ("org.junit.jupiter", "junit-jupiter-engine", "5.9.0"),
+            (
+                "org.apache.maven.plugins",
+                "maven-compiler-plugin",
+                "3.11.0",
+            ),
+        ]
+        .map(|(group_id, artifact_id, version)| {
+            (
+                group_id.to_owned(),
+                artifact_id.to_owned(),
+                version.to_owned(),
+            )
+        });
+        assert_eq!(artifacts, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn use_latest_releases() -> anyhow::Result<()> {
+        let mut editor = PomEditor::load_from_str(POM)?;
+        let policy = UpdatePolicy {
+            allow_major_updates: false,
+            ignored_versions: vec![Regex::new(r"33\.1\..*")?],
+            ..Default::default()
+        };
+        let updates = editor.use_latest_releases_with(&policy, metadata)?;
+        let summary: Vec<_> = updates
+            .iter()
+            .map(|update| (update.artifact_id.as_str(), update.version.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("parent", "1.1"),
+                ("guava", "31.1-jre"),
+                ("maven-compiler-plugin", "3.13.0"),
+                ("junit-jupiter-api", "5.10.0"),
+                ("junit-jupiter-engine", "5.10.0"),
+            ]
+        );
+        assert_eq!(
+            editor.get_property("junit.version"),
+            Some("5.10.0".to_owned())
+        );
+        let written = editor.write_to_str()?;
+        assert!(written.contains("<!-- Shared by all of junit -->"));
+        assert!(written.contains("<version>${junit.version}</version>"));
+        assert!(written.contains("<version>[1.0,2.0)</version>"));
+
+        let mut editor = PomEditor::load_from_str(POM)?;
+        editor.use_latest_releases_with(&UpdatePolicy::default(), metadata)?;
+        assert_eq!(
+            editor.get_parent()?.unwrap().version.as_deref(),
+            Some("2.0")
+        );
+        let guava = editor.get_updatable_artifacts().remove(1);
+        assert_eq!(guava.version, "33.1.0-jre");
+        Ok(())
+    }
+}
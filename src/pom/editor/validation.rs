@@ -0,0 +1,743 @@
+use std::{collections::HashMap, fmt};
+
+use edit_xml::{Document, Element, Node};
+use serde::Serialize;
+
+use crate::{
+    editor::{SourcePosition, utils::text_value},
+    pom::DEFAULT_PLUGIN_GROUP_ID,
+};
+
+use super::PomEditor;
+
+/// The validation levels of Maven. Higher levels turn some warnings into errors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum ValidationLevel {
+    /// Invalid plugin versions and booleans are warnings instead of errors
+    Maven2_0,
+    /// The level used by `mvn` when building a project
+    #[default]
+    Maven3_0,
+    Maven3_1,
+    Maven4_0,
+}
+/// The severities of a [ModelProblem]. Ordered from the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Severity {
+    /// The model can not be built at all
+    Fatal,
+    Error,
+    Warning,
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Fatal => f.write_str("FATAL"),
+            Severity::Error => f.write_str("ERROR"),
+            Severity::Warning => f.write_str("WARNING"),
+        }
+    }
+}
+/// Which checks are run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+pub enum ModelKind {
+    /// The pom as it is written. Values inherited from the parent may be missing
+    #[default]
+    Raw,
+    /// The pom after inheritance and interpolation. Everything required must be present
+    Effective,
+}
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationOptions {
+    pub level: ValidationLevel,
+    pub kind: ModelKind,
+}
+/// A single problem found by [PomEditor::validate]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelProblem {
+    pub severity: Severity,
+    /// The field in the same format Maven uses. Such as `dependencies.dependency.version`
+    pub field: String,
+    pub message: String,
+    /// Where the problem is in the source file. None if the pom was not loaded from a file
+    pub position: Option<SourcePosition>,
+}
+impl fmt::Display for ModelProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.severity, self.message)?;
+        if let Some(position) = &self.position {
+            write!(f, " @ line {}, column {}", position.line, position.column)?;
+        }
+        Ok(())
+    }
+}
+/// The problems found by [PomEditor::validate] in the order they were found
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ModelProblems {
+    pub problems: Vec<ModelProblem>,
+}
+impl ModelProblems {
+    /// True if there is a [Severity::Error] or [Severity::Fatal] problem
+    pub fn has_errors(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| problem.severity <= Severity::Error)
+    }
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &ModelProblem> {
+        self.problems
+            .iter()
+            .filter(move |problem| problem.severity == severity)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+impl fmt::Display for ModelProblems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for problem in &self.problems {
+            writeln!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
+/// The only model version Maven 3 supports. Maven 4 also accepts [MODEL_VERSION_4_1]
+pub static MODEL_VERSION_4_0: &str = "4.0.0";
+pub static MODEL_VERSION_4_1: &str = "4.1.0";
+/// The scopes a dependency can have
+pub static DEPENDENCY_SCOPES: [&str; 5] = ["compile", "provided", "runtime", "test", "system"];
+/// Expressions that can be used in the version. See [Maven CI Friendly Versions](https://maven.apache.org/maven-ci-friendly.html)
+static CI_FRIENDLY_PROPERTIES: [&str; 3] = ["revision", "sha1", "changelist"];
+/// Expressions without the `project.` prefix that Maven still accepts but warns about
+static DEPRECATED_EXPRESSIONS: [&str; 3] = ["groupId", "artifactId", "version"];
+impl PomEditor {
+    /// Validates the pom the same way Maven does when it builds the model.
+    ///
+    /// [PomEditor] accepts any document with a `<project>` root. This finds the problems Maven would report.
+    /// Run it with [ModelKind::Raw] on a pom as written and [ModelKind::Effective] on a pom that has its parents merged in.
+    ///
+    /// ```rust
+    /// use maven_rs::pom::editor::{PomEditor, Severity, ValidationOptions};
+    /// let editor = PomEditor::load_from_str(r#"<project>
+    ///   <modelVersion>4.0.0</modelVersion>
+    ///   <groupId>${group}</groupId>
+    ///   <artifactId>example</artifactId>
+    ///   <version>1.0</version>
+    /// </project>"#).unwrap();
+    /// let problems = editor.validate(ValidationOptions::default());
+    /// assert!(!problems.has_errors());
+    /// assert_eq!(
+    ///     problems.to_string(),
+    ///     "[WARNING] 'groupId' contains an expression but should be a constant. @ line 3, column 3\n"
+    /// );
+    /// ```
+    pub fn validate(&self, options: ValidationOptions) -> ModelProblems {
+        let mut validator = Validator {
+            editor: self,
+            document: &self.document,
+            level: options.level,
+            problems: Vec::new(),
+        };
+        match options.kind {
+            ModelKind::Raw => validator.validate_raw(),
+            ModelKind::Effective => validator.validate_effective(),
+        }
+        ModelProblems {
+            problems: validator.problems,
+        }
+    }
+}
+struct Validator<'a> {
+    editor: &'a PomEditor,
+    document: &'a Document,
+    level: ValidationLevel,
+    problems: Vec<ModelProblem>,
+}
+impl Validator<'_> {
+    fn add(
+        &mut self,
+        severity: Severity,
+        field: impl Into<String>,
+        message: impl Into<String>,
+        element: Element,
+    ) {
+        self.problems.push(ModelProblem {
+            severity,
+            field: field.into(),
+            message: message.into(),
+            position: self.editor.spans.get(element).map(|span| span.start),
+        });
+    }
+    /// An error from `level` and a warning before it
+    fn error_on(&self, level: ValidationLevel) -> Severity {
+        if self.level >= level {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    }
+    fn text(&self, element: Element, name: &str) -> Option<(String, Element)> {
+        element
+            .find(self.document, name)
+            .map(|child| (text_value(child, self.document), child))
+    }
+    fn validate_raw(&mut self) {
+        let root = self.editor.root();
+        match self.text(root, "modelVersion") {
+            None => self.add(
+                Severity::Error,
+                "modelVersion",
+                "'modelVersion' is missing.",
+                root,
+            ),
+            Some((version, element)) => {
+                let supported = version == MODEL_VERSION_4_0
+                    || (self.level >= ValidationLevel::Maven4_0 && version == MODEL_VERSION_4_1);
+                if !supported {
+                    self.add(
+                        Severity::Error,
+                        "modelVersion",
+                        format!("'modelVersion' of '{version}' is not supported."),
+                        element,
+                    );
+                }
+            }
+        }
+        self.validate_raw_parent(root);
+        for name in ["groupId", "artifactId"] {
+            if let Some((value, element)) = self.text(root, name)
+                && value.contains("${")
+            {
+                self.add(
+                    Severity::Warning,
+                    name,
+                    format!("'{name}' contains an expression but should be a constant."),
+                    element,
+                );
+            }
+        }
+        if let Some((version, element)) = self.text(root, "version")
+            && variables(&version).any(|variable| !CI_FRIENDLY_PROPERTIES.contains(&variable))
+        {
+            self.add(
+                Severity::Warning,
+                "version",
+                "'version' contains an expression but should be a constant.",
+                element,
+            );
+        }
+        if let Some(modules) = root.find(self.document, "modules")
+            && !modules.child_elements(self.document).is_empty()
+        {
+            let (found, element) = self
+                .text(root, "packaging")
+                .unwrap_or_else(|| ("jar".to_owned(), root));
+            if found != "pom" {
+                self.add(
+                    Severity::Error,
+                    "packaging",
+                    format!(
+                        "'packaging' with value '{found}' is invalid. Aggregator projects require 'pom' as packaging."
+                    ),
+                    element,
+                );
+            }
+        }
+        for (prefix, container) in self.containers(root) {
+            self.validate_duplicate_dependencies(&prefix, container);
+            self.validate_duplicate_plugins(&prefix, container);
+        }
+        self.validate_expressions(root);
+    }
+    fn validate_raw_parent(&mut self, root: Element) {
+        let Some(parent) = root.find(self.document, "parent") else {
+            return;
+        };
+        for name in ["groupId", "artifactId", "version"] {
+            if self
+                .text(parent, name)
+                .is_none_or(|(value, _)| value.is_empty())
+            {
+                self.add(
+                    Severity::Fatal,
+                    format!("parent.{name}"),
+                    format!("'parent.{name}' is missing."),
+                    parent,
+                );
+            }
+        }
+        let id = |element| {
+            (
+                self.text(element, "groupId").map(|(value, _)| value),
+                self.text(element, "artifactId").map(|(value, _)| value),
+            )
+        };
+        let (group_id, artifact_id) = id(parent);
+        if group_id.is_some() && (group_id.clone(), artifact_id.clone()) == id(root) {
+            self.add(
+                Severity::Fatal,
+                "parent.artifactId",
+                format!(
+                    "The parent element cannot have the same groupId:artifactId as the project. {}:{}",
+                    group_id.unwrap_or_default(),
+                    artifact_id.unwrap_or_default()
+                ),
+                parent,
+            );
+        }
+    }
+    /// The project and every profile with the prefix of their fields
+    fn containers(&self, root: Element) -> Vec<(String, Element)> {
+        let mut result = vec![(String::new(), root)];
+        if let Some(profiles) = root.find(self.document, "profiles") {
+            for profile in profiles.child_elements(self.document) {
+                let id = self
+                    .text(profile, "id")
+                    .map(|(id, _)| id)
+                    .unwrap_or_default();
+                result.push((format!("profiles.profile[{id}]."), profile));
+            }
+        }
+        result
+    }
+    fn dependency_lists(&self, prefix: &str, container: Element) -> Vec<(String, Element)> {
+        let mut result = Vec::new();
+        if let Some(dependencies) = container.find(self.document, "dependencies") {
+            result.push((format!("{prefix}dependencies.dependency"), dependencies));
+        }
+        if let Some(dependencies) = container
+            .find(self.document, "dependencyManagement")
+            .and_then(|management| management.find(self.document, "dependencies"))
+        {
+            result.push((
+                format!("{prefix}dependencyManagement.dependencies.dependency"),
+                dependencies,
+            ));
+        }
+        result
+    }
+    /// `groupId:artifactId:type[:classifier]`
+    fn dependency_key(&self, dependency: Element) -> String {
+        let text = |name| self.text(dependency, name).map(|(value, _)| value);
+        let mut key = format!(
+            "{}:{}:{}",
+            text("groupId").unwrap_or_default(),
+            text("artifactId").unwrap_or_default(),
+            text("type").unwrap_or_else(|| "jar".to_owned())
+        );
+        if let Some(classifier) = text("classifier") {
+            key.push(':');
+            key.push_str(&classifier);
+        }
+        key
+    }
+    fn validate_duplicate_dependencies(&mut self, prefix: &str, container: Element) {
+        let severity = self.error_on(ValidationLevel::Maven3_1);
+        for (field, dependencies) in self.dependency_lists(prefix, container) {
+            let mut seen = HashMap::new();
+            for dependency in dependencies.child_elements(self.document) {
+                let key = self.dependency_key(dependency);
+                if seen.insert(key.clone(), dependency).is_none() {
+                    continue;
+                }
+                let version = self
+                    .text(dependency, "version")
+                    .map(|(version, _)| version)
+                    .unwrap_or_else(|| "(?)".to_owned());
+                self.add(
+                    severity,
+                    format!("{field}.(groupId:artifactId:type:classifier)"),
+                    format!(
+                        "'{field}.(groupId:artifactId:type:classifier)' must be unique: {key} -> duplicate declaration of version {version}"
+                    ),
+                    dependency,
+                );
+            }
+        }
+    }
+    /// `groupId:artifactId` with the default group of plugins
+    fn plugin_key(&self, plugin: Element) -> String {
+        format!(
+            "{}:{}",
+            self.text(plugin, "groupId")
+                .map(|(value, _)| value)
+                .unwrap_or_else(|| DEFAULT_PLUGIN_GROUP_ID.to_owned()),
+            self.text(plugin, "artifactId")
+                .map(|(value, _)| value)
+                .unwrap_or_default()
+        )
+    }
+    fn validate_duplicate_plugins(&mut self, prefix: &str, container: Element) {
+        let severity = self.error_on(ValidationLevel::Maven3_1);
+        let Some(build) = container.find(self.document, "build") else {
+            return;
+        };
+        let mut lists = Vec::new();
+        if let Some(plugins) = build.find(self.document, "plugins") {
+            lists.push((format!("{prefix}build.plugins.plugin"), plugins));
+        }
+        if let Some(plugins) = build
+            .find(self.document, "pluginManagement")
+            .and_then(|management| management.find(self.document, "plugins"))
+        {
+            lists.push((
+                format!("{prefix}build.pluginManagement.plugins.plugin"),
+                plugins,
+            ));
+        }
+        for (field, plugins) in lists {
+            let mut seen = HashMap::new();
+            for plugin in plugins.child_elements(self.document) {
+                let key = self.plugin_key(plugin);
+                if seen.insert(key.clone(), plugin).is_some() {
+                    self.add(
+                        severity,
+                        format!("{field}.(groupId:artifactId)"),
+                        format!(
+                            "'{field}.(groupId:artifactId)' must be unique but found duplicate declaration of plugin {key}"
+                        ),
+                        plugin,
+                    );
+                }
+            }
+        }
+    }
+    /// Warns about `${pom.*}` and the unprefixed `${version}` style expressions
+    fn validate_expressions(&mut self, element: Element) {
+        let has_text = element
+            .children(self.document)
+            .iter()
+            .any(|node| matches!(node, Node::Text(_)));
+        if has_text && element.child_elements(self.document).is_empty() {
+            let text = text_value(element, self.document);
+            for variable in variables(&text) {
+                let replacement = if let Some(rest) = variable.strip_prefix("pom.") {
+                    rest
+                } else if DEPRECATED_EXPRESSIONS.contains(&variable) {
+                    variable
+                } else {
+                    continue;
+                };
+                self.add(
+                    Severity::Warning,
+                    element.name(self.document),
+                    format!(
+                        "The expression ${{{variable}}} is deprecated. Please use ${{project.{replacement}}} instead."
+                    ),
+                    element,
+                );
+            }
+        }
+        for child in element.child_elements(self.document) {
+            self.validate_expressions(child);
+        }
+    }
+    fn validate_effective(&mut self) {
+        let root = self.editor.root();
+        for name in ["groupId", "artifactId", "version"] {
+            self.validate_required(name, root, name, name != "version", None);
+        }
+        for (prefix, container) in self.containers(root) {
+            for (field, dependencies) in self.dependency_lists(&prefix, container) {
+                let managed = field.contains("dependencyManagement");
+                for dependency in dependencies.child_elements(self.document) {
+                    self.validate_effective_dependency(&field, dependency, managed);
+                }
+            }
+            if let Some(plugins) = container
+                .find(self.document, "build")
+                .and_then(|build| build.find(self.document, "plugins"))
+            {
+                let field = format!("{prefix}build.plugins.plugin");
+                for plugin in plugins.child_elements(self.document) {
+                    self.validate_effective_plugin(&field, plugin);
+                }
+            }
+        }
+    }
+    /// Checks the version and the boolean fields of a plugin. Errors from Maven 3.0
+    fn validate_effective_plugin(&mut self, field: &str, plugin: Element) {
+        let severity = self.error_on(ValidationLevel::Maven3_0);
+        let key = self.plugin_key(plugin);
+        if let Some((version, element)) = self.text(plugin, "version")
+            && matches!(version.as_str(), "" | "RELEASE" | "LATEST")
+        {
+            self.add(
+                severity,
+                format!("{field}.version"),
+                format!("'{field}.version' for {key} must be a valid version but is '{version}'."),
+                element,
+            );
+        }
+        for name in ["inherited", "extensions"] {
+            if let Some((value, element)) = self.text(plugin, name)
+                && !value.is_empty()
+                && !value.eq_ignore_ascii_case("true")
+                && !value.eq_ignore_ascii_case("false")
+            {
+                self.add(
+                    severity,
+                    format!("{field}.{name}"),
+                    format!(
+                        "'{field}.{name}' for {key} must be 'true' or 'false' but is '{value}'."
+                    ),
+                    element,
+                );
+            }
+        }
+    }
+    /// Checks `name` is present in `element` and is a valid id if `is_id`.
+    ///
+    /// `field` is the name in the messages and `source` is the dependency the field belongs to
+    fn validate_required(
+        &mut self,
+        field: &str,
+        element: Element,
+        name: &str,
+        is_id: bool,
+        source: Option<&str>,
+    ) {
+        let hint = source
+            .map(|source| format!(" for {source}"))
+            .unwrap_or_default();
+        match self.text(element, name) {
+            None => self.add(
+                Severity::Error,
+                field,
+                format!("'{field}'{hint} is missing."),
+                element,
+            ),
+            Some((value, child)) if value.is_empty() => self.add(
+                Severity::Error,
+                field,
+                format!("'{field}'{hint} is missing."),
+                child,
+            ),
+            Some((value, child)) if is_id && !is_valid_id(&value) => self.add(
+                Severity::Error,
+                field,
+                format!("'{field}'{hint} with value '{value}' does not match a valid id pattern."),
+                child,
+            ),
+            Some(_) => {}
+        }
+    }
+    fn validate_effective_dependency(&mut self, field: &str, dependency: Element, managed: bool) {
+        let key = self.dependency_key(dependency);
+        for name in ["groupId", "artifactId", "version"] {
+            self.validate_required(
+                &format!("{field}.{name}"),
+                dependency,
+                name,
+                name != "version",
+                Some(&key),
+            );
+        }
+        let scope = self.text(dependency, "scope");
+        let system_path = self.text(dependency, "systemPath");
+        let is_system = scope.as_ref().is_some_and(|(scope, _)| scope == "system");
+        if is_system {
+            if system_path.is_none() {
+                self.add(
+                    Severity::Error,
+                    format!("{field}.systemPath"),
+                    format!("'{field}.systemPath' for {key} is missing."),
+                    dependency,
+                );
+            }
+            if self.level >= ValidationLevel::Maven4_0 {
+                self.add(
+                    Severity::Warning,
+                    format!("{field}.scope"),
+                    format!(
+                        "'{field}.scope' for {key} declares usage of deprecated 'system' scope"
+                    ),
+                    dependency,
+                );
+            }
+        } else if let Some((_, element)) = system_path {
+            self.add(
+                Severity::Error,
+                format!("{field}.systemPath"),
+                format!(
+                    "'{field}.systemPath' for {key} must be omitted. This field may only be specified for a dependency with system scope"
+                ),
+                element,
+            );
+        }
+        if let Some((scope, element)) = scope {
+            let valid =
+                DEPENDENCY_SCOPES.contains(&scope.as_str()) || (managed && scope == "import");
+            if !valid {
+                let mut scopes = DEPENDENCY_SCOPES.to_vec();
+                if managed {
+                    scopes.push("import");
+                }
+                self.add(
+                    Severity::Warning,
+                    format!("{field}.scope"),
+                    format!(
+                        "'{field}.scope' for {key} must be one of [{}] but is '{scope}'.",
+                        scopes.join(", ")
+                    ),
+                    element,
+                );
+            }
+        }
+    }
+}
+/// The names of the `${...}` expressions in the value
+fn variables(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split("${")
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(variable, _)| variable.trim()))
+}
+/// Matches `[A-Za-z0-9_\-.]+`
+fn is_valid_id(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const POM: &str = r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <parent>
+        <groupId>com.example</groupId>
+        <artifactId>parent</artifactId>
+    </parent>
+    <artifactId>example</artifactId>
+    <version>${revision}</version>
+    <modules>
+        <module>core</module>
+    </modules>
+    <dependencies>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>${pom.version}</version>
+        </dependency>
+        <dependency>
+            <groupId>com.google.guava</groupId>
+            <artifactId>guava</artifactId>
+            <version>33.0.0-jre</version>
+        </dependency>
+        <dependency>
+            <groupId>com.sun</groupId>
+            <artifactId>tools</artifactId>
+            <scope>system</scope>
+        </dependency>
+    </dependencies>
+</project>"#;
+
+    fn messages(problems: &ModelProblems) -> Vec<String> {
+        problems.problems.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn raw() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let problems = editor.validate(ValidationOptions::default());
+        assert_eq!(
+            messages(&problems),
+            vec![
+                "[FATAL] 'parent.version' is missing. @ line 3, column 5",
+                "[ERROR] 'packaging' with value 'jar' is invalid. Aggregator projects require 'pom' as packaging. @ line 1, column 1",
+                "[WARNING] 'dependencies.dependency.(groupId:artifactId:type:classifier)' must be unique: com.google.guava:guava:jar -> duplicate declaration of version 33.0.0-jre @ line 18, column 9",
+                "[WARNING] The expression ${pom.version} is deprecated. Please use ${project.version} instead. @ line 16, column 13",
+            ]
+        );
+        assert!(problems.has_errors());
+
+        let strict = editor.validate(ValidationOptions {
+            level: ValidationLevel::Maven3_1,
+            kind: ModelKind::Raw,
+        });
+        assert_eq!(strict.problems[2].severity, Severity::Error);
+        assert_eq!(
+            strict.problems[2].field,
+            "dependencies.dependency.(groupId:artifactId:type:classifier)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn effective() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(POM)?;
+        let options = ValidationOptions {
+            level: ValidationLevel::Maven4_0,
+            kind: ModelKind::Effective,
+        };
+        let problems = editor.validate(options);
+        assert_eq!(
+            messages(&problems),
+            vec![
+                "[ERROR] 'groupId' is missing. @ line 1, column 1",
+                "[ERROR] 'dependencies.dependency.version' for com.sun:tools:jar is missing. @ line 23, column 9",
+                "[ERROR] 'dependencies.dependency.systemPath' for com.sun:tools:jar is missing. @ line 23, column 9",
+                "[WARNING] 'dependencies.dependency.scope' for com.sun:tools:jar declares usage of deprecated 'system' scope @ line 23, column 9",
+            ]
+        );
+        assert_eq!(problems.with_severity(Severity::Warning).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn plugins() -> anyhow::Result<()> {
+        let editor = PomEditor::load_from_str(
+            r#"<project>
+    <modelVersion>4.0.0</modelVersion>
+    <groupId>com.example</groupId>
+    <artifactId>example</artifactId>
+    <version>1.0.0</version>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-jar-plugin</artifactId>
+                <version>LATEST</version>
+                <inherited>yes</inherited>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#,
+        )?;
+        let validate = |level| {
+            editor.validate(ValidationOptions {
+                level,
+                kind: ModelKind::Effective,
+            })
+        };
+        let maven2 = validate(ValidationLevel::Maven2_0);
+        assert_eq!(
+            messages(&maven2),
+            vec![
+                "[WARNING] 'build.plugins.plugin.version' for org.apache.maven.plugins:maven-jar-plugin must be a valid version but is 'LATEST'. @ line 10, column 17",
+                "[WARNING] 'build.plugins.plugin.inherited' for org.apache.maven.plugins:maven-jar-plugin must be 'true' or 'false' but is 'yes'. @ line 11, column 17",
+            ]
+        );
+        assert!(!maven2.has_errors());
+        let maven3 = validate(ValidationLevel::Maven3_0);
+        assert_eq!(maven3.with_severity(Severity::Error).count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn valid() -> anyhow::Result<()> {
+        let mut editor = PomEditor::new_with_group_and_artifact("com.example", "example");
+        editor.set_version("1.0.0");
+        for kind in [ModelKind::Raw, ModelKind::Effective] {
+            let problems = editor.validate(ValidationOptions {
+                level: ValidationLevel::Maven4_0,
+                kind,
+            });
+            assert!(problems.is_empty(), "{problems}");
+        }
+        Ok(())
+    }
+}
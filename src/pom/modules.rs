@@ -0,0 +1,45 @@
+use edit_xml::{Document, Element};
+use serde::{Deserialize, Serialize};
+
+use crate::editor::{
+    ElementConverter, HasElementName, XMLEditorError,
+    utils::{create_basic_text_element, text_value},
+};
+
+/// The `<modules>` of a pom file.
+///
+/// Each module is a path relative to the pom file. Either the directory of the module or its pom file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modules {
+    #[serde(default, rename = "module")]
+    pub modules: Vec<String>,
+}
+impl Modules {
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+impl HasElementName for Modules {
+    fn element_name() -> &'static str {
+        "modules"
+    }
+}
+impl ElementConverter for Modules {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let modules = element
+            .child_elements(document)
+            .into_iter()
+            .filter(|child| child.name(document) == "module")
+            .map(|child| text_value(child, document))
+            .collect();
+        Ok(Self { modules })
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        Ok(self
+            .modules
+            .into_iter()
+            .map(|module| create_basic_text_element(document, "module", module))
+            .collect())
+    }
+}
@@ -59,6 +59,8 @@ impl UpdatableElement for Parent {
         element: edit_xml::Element,
         document: &mut edit_xml::Document,
     ) -> Result<(), crate::editor::XMLEditorError> {
+        sync_element(document, element, "groupId", self.group_id.as_deref());
+        sync_element(document, element, "artifactId", self.artifact_id.as_deref());
         sync_element(document, element, "version", self.version.as_deref());
         sync_element(
             document,
@@ -0,0 +1,558 @@
+use edit_xml::{Document, Element};
+use serde::{Deserialize, Serialize};
+
+use crate::editor::{
+    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, XMLEditorError,
+    utils::{MissingElementError, create_basic_text_element, text_value},
+};
+
+use super::{
+    Activation, ActivationContext, Build, Dependencies, Dependency, DependencyManagement, Modules,
+    Plugin, PluginRepositories, Pom, Properties, Repositories, Repository,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    #[serde(default, rename = "profile")]
+    pub profiles: Vec<Profile>,
+}
+impl Profiles {
+    /// The profiles that are active for the context. Using the same rules as Maven.
+    ///
+    /// - A profile is active if it is listed in [ActivationContext::active_profiles] or all of its activation conditions match.
+    /// - Profiles marked `activeByDefault` are only active if no other profile is active.
+    /// - Profiles in [ActivationContext::inactive_profiles] are never active.
+    pub fn active_profiles(&self, context: &ActivationContext) -> Vec<&Profile> {
+        let candidates = self
+            .profiles
+            .iter()
+            .filter(|profile| !context.is_explicitly_inactive(&profile.id));
+        let active: Vec<&Profile> = candidates
+            .clone()
+            .filter(|profile| {
+                context.is_explicitly_active(&profile.id)
+                    || profile
+                        .activation
+                        .as_ref()
+                        .is_some_and(|activation| activation.conditions_match(context))
+            })
+            .collect();
+        if !active.is_empty() {
+            return active;
+        }
+        candidates
+            .filter(|profile| profile.is_active_by_default())
+            .collect()
+    }
+}
+/// A profile inside of a pom file.
+///
+/// ```xml
+/// <profile>
+///     <id>release</id>
+///     <activation>
+///         <property>
+///             <name>release</name>
+///         </property>
+///     </activation>
+///     <properties>
+///         <skipTests>true</skipTests>
+///     </properties>
+/// </profile>
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub activation: Option<Activation>,
+    pub properties: Option<Properties>,
+    #[serde(default)]
+    pub dependencies: Dependencies,
+    pub dependency_management: Option<DependencyManagement>,
+    #[serde(default)]
+    pub repositories: Repositories,
+    #[serde(default)]
+    pub plugin_repositories: PluginRepositories,
+    pub build: Option<Build>,
+    #[serde(default)]
+    pub modules: Modules,
+}
+impl Profile {
+    pub fn is_active_by_default(&self) -> bool {
+        self.activation
+            .as_ref()
+            .and_then(|activation| activation.active_by_default)
+            .unwrap_or(false)
+    }
+}
+impl HasElementName for Profile {
+    fn element_name() -> &'static str {
+        "profile"
+    }
+}
+impl ElementConverter for Profile {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut id = None;
+        let mut profile = Profile::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "id" => id = Some(text_value(child, document)),
+                "activation" => {
+                    profile.activation = Some(Activation::from_element(child, document)?);
+                }
+                "properties" => {
+                    profile.properties = Some(Properties::from_element(child, document)?);
+                }
+                "dependencies" => {
+                    profile.dependencies.dependencies = read_list(child, document)?;
+                }
+                "dependencyManagement" => {
+                    let mut dependency_management = DependencyManagement::default();
+                    if let Some(dependencies) = child.find(document, "dependencies") {
+                        dependency_management.dependencies.dependencies =
+                            read_list(dependencies, document)?;
+                    }
+                    profile.dependency_management = Some(dependency_management);
+                }
+                "repositories" => {
+                    profile.repositories.repositories = read_list(child, document)?;
+                }
+                "pluginRepositories" => {
+                    profile.plugin_repositories.repositories = read_list(child, document)?;
+                }
+                "build" => profile.build = Some(Build::from_element(child, document)?),
+                "modules" => profile.modules = Modules::from_element(child, document)?,
+                _ => {}
+            }
+        }
+        profile.id = id.ok_or(MissingElementError("id"))?;
+        Ok(profile)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            id,
+            activation,
+            properties,
+            dependencies,
+            dependency_management,
+            repositories,
+            plugin_repositories,
+            build,
+            modules,
+        } = self;
+        let mut children = vec![create_basic_text_element(document, "id", id)];
+        if let Some(activation) = activation {
+            children.push(activation.into_element(document)?);
+        }
+        if let Some(properties) = properties {
+            children.push(properties.into_element(document)?);
+        }
+        if !dependencies.dependencies.is_empty() {
+            children.push(write_list(
+                document,
+                "dependencies",
+                "dependency",
+                dependencies.dependencies,
+            )?);
+        }
+        if let Some(dependency_management) = dependency_management {
+            let element = Element::new(document, "dependencyManagement");
+            let dependencies = write_list(
+                document,
+                "dependencies",
+                "dependency",
+                dependency_management.dependencies.dependencies,
+            )?;
+            element.push_child(document, dependencies)?;
+            children.push(element);
+        }
+        if !repositories.repositories.is_empty() {
+            children.push(write_list(
+                document,
+                "repositories",
+                "repository",
+                repositories.repositories,
+            )?);
+        }
+        if !plugin_repositories.repositories.is_empty() {
+            children.push(write_list(
+                document,
+                "pluginRepositories",
+                "pluginRepository",
+                plugin_repositories.repositories,
+            )?);
+        }
+        if let Some(build) = build {
+            children.push(build.into_element(document)?);
+        }
+        if !modules.is_empty() {
+            children.push(modules.into_element(document)?);
+        }
+        Ok(children)
+    }
+}
+impl ChildOfListElement for Profile {
+    fn parent_element_name() -> &'static str {
+        "profiles"
+    }
+}
+impl ComparableElement for Profile {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+/// Reads each child. `<pluginRepositories>` uses `<pluginRepository>` so the names are not checked
+fn read_list<T: ElementConverter>(
+    element: Element,
+    document: &Document,
+) -> Result<Vec<T>, XMLEditorError> {
+    element
+        .child_elements(document)
+        .into_iter()
+        .map(|child| T::from_element(child, document))
+        .collect()
+}
+/// Creates `<name>` with a `<child_name>` for each item
+fn write_list<T: ElementConverter>(
+    document: &mut Document,
+    name: &str,
+    child_name: &str,
+    items: Vec<T>,
+) -> Result<Element, XMLEditorError> {
+    let parent = Element::new(document, name);
+    for item in items {
+        let child = Element::new(document, child_name);
+        for grand_child in item.into_children(document)? {
+            child.push_child(document, grand_child)?;
+        }
+        parent.push_child(document, child)?;
+    }
+    Ok(parent)
+}
+
+impl Pom {
+    /// The profiles that are active for the context. See [Profiles::active_profiles]
+    pub fn active_profiles(&self, context: &ActivationContext) -> Vec<&Profile> {
+        self.profiles.active_profiles(context)
+    }
+    /// Merges the active profiles into the pom.
+    ///
+    /// Returns the ids of the profiles that were applied. In the order they were applied.
+    pub fn apply_active_profiles(&mut self, context: &ActivationContext) -> Vec<String> {
+        let active: Vec<Profile> = self.active_profiles(context).into_iter().cloned().collect();
+        for profile in &active {
+            self.merge_profile(profile);
+        }
+        active.into_iter().map(|profile| profile.id).collect()
+    }
+    /// Merges a profile into the pom. Values in the profile win.
+    ///
+    /// - Properties are overwritten.
+    /// - Dependencies replace the dependency with the same groupId, artifactId, type and classifier.
+    /// - Repositories replace the repository with the same id.
+    /// - Plugins replace the version of the plugin with the same groupId and artifactId.
+    ///
+    /// Everything else is appended.
+    pub fn merge_profile(&mut self, profile: &Profile) {
+        if let Some(properties) = &profile.properties {
+            self.properties
+                .get_or_insert_with(Default::default)
+                .0
+                .extend(properties.0.clone());
+        }
+        merge_dependencies(
+            &mut self.dependencies.dependencies,
+            &profile.dependencies.dependencies,
+        );
+        if let Some(dependency_management) = &profile.dependency_management {
+            merge_dependencies(
+                &mut self
+                    .dependency_management
+                    .get_or_insert_with(Default::default)
+                    .dependencies
+                    .dependencies,
+                &dependency_management.dependencies.dependencies,
+            );
+        }
+        merge_repositories(
+            &mut self.repositories.repositories,
+            &profile.repositories.repositories,
+        );
+        merge_repositories(
+            &mut self.plugin_repositories.repositories,
+            &profile.plugin_repositories.repositories,
+        );
+        if let Some(profile_build) = &profile.build {
+            let build = self.build.get_or_insert_with(Default::default);
+            if profile_build.source_directory.is_some() {
+                build.source_directory = profile_build.source_directory.clone();
+            }
+            merge_plugins(&mut build.plugins.plugins, &profile_build.plugins.plugins);
+        }
+        for module in &profile.modules.modules {
+            if !self.modules.modules.contains(module) {
+                self.modules.modules.push(module.clone());
+            }
+        }
+    }
+}
+/// The key Maven uses to merge dependencies. `groupId:artifactId:type:classifier`
+fn is_same_dependency_key(a: &Dependency, b: &Dependency) -> bool {
+    a.is_same_dependency(b)
+        && a.depend_type.as_deref().unwrap_or("jar") == b.depend_type.as_deref().unwrap_or("jar")
+        && a.classifier == b.classifier
+}
+fn merge_dependencies(target: &mut Vec<Dependency>, source: &[Dependency]) {
+    for dependency in source {
+        match target
+            .iter_mut()
+            .find(|existing| is_same_dependency_key(existing, dependency))
+        {
+            Some(existing) => *existing = dependency.clone(),
+            None => target.push(dependency.clone()),
+        }
+    }
+}
+fn merge_repositories(target: &mut Vec<Repository>, source: &[Repository]) {
+    for repository in source {
+        let existing = repository.id.as_ref().and_then(|id| {
+            target
+                .iter_mut()
+                .find(|existing| existing.id.as_ref() == Some(id))
+        });
+        match existing {
+            Some(existing) => *existing = repository.clone(),
+            None => target.push(repository.clone()),
+        }
+    }
+}
+fn merge_plugins(target: &mut Vec<Plugin>, source: &[Plugin]) {
+    for plugin in source {
+        match target
+            .iter_mut()
+            .find(|existing| existing.is_same_plugin(plugin))
+        {
+            Some(existing) => {
+                if plugin.version.is_some() {
+                    existing.version = plugin.version.clone();
+                }
+            }
+            None => target.push(plugin.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{editor::utils::test_utils, pom::Plugins};
+
+    use super::*;
+
+    const PROFILE: &str = r#"
+        <profile>
+            <id>release</id>
+            <activation>
+                <property>
+                    <name>release</name>
+                </property>
+            </activation>
+            <properties>
+                <skipTests>true</skipTests>
+            </properties>
+            <dependencies>
+                <dependency>
+                    <groupId>org.slf4j</groupId>
+                    <artifactId>slf4j-api</artifactId>
+                    <version>2.0.16</version>
+                </dependency>
+            </dependencies>
+            <dependencyManagement>
+                <dependencies>
+                    <dependency>
+                        <groupId>com.google.guava</groupId>
+                        <artifactId>guava</artifactId>
+                        <version>33.0.0-jre</version>
+                    </dependency>
+                </dependencies>
+            </dependencyManagement>
+            <repositories>
+                <repository>
+                    <id>releases</id>
+                    <url>https://repo.example.com/releases</url>
+                </repository>
+            </repositories>
+            <pluginRepositories>
+                <pluginRepository>
+                    <id>plugins</id>
+                    <url>https://repo.example.com/plugins</url>
+                </pluginRepository>
+            </pluginRepositories>
+            <build>
+                <plugins>
+                    <plugin>
+                        <artifactId>maven-gpg-plugin</artifactId>
+                        <version>3.2.7</version>
+                    </plugin>
+                </plugins>
+            </build>
+            <modules>
+                <module>release-notes</module>
+            </modules>
+        </profile>
+    "#;
+
+    fn expected() -> Profile {
+        let mut properties = Properties::default();
+        properties
+            .0
+            .insert("skipTests".to_owned(), "true".to_owned());
+        Profile {
+            id: "release".to_owned(),
+            activation: Some(Activation {
+                property: Some(crate::pom::ActivationProperty {
+                    name: "release".to_owned(),
+                    value: None,
+                }),
+                ..Default::default()
+            }),
+            properties: Some(properties),
+            dependencies: Dependencies {
+                dependencies: vec!["org.slf4j:slf4j-api:2.0.16".parse().unwrap()],
+            },
+            dependency_management: Some(DependencyManagement {
+                dependencies: Dependencies {
+                    dependencies: vec!["com.google.guava:guava:33.0.0-jre".parse().unwrap()],
+                },
+            }),
+            repositories: Repositories {
+                repositories: vec![Repository {
+                    id: Some("releases".to_owned()),
+                    url: "https://repo.example.com/releases".to_owned(),
+                    ..Default::default()
+                }],
+            },
+            plugin_repositories: PluginRepositories {
+                repositories: vec![Repository {
+                    id: Some("plugins".to_owned()),
+                    url: "https://repo.example.com/plugins".to_owned(),
+                    ..Default::default()
+                }],
+            },
+            build: Some(Build {
+                source_directory: None,
+                plugins: Plugins {
+                    plugins: vec![Plugin {
+                        group_id: None,
+                        artifact_id: "maven-gpg-plugin".to_owned(),
+                        version: Some("3.2.7".parse().unwrap()),
+                        ..Default::default()
+                    }],
+                },
+                ..Default::default()
+            }),
+            modules: Modules {
+                modules: vec!["release-notes".to_owned()],
+            },
+        }
+    }
+
+    #[test]
+    fn parse_methods() -> anyhow::Result<()> {
+        let via_edit_xml = test_utils::create_xml_to_element::<Profile>(PROFILE)?;
+        let via_serde: Profile = quick_xml::de::from_str(PROFILE)?;
+        assert_eq!(via_edit_xml, expected());
+        assert_eq!(via_serde, expected());
+
+        let mut document = Document::new();
+        let element = expected().into_element(&mut document)?;
+        assert_eq!(Profile::from_element(element, &document)?, expected());
+        Ok(())
+    }
+
+    fn pom() -> Pom {
+        quick_xml::de::from_str(
+            r#"<project>
+                <artifactId>app</artifactId>
+                <properties>
+                    <skipTests>false</skipTests>
+                </properties>
+                <dependencies>
+                    <dependency>
+                        <groupId>org.slf4j</groupId>
+                        <artifactId>slf4j-api</artifactId>
+                        <version>1.7.36</version>
+                    </dependency>
+                </dependencies>
+                <profiles>
+                    <profile>
+                        <id>default</id>
+                        <activation>
+                            <activeByDefault>true</activeByDefault>
+                        </activation>
+                        <properties>
+                            <mode>default</mode>
+                        </properties>
+                    </profile>
+                    <profile>
+                        <id>release</id>
+                        <activation>
+                            <property>
+                                <name>release</name>
+                            </property>
+                        </activation>
+                        <properties>
+                            <skipTests>true</skipTests>
+                        </properties>
+                        <dependencies>
+                            <dependency>
+                                <groupId>org.slf4j</groupId>
+                                <artifactId>slf4j-api</artifactId>
+                                <version>2.0.16</version>
+                            </dependency>
+                        </dependencies>
+                    </profile>
+                </profiles>
+            </project>"#,
+        )
+        .unwrap()
+    }
+    fn active_ids(pom: &Pom, context: &ActivationContext) -> Vec<String> {
+        pom.active_profiles(context)
+            .into_iter()
+            .map(|profile| profile.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn active_by_default() {
+        let pom = pom();
+        assert_eq!(
+            active_ids(&pom, &ActivationContext::default()),
+            vec!["default"]
+        );
+        let release = ActivationContext::default().with_property("release", "true");
+        assert_eq!(active_ids(&pom, &release), vec!["release"]);
+        let explicit = ActivationContext::default().with_active_profile("default");
+        assert_eq!(active_ids(&pom, &explicit), vec!["default"]);
+        let deactivated = ActivationContext::default().with_inactive_profile("default");
+        assert!(active_ids(&pom, &deactivated).is_empty());
+    }
+
+    #[test]
+    fn merge_active_profiles() {
+        let mut pom = pom();
+        let context = ActivationContext::default().with_property("release", "true");
+        assert_eq!(pom.apply_active_profiles(&context), vec!["release"]);
+        let properties = pom.properties.as_ref().unwrap();
+        assert_eq!(properties.get("skipTests"), Some("true"));
+        assert!(!properties.0.contains_key("mode"));
+        assert_eq!(pom.dependencies.dependencies.len(), 1);
+        assert_eq!(
+            pom.dependencies.dependencies[0].to_string(),
+            "org.slf4j:slf4j-api:2.0.16"
+        );
+    }
+}
@@ -1,11 +1,173 @@
-use ahash::{HashMap, HashMapExt};
+use std::fmt;
 
-use crate::editor::{ElementConverter, HasElementName, UpdatableElement};
+use serde::{
+    Deserialize, Serialize,
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+};
+
+use crate::editor::{ElementConverter, HasElementName, UpdatableElement, utils::text_value};
 //TODO: Do the values need to be something other than strings?
-//TODO: Ordering will be lost if we use a HashMap
 /// Represents the properties of a pom file.
+///
+/// The properties are kept in the order they appear in the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Properties(pub PropertyMap);
+impl Properties {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key)
+    }
+    /// Sets the property. Returns the previous value
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.0.insert(key.into(), value.into())
+    }
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+}
+/// A map that keeps the order keys were inserted in.
+///
+/// Properties are small. So lookups are a linear search.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct Properties(pub HashMap<String, String>);
+pub struct PropertyMap {
+    entries: Vec<(String, String)>,
+}
+impl PropertyMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut String> {
+        self.entries
+            .iter_mut()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value)
+    }
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    /// If the key exists the value is replaced in place. Otherwise it is added to the end.
+    ///
+    /// Returns the previous value
+    pub fn insert(&mut self, key: String, value: String) -> Option<String> {
+        match self.get_mut(&key) {
+            Some(existing) => Some(std::mem::replace(existing, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+    /// Removes the key. The order of the other keys is kept
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self
+            .entries
+            .iter()
+            .position(|(existing, _)| existing == key)?;
+        Some(self.entries.remove(index).1)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut String)> {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
+impl Extend<(String, String)> for PropertyMap {
+    fn extend<T: IntoIterator<Item = (String, String)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+impl FromIterator<(String, String)> for PropertyMap {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        let mut map = PropertyMap::new();
+        map.extend(iter);
+        map
+    }
+}
+impl IntoIterator for PropertyMap {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a PropertyMap {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, String)>,
+        fn(&'a (String, String)) -> (&'a String, &'a String),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+impl Serialize for PropertyMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (key, value) in &self.entries {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+impl<'de> Deserialize<'de> for PropertyMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct PropertyMapVisitor;
+        impl<'de> Visitor<'de> for PropertyMapVisitor {
+            type Value = PropertyMap;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of properties")
+            }
+            /// `<properties/>`
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(PropertyMap::new())
+            }
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(PropertyMap::new())
+            }
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut result = PropertyMap::new();
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    result.insert(key, value);
+                }
+                Ok(result)
+            }
+        }
+        deserializer.deserialize_map(PropertyMapVisitor)
+    }
+}
 impl HasElementName for Properties {
     fn element_name() -> &'static str {
         "properties"
@@ -16,10 +178,10 @@ impl ElementConverter for Properties {
         element: edit_xml::Element,
         document: &edit_xml::Document,
     ) -> Result<Self, crate::editor::XMLEditorError> {
-        let mut properties = HashMap::new();
+        let mut properties = PropertyMap::new();
         for child in element.child_elements(document) {
             let name = child.name(document).to_owned();
-            let value = child.text_content(document);
+            let value = text_value(child, document);
             properties.insert(name, value);
         }
         Ok(Properties(properties))
@@ -40,14 +202,35 @@ impl ElementConverter for Properties {
 }
 
 impl UpdatableElement for Properties {
-    /// Updating a Properties element means all of the original children are removed and replaced with the new children.
+    /// Only the properties that changed are touched.
+    ///
+    /// Removed properties are detached, changed values are replaced in place and new properties are added to the end.
+    /// So the order and comments of the existing properties are kept.
     fn update_element(
         &self,
         element: edit_xml::Element,
         document: &mut edit_xml::Document,
     ) -> Result<(), crate::editor::XMLEditorError> {
-        element.clear_children(document);
+        let mut seen = Vec::new();
+        for child in element.child_elements(document) {
+            let name = child.name(document).to_owned();
+            match self.0.get(&name) {
+                Some(value) if !seen.contains(&name) => {
+                    if text_value(child, document) != value {
+                        child.set_text_content(document, value);
+                    }
+                    seen.push(name);
+                }
+                // Removed or a duplicate
+                _ => {
+                    child.detach(document)?;
+                }
+            }
+        }
         for (key, value) in self.0.iter() {
+            if seen.contains(key) {
+                continue;
+            }
             let child = edit_xml::Element::new(document, key);
             child.set_text_content(document, value);
             element.push_child(document, child)?;
@@ -55,3 +238,52 @@ impl UpdatableElement for Properties {
         Ok(())
     }
 }
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::editor::utils::test_utils;
+
+    use super::*;
+
+    const XML: &str = r#"
+        <properties>
+            <java.version>17</java.version>
+            <!-- Keep in sync with the parent -->
+            <guava.version>33.0.0-jre</guava.version>
+            <junit.version>5.10.0</junit.version>
+        </properties>
+    "#;
+
+    #[test]
+    fn keeps_order() -> anyhow::Result<()> {
+        let via_edit_xml = test_utils::create_xml_to_element::<Properties>(XML)?;
+        let via_serde: Properties = quick_xml::de::from_str(XML)?;
+        assert_eq!(via_edit_xml, via_serde);
+        let keys: Vec<_> = via_edit_xml.0.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["java.version", "guava.version", "junit.version"]);
+        Ok(())
+    }
+
+    #[test]
+    fn update_in_place() -> anyhow::Result<()> {
+        let mut document = edit_xml::Document::parse_str(&format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>{}"#,
+            XML.trim()
+        ))?;
+        let element = document.root_element().unwrap();
+        let mut properties = Properties::from_element(element, &document)?;
+        properties.insert("java.version", "21");
+        properties.remove("junit.version");
+        properties.insert("slf4j.version", "2.0.16");
+        properties.update_element(element, &mut document)?;
+
+        let written = document.write_str()?;
+        assert!(written.contains("<!-- Keep in sync with the parent -->"));
+        let reread = Properties::from_element(document.root_element().unwrap(), &document)?;
+        assert_eq!(reread, properties);
+        let keys: Vec<_> = reread.0.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["java.version", "guava.version", "slf4j.version"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,438 @@
+//! Loading a multi-module project
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::editor::XMLEditorError;
+
+use super::editor::PomEditor;
+mod build_order;
+mod versions;
+pub use build_order::*;
+pub use versions::*;
+
+/// The file Maven looks for inside of a module directory
+pub static POM_FILE_NAME: &str = "pom.xml";
+/// The `relativePath` used when the parent does not set one
+pub static DEFAULT_RELATIVE_PATH: &str = "../pom.xml";
+
+#[derive(Debug, Error)]
+pub enum ReactorError {
+    #[error("Failed to read {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Invalid pom {}: {error}", path.display())]
+    InvalidPom {
+        path: PathBuf,
+        error: XMLEditorError,
+    },
+    #[error("Module `{module}` declared in {} does not exist", declared_in.display())]
+    MissingModule {
+        module: String,
+        declared_in: PathBuf,
+    },
+}
+/// How two modules of a [Reactor] are related
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleRelationship {
+    /// Lists the module in `<modules>` and is its `<parent>`
+    AggregatorAndParent,
+    /// Only lists the module in `<modules>`
+    AggregatorOnly,
+    /// Only the `<parent>` of the module
+    ParentOnly,
+}
+/// A single pom inside of a [Reactor]
+#[derive(Debug)]
+pub struct ReactorModule {
+    path: PathBuf,
+    editor: PomEditor,
+    aggregator: Option<usize>,
+    parent: Option<usize>,
+    profile: Option<String>,
+}
+impl ReactorModule {
+    /// The path to the pom file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// The directory the pom file is in
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new(""))
+    }
+    pub fn editor(&self) -> &PomEditor {
+        &self.editor
+    }
+    pub fn editor_mut(&mut self) -> &mut PomEditor {
+        &mut self.editor
+    }
+    /// The index of the module that lists this module in its `<modules>`
+    ///
+    /// None for the root of the reactor
+    pub fn aggregator(&self) -> Option<usize> {
+        self.aggregator
+    }
+    /// The index of the module that is the `<parent>` of this module
+    ///
+    /// None if the parent is not part of the reactor
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+    /// The id of the profile the module was declared in. None if it was declared in the top level `<modules>`
+    pub fn declared_in_profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+    /// The group id of the module. Falls back to the group id of the `<parent>`
+    pub fn group_id(&self) -> Option<String> {
+        self.editor.get_group_id().or_else(|| {
+            self.editor
+                .get_parent()
+                .ok()
+                .flatten()
+                .and_then(|parent| parent.group_id)
+        })
+    }
+    pub fn artifact_id(&self) -> Option<String> {
+        self.editor.get_artifact_id()
+    }
+    /// The version of the module. Falls back to the version of the `<parent>`
+    pub fn version(&self) -> Option<String> {
+        self.editor.get_version().or_else(|| {
+            self.editor
+                .get_parent()
+                .ok()
+                .flatten()
+                .and_then(|parent| parent.version)
+        })
+    }
+    /// Writes the editor back to [ReactorModule::path]
+    pub fn save(&self) -> Result<(), ReactorError> {
+        let content = self
+            .editor
+            .write_to_str()
+            .map_err(|error| ReactorError::InvalidPom {
+                path: self.path.clone(),
+                error,
+            })?;
+        std::fs::write(&self.path, content).map_err(|error| ReactorError::Io {
+            path: self.path.clone(),
+            error,
+        })
+    }
+}
+impl fmt::Display for ReactorModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}",
+            self.group_id().unwrap_or_default(),
+            self.artifact_id().unwrap_or_default()
+        )
+    }
+}
+/// All the poms of a multi-module project.
+///
+/// Modules are loaded recursively from `<modules>`. Including the modules declared in profiles.
+/// Modules are referenced by their index in [Reactor::modules]. The root pom is always index 0.
+///
+/// # Example
+/// ```no_run
+/// use maven_rs::pom::Reactor;
+/// let reactor = Reactor::load("pom.xml").unwrap();
+/// for module in reactor.modules() {
+///     println!("{} {}", module, module.path().display());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Reactor {
+    modules: Vec<ReactorModule>,
+}
+impl Reactor {
+    /// Loads the root pom and every module below it.
+    ///
+    /// `root` can be the pom file or the directory containing it.
+    pub fn load(root: impl AsRef<Path>) -> Result<Self, ReactorError> {
+        let root = root.as_ref();
+        let path = canonical_pom_path(root).map_err(|error| ReactorError::Io {
+            path: root.to_path_buf(),
+            error,
+        })?;
+        let mut reactor = Self { modules: vec![] };
+        let mut by_path = HashMap::new();
+        reactor.load_module(path, None, None, &mut by_path)?;
+        reactor.link_parents(&by_path)?;
+        Ok(reactor)
+    }
+    fn load_module(
+        &mut self,
+        path: PathBuf,
+        aggregator: Option<usize>,
+        profile: Option<String>,
+        by_path: &mut HashMap<PathBuf, usize>,
+    ) -> Result<usize, ReactorError> {
+        if let Some(index) = by_path.get(&path) {
+            return Ok(*index);
+        }
+        let editor = read_pom(&path)?;
+        let mut declared: Vec<(String, Option<String>)> = editor
+            .get_modules()
+            .into_iter()
+            .map(|module| (module, None))
+            .collect();
+        let profiles = editor
+            .get_profiles()
+            .map_err(|error| ReactorError::InvalidPom {
+                path: path.clone(),
+                error,
+            })?;
+        for profile in profiles {
+            for module in profile.modules.modules {
+                declared.push((module, Some(profile.id.clone())));
+            }
+        }
+
+        let index = self.modules.len();
+        by_path.insert(path.clone(), index);
+        self.modules.push(ReactorModule {
+            path,
+            editor,
+            aggregator,
+            parent: None,
+            profile,
+        });
+        for (module, profile) in declared {
+            let declared_in = &self.modules[index].path;
+            let module_path = self.modules[index].directory().join(&module);
+            let module_path =
+                canonical_pom_path(&module_path).map_err(|_| ReactorError::MissingModule {
+                    module,
+                    declared_in: declared_in.clone(),
+                })?;
+            self.load_module(module_path, Some(index), profile, by_path)?;
+        }
+        Ok(index)
+    }
+    /// Links each module to its `<parent>`.
+    ///
+    /// The `relativePath` is checked first. If it does not point at the parent the reactor is searched by group id and artifact id. The same way Maven does.
+    fn link_parents(&mut self, by_path: &HashMap<PathBuf, usize>) -> Result<(), ReactorError> {
+        for index in 0..self.modules.len() {
+            let module = &self.modules[index];
+            let parent = module
+                .editor
+                .get_parent()
+                .map_err(|error| ReactorError::InvalidPom {
+                    path: module.path.clone(),
+                    error,
+                })?;
+            let Some(parent) = parent else {
+                continue;
+            };
+            let is_parent = |candidate: usize| {
+                let candidate = &self.modules[candidate];
+                candidate.artifact_id() == parent.artifact_id
+                    && (parent.group_id.is_none() || candidate.group_id() == parent.group_id)
+            };
+            let relative_path = parent
+                .relative_path
+                .as_deref()
+                .map(str::trim)
+                .unwrap_or(DEFAULT_RELATIVE_PATH);
+            let by_relative_path = (!relative_path.is_empty())
+                .then(|| canonical_pom_path(&module.directory().join(relative_path)).ok())
+                .flatten()
+                .and_then(|path| by_path.get(&path).copied())
+                .filter(|candidate| *candidate != index && is_parent(*candidate));
+            let linked = by_relative_path.or_else(|| {
+                (0..self.modules.len())
+                    .find(|candidate| *candidate != index && is_parent(*candidate))
+            });
+            self.modules[index].parent = linked;
+        }
+        Ok(())
+    }
+    /// The pom the reactor was loaded from
+    pub fn root(&self) -> &ReactorModule {
+        &self.modules[0]
+    }
+    pub fn modules(&self) -> &[ReactorModule] {
+        &self.modules
+    }
+    pub fn module(&self, index: usize) -> Option<&ReactorModule> {
+        self.modules.get(index)
+    }
+    pub fn module_mut(&mut self, index: usize) -> Option<&mut ReactorModule> {
+        self.modules.get_mut(index)
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ReactorModule> {
+        self.modules.iter_mut()
+    }
+    /// Finds the index of the module with the group id and artifact id
+    pub fn find(&self, group_id: &str, artifact_id: &str) -> Option<usize> {
+        self.modules.iter().position(|module| {
+            module.artifact_id().as_deref() == Some(artifact_id)
+                && module.group_id().as_deref() == Some(group_id)
+        })
+    }
+    /// The modules that list `index` as their `<parent>`
+    pub fn children_of(&self, index: usize) -> Vec<usize> {
+        self.positions(|module| module.parent == Some(index))
+    }
+    /// The modules that are listed in the `<modules>` of `index`
+    pub fn submodules_of(&self, index: usize) -> Vec<usize> {
+        self.positions(|module| module.aggregator == Some(index))
+    }
+    /// How `upper` is related to `lower`. None if `upper` is neither the aggregator nor the parent of `lower`
+    pub fn relationship(&self, upper: usize, lower: usize) -> Option<ModuleRelationship> {
+        let lower = self.modules.get(lower)?;
+        match (lower.aggregator == Some(upper), lower.parent == Some(upper)) {
+            (true, true) => Some(ModuleRelationship::AggregatorAndParent),
+            (true, false) => Some(ModuleRelationship::AggregatorOnly),
+            (false, true) => Some(ModuleRelationship::ParentOnly),
+            (false, false) => None,
+        }
+    }
+    /// Writes every module back to its pom file
+    pub fn save(&self) -> Result<(), ReactorError> {
+        for module in &self.modules {
+            module.save()?;
+        }
+        Ok(())
+    }
+    fn positions(&self, filter: impl Fn(&ReactorModule) -> bool) -> Vec<usize> {
+        self.modules
+            .iter()
+            .enumerate()
+            .filter(|(_, module)| filter(module))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+/// A module can point at the directory of the module or at the pom file itself
+fn canonical_pom_path(path: &Path) -> std::io::Result<PathBuf> {
+    let path = if path.is_dir() {
+        path.join(POM_FILE_NAME)
+    } else {
+        path.to_path_buf()
+    };
+    path.canonicalize()
+}
+fn read_pom(path: &Path) -> Result<PomEditor, ReactorError> {
+    let content = std::fs::read_to_string(path).map_err(|error| ReactorError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    PomEditor::load_from_str(&content).map_err(|error| ReactorError::InvalidPom {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::utils::test_utils::TempDirectory;
+
+    pub(crate) fn reactor_directory() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/reactor")
+    }
+
+    #[test]
+    fn load() -> anyhow::Result<()> {
+        let reactor = Reactor::load(reactor_directory())?;
+        let names: Vec<_> = reactor
+            .modules()
+            .iter()
+            .map(|module| module.artifact_id().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["root", "build-parent", "core", "app", "app-web", "docs"]
+        );
+        let index = |artifact_id: &str| reactor.find("com.example", artifact_id).unwrap();
+        let (root, build_parent, core, app, app_web, docs) = (
+            index("root"),
+            index("build-parent"),
+            index("core"),
+            index("app"),
+            index("app-web"),
+            index("docs"),
+        );
+        assert_eq!(root, 0);
+        assert!(reactor.root().aggregator().is_none());
+        assert!(reactor.root().parent().is_none());
+
+        assert_eq!(
+            reactor.module(docs).unwrap().declared_in_profile(),
+            Some("docs")
+        );
+        assert_eq!(
+            reactor.module(app_web).unwrap().path(),
+            reactor_directory()
+                .join("app/app-web/pom.xml")
+                .canonicalize()?
+        );
+        assert_eq!(
+            reactor.module(core).unwrap().version(),
+            Some("1.0.0-SNAPSHOT".to_owned())
+        );
+
+        assert_eq!(
+            reactor.relationship(root, build_parent),
+            Some(ModuleRelationship::AggregatorOnly)
+        );
+        assert_eq!(
+            reactor.relationship(root, core),
+            Some(ModuleRelationship::AggregatorOnly)
+        );
+        assert_eq!(
+            reactor.relationship(build_parent, core),
+            Some(ModuleRelationship::ParentOnly)
+        );
+        assert_eq!(
+            reactor.relationship(root, app),
+            Some(ModuleRelationship::AggregatorAndParent)
+        );
+        assert_eq!(
+            reactor.relationship(app, app_web),
+            Some(ModuleRelationship::AggregatorAndParent)
+        );
+        assert_eq!(reactor.relationship(root, app_web), None);
+
+        assert_eq!(
+            reactor.submodules_of(root),
+            vec![build_parent, core, app, docs]
+        );
+        assert_eq!(reactor.children_of(root), vec![app, docs]);
+        assert_eq!(reactor.children_of(build_parent), vec![core]);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_module() {
+        let directory = TempDirectory::new("missing-module");
+        std::fs::write(
+            directory.join(POM_FILE_NAME),
+            r#"<project>
+                <artifactId>root</artifactId>
+                <modules>
+                    <module>missing</module>
+                </modules>
+            </project>"#,
+        )
+        .unwrap();
+        let result = Reactor::load(&directory);
+        assert!(matches!(
+            result,
+            Err(ReactorError::MissingModule { module, .. }) if module == "missing"
+        ));
+    }
+}
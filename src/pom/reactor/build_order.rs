@@ -0,0 +1,400 @@
+//! Sorting the modules of a [Reactor] in the order Maven would build them
+use std::collections::{BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use super::Reactor;
+
+/// The group id Maven uses for a plugin without a `<groupId>`
+pub static DEFAULT_PLUGIN_GROUP_ID: &str = "org.apache.maven.plugins";
+
+/// The modules of the reactor depend on each other in a loop
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("The reactor contains a cycle: {}", .cycle.join(" -> "))]
+pub struct CycleError {
+    /// The modules in the cycle as `groupId:artifactId`. The first module is repeated at the end
+    pub cycle: Vec<String>,
+}
+/// Which modules are added to a selection. Same as `-am` and `-amd` in Maven
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MakeBehavior {
+    /// Also build the modules the selected modules depend on. `--also-make`
+    pub also_make: bool,
+    /// Also build the modules that depend on the selected modules. `--also-make-dependents`
+    pub also_make_dependents: bool,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    NotVisited,
+    InProgress,
+    Done,
+}
+impl Reactor {
+    /// The modules that have to be built before `index`
+    ///
+    /// Includes the `<parent>`, dependencies, build plugins, dependencies of the build plugins and build extensions that are part of the reactor.
+    pub fn dependencies_of(&self, index: usize) -> Vec<usize> {
+        self.dependencies_with(index, |group_id, artifact_id| {
+            self.find(group_id, artifact_id)
+        })
+    }
+    /// [Reactor::dependencies_of] with `find` used to look up the modules by group id and artifact id
+    fn dependencies_with(
+        &self,
+        index: usize,
+        find: impl Fn(&str, &str) -> Option<usize>,
+    ) -> Vec<usize> {
+        let Some(module) = self.modules.get(index) else {
+            return vec![];
+        };
+        let mut result = BTreeSet::new();
+        if let Some(parent) = module.parent {
+            result.insert(parent);
+        }
+        let editor = module.editor();
+        let mut coordinates: Vec<(String, String)> = editor
+            .get_dependencies()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|dependency| (dependency.group_id, dependency.artifact_id))
+            .collect();
+        if let Ok(Some(build)) = editor.get_build() {
+            for plugin in build.plugins.plugins {
+                let group_id = plugin
+                    .group_id
+                    .unwrap_or_else(|| DEFAULT_PLUGIN_GROUP_ID.to_owned());
+                coordinates.push((group_id, plugin.artifact_id));
+                coordinates.extend(
+                    plugin
+                        .dependencies
+                        .dependencies
+                        .into_iter()
+                        .map(|dependency| (dependency.group_id, dependency.artifact_id)),
+                );
+            }
+            coordinates.extend(
+                build
+                    .extensions
+                    .extensions
+                    .into_iter()
+                    .map(|extension| (extension.group_id, extension.artifact_id)),
+            );
+        }
+        for (group_id, artifact_id) in coordinates {
+            let group_id = module
+                .editor()
+                .resolve_group_id(&group_id)
+                .unwrap_or(group_id);
+            if let Some(found) = find(&group_id, &artifact_id)
+                && found != index
+            {
+                result.insert(found);
+            }
+        }
+        result.into_iter().collect()
+    }
+    /// The modules that have `index` in [Reactor::dependencies_of]
+    pub fn dependents_of(&self, index: usize) -> Vec<usize> {
+        dependents(&self.dependency_graph())
+            .get(index)
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// [Reactor::dependencies_of] for every module. Each pom is only read once
+    fn dependency_graph(&self) -> Vec<Vec<usize>> {
+        let mut coordinates = HashMap::new();
+        for (index, module) in self.modules.iter().enumerate() {
+            if let (Some(group_id), Some(artifact_id)) = (module.group_id(), module.artifact_id()) {
+                coordinates.entry((group_id, artifact_id)).or_insert(index);
+            }
+        }
+        (0..self.modules.len())
+            .map(|index| {
+                self.dependencies_with(index, |group_id, artifact_id| {
+                    coordinates
+                        .get(&(group_id.to_owned(), artifact_id.to_owned()))
+                        .copied()
+                })
+            })
+            .collect()
+    }
+    /// Every module of the reactor in the order they have to be built.
+    ///
+    /// Modules keep their declaration order unless a module has to be built earlier.
+    pub fn build_order(&self) -> Result<Vec<usize>, CycleError> {
+        self.order(&self.dependency_graph())
+    }
+    fn order(&self, dependencies: &[Vec<usize>]) -> Result<Vec<usize>, CycleError> {
+        let mut state = vec![VisitState::NotVisited; self.modules.len()];
+        let mut order = Vec::with_capacity(self.modules.len());
+        let mut stack = Vec::new();
+        for index in 0..self.modules.len() {
+            self.visit(index, dependencies, &mut state, &mut stack, &mut order)?;
+        }
+        Ok(order)
+    }
+    /// The build order of `selected` with the modules added by `behavior`
+    ///
+    /// ```rust,no_run
+    /// use maven_rs::pom::{MakeBehavior, Reactor};
+    /// let reactor = Reactor::load("pom.xml").unwrap();
+    /// let app = reactor.find_by_selector(":app").unwrap();
+    /// let behavior = MakeBehavior {
+    ///     also_make: true,
+    ///     ..Default::default()
+    /// };
+    /// for index in reactor.build_order_for(&[app], behavior).unwrap() {
+    ///     println!("{}", reactor.modules()[index]);
+    /// }
+    /// ```
+    pub fn build_order_for(
+        &self,
+        selected: &[usize],
+        behavior: MakeBehavior,
+    ) -> Result<Vec<usize>, CycleError> {
+        let dependencies = self.dependency_graph();
+        let order = self.order(&dependencies)?;
+        let mut included: BTreeSet<usize> = selected.iter().copied().collect();
+        if behavior.also_make {
+            included.extend(closure(selected, &dependencies));
+        }
+        if behavior.also_make_dependents {
+            included.extend(closure(selected, &dependents(&dependencies)));
+        }
+        Ok(order
+            .into_iter()
+            .filter(|index| included.contains(index))
+            .collect())
+    }
+    /// Finds a module the same way `--projects` does.
+    ///
+    /// Accepts `:artifactId`, `groupId:artifactId` or the path of the module relative to the root of the reactor
+    pub fn find_by_selector(&self, selector: &str) -> Option<usize> {
+        if let Some((group_id, artifact_id)) = selector.split_once(':') {
+            return self.modules.iter().position(|module| {
+                module.artifact_id().as_deref() == Some(artifact_id)
+                    && (group_id.is_empty() || module.group_id().as_deref() == Some(group_id))
+            });
+        }
+        let path = super::canonical_pom_path(&self.root().directory().join(selector)).ok()?;
+        self.modules.iter().position(|module| module.path == path)
+    }
+    fn visit(
+        &self,
+        index: usize,
+        dependencies: &[Vec<usize>],
+        state: &mut [VisitState],
+        stack: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), CycleError> {
+        match state[index] {
+            VisitState::Done => return Ok(()),
+            VisitState::InProgress => {
+                let start = stack
+                    .iter()
+                    .position(|other| *other == index)
+                    .unwrap_or_default();
+                let cycle = stack[start..]
+                    .iter()
+                    .chain(std::iter::once(&index))
+                    .map(|index| self.modules[*index].to_string())
+                    .collect();
+                return Err(CycleError { cycle });
+            }
+            VisitState::NotVisited => {}
+        }
+        state[index] = VisitState::InProgress;
+        stack.push(index);
+        for dependency in &dependencies[index] {
+            self.visit(*dependency, dependencies, state, stack, order)?;
+        }
+        stack.pop();
+        state[index] = VisitState::Done;
+        order.push(index);
+        Ok(())
+    }
+}
+/// Reverses the edges of a graph from [Reactor::dependency_graph]
+fn dependents(dependencies: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut result = vec![Vec::new(); dependencies.len()];
+    for (index, module_dependencies) in dependencies.iter().enumerate() {
+        for dependency in module_dependencies {
+            result[*dependency].push(index);
+        }
+    }
+    result
+}
+/// Every module reachable from `start` in `graph`. Does not include `start`
+fn closure(start: &[usize], graph: &[Vec<usize>]) -> BTreeSet<usize> {
+    let mut result = BTreeSet::new();
+    let mut queue: Vec<usize> = start.to_vec();
+    while let Some(index) = queue.pop() {
+        for other in &graph[index] {
+            if result.insert(*other) {
+                queue.push(*other);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        pom::{POM_FILE_NAME, reactor::tests::reactor_directory},
+        utils::test_utils::TempDirectory,
+    };
+
+    fn names(reactor: &Reactor, indexes: &[usize]) -> Vec<String> {
+        indexes
+            .iter()
+            .map(|index| reactor.modules()[*index].artifact_id().unwrap_or_default())
+            .collect()
+    }
+    /// Writes a reactor with the given modules to a temporary directory
+    fn write_reactor(name: &str, modules: &[(&str, &str)]) -> TempDirectory {
+        let directory = TempDirectory::new(name);
+        let module_list: String = modules
+            .iter()
+            .map(|(module, _)| format!("<module>{module}</module>"))
+            .collect();
+        std::fs::write(
+            directory.join(POM_FILE_NAME),
+            format!(
+                "<project><groupId>test</groupId><artifactId>root</artifactId><modules>{module_list}</modules></project>"
+            ),
+        )
+        .unwrap();
+        for (module, body) in modules {
+            std::fs::create_dir_all(directory.join(module)).unwrap();
+            std::fs::write(
+                directory.join(module).join(POM_FILE_NAME),
+                format!(
+                    "<project><groupId>test</groupId><artifactId>{module}</artifactId>{body}</project>"
+                ),
+            )
+            .unwrap();
+        }
+        directory
+    }
+    fn dependency(artifact_id: &str) -> String {
+        format!(
+            "<dependency><groupId>${{project.groupId}}</groupId><artifactId>{artifact_id}</artifactId><version>1</version></dependency>"
+        )
+    }
+
+    #[test]
+    fn build_order() -> anyhow::Result<()> {
+        let reactor = Reactor::load(reactor_directory())?;
+        let order = reactor.build_order()?;
+        assert_eq!(
+            names(&reactor, &order),
+            vec!["root", "build-parent", "core", "app", "app-web", "docs"]
+        );
+        let app_web = reactor.find_by_selector(":app-web").unwrap();
+        assert_eq!(
+            names(&reactor, &reactor.dependencies_of(app_web)),
+            vec!["core", "app"]
+        );
+        assert_eq!(reactor.find_by_selector("app/app-web"), Some(app_web));
+        assert_eq!(
+            reactor.find_by_selector("com.example:app-web"),
+            Some(app_web)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plugins_and_extensions() -> anyhow::Result<()> {
+        let directory = write_reactor(
+            "build-order-plugins",
+            &[
+                (
+                    "app",
+                    &format!(
+                        "<dependencies>{}</dependencies><build><plugins><plugin><groupId>test</groupId><artifactId>plugin</artifactId><dependencies>{}</dependencies></plugin></plugins><extensions><extension><groupId>test</groupId><artifactId>extension</artifactId></extension></extensions></build>",
+                        dependency("lib"),
+                        dependency("plugin-support")
+                    ),
+                ),
+                ("lib", ""),
+                ("plugin", ""),
+                ("plugin-support", ""),
+                ("extension", ""),
+            ],
+        );
+        let reactor = Reactor::load(&directory)?;
+        assert_eq!(
+            names(&reactor, &reactor.build_order()?),
+            vec![
+                "root",
+                "lib",
+                "plugin",
+                "plugin-support",
+                "extension",
+                "app"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn also_make() -> anyhow::Result<()> {
+        let reactor = Reactor::load(reactor_directory())?;
+        let core = reactor.find_by_selector(":core").unwrap();
+        let app_web = reactor.find_by_selector(":app-web").unwrap();
+
+        let only = reactor.build_order_for(&[app_web], MakeBehavior::default())?;
+        assert_eq!(names(&reactor, &only), vec!["app-web"]);
+
+        let also_make = MakeBehavior {
+            also_make: true,
+            ..Default::default()
+        };
+        let upstream = reactor.build_order_for(&[app_web], also_make)?;
+        assert_eq!(
+            names(&reactor, &upstream),
+            vec!["root", "build-parent", "core", "app", "app-web"]
+        );
+
+        let also_make_dependents = MakeBehavior {
+            also_make_dependents: true,
+            ..Default::default()
+        };
+        let downstream = reactor.build_order_for(&[core], also_make_dependents)?;
+        assert_eq!(names(&reactor, &downstream), vec!["core", "app-web"]);
+        assert!(reactor.dependents_of(usize::MAX).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() -> anyhow::Result<()> {
+        let directory = write_reactor(
+            "build-order-cycle",
+            &[
+                (
+                    "a",
+                    &format!("<dependencies>{}</dependencies>", dependency("b")),
+                ),
+                (
+                    "b",
+                    &format!("<dependencies>{}</dependencies>", dependency("c")),
+                ),
+                (
+                    "c",
+                    &format!("<dependencies>{}</dependencies>", dependency("a")),
+                ),
+            ],
+        );
+        let error = Reactor::load(&directory)?.build_order().unwrap_err();
+        assert_eq!(error.cycle, vec!["test:a", "test:b", "test:c", "test:a"]);
+        assert_eq!(
+            error.to_string(),
+            "The reactor contains a cycle: test:a -> test:b -> test:c -> test:a"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+//! Changing the version of every module in a [Reactor]. Same as `mvn versions:set`
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::{
+    editor::XMLEditorError,
+    pom::editor::{ArtifactLocation, ArtifactReference},
+    types::{next_development_version, release_version},
+};
+
+use super::{DEFAULT_PLUGIN_GROUP_ID, Reactor, ReactorError};
+
+#[derive(Debug, Error)]
+pub enum SetVersionError {
+    #[error("The root of the reactor does not have a version")]
+    MissingVersion,
+    #[error(transparent)]
+    XMLEditorError(#[from] XMLEditorError),
+}
+/// What was changed by a [VersionUpdate]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionTarget {
+    /// The `<version>` of the project
+    Project,
+    /// A property the version is read from. Such as `${revision}`
+    Property(String),
+    /// A reference to another module of the reactor
+    Reference {
+        location: ArtifactLocation,
+        group_id: String,
+        artifact_id: String,
+    },
+}
+/// A single change made by [Reactor::set_version]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionUpdate {
+    /// The index of the module that was changed
+    pub module: usize,
+    /// The pom file of the module
+    pub path: PathBuf,
+    pub target: VersionTarget,
+    pub previous: String,
+    pub version: String,
+}
+/// Everything changed by [Reactor::set_version]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetVersionReport {
+    pub previous: String,
+    pub version: String,
+    pub updates: Vec<VersionUpdate>,
+}
+impl SetVersionReport {
+    /// The indexes of the modules that were changed. In the order they were changed
+    pub fn modules(&self) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        self.updates
+            .iter()
+            .map(|update| update.module)
+            .filter(|module| seen.insert(*module))
+            .collect()
+    }
+    /// The pom files that were changed
+    pub fn touched_files(&self) -> Vec<&Path> {
+        let mut seen = HashSet::new();
+        self.updates
+            .iter()
+            .filter(|update| seen.insert(update.module))
+            .map(|update| update.path.as_path())
+            .collect()
+    }
+}
+/// A change that was found but not applied yet
+enum PlannedUpdate {
+    Project,
+    Property(String),
+    Reference(ArtifactReference),
+}
+impl Reactor {
+    /// The version of the root module. Resolving properties such as `${revision}`
+    pub fn version(&self) -> Option<String> {
+        self.effective_version(0)
+    }
+    /// Changes the version of every module that shares the version of the root module.
+    ///
+    /// Updates the `<version>` of the modules, the `<parent>` and dependency references between modules
+    /// and the properties the versions are read from. Only the text of the elements is changed.
+    ///
+    /// The files are not written. Use [Reactor::save_touched] with the returned report.
+    /// ```rust,no_run
+    /// use maven_rs::pom::Reactor;
+    /// let mut reactor = Reactor::load("pom.xml").unwrap();
+    /// let report = reactor.set_version("2.0.0").unwrap();
+    /// for file in report.touched_files() {
+    ///     println!("Updated {}", file.display());
+    /// }
+    /// reactor.save_touched(&report).unwrap();
+    /// ```
+    pub fn set_version(
+        &mut self,
+        version: impl Into<String>,
+    ) -> Result<SetVersionReport, SetVersionError> {
+        let version = version.into();
+        let previous = self.version().ok_or(SetVersionError::MissingVersion)?;
+        let mut report = SetVersionReport {
+            previous: previous.clone(),
+            version: version.clone(),
+            updates: vec![],
+        };
+        if previous == version {
+            return Ok(report);
+        }
+        let planned = self.plan_version_updates(&previous);
+        for (module, update) in planned {
+            let editor = self.modules[module].editor_mut();
+            let (target, previous) = match update {
+                PlannedUpdate::Project => {
+                    let previous = editor.get_version().unwrap_or_default();
+                    editor.set_version(version.as_str());
+                    (VersionTarget::Project, previous)
+                }
+                PlannedUpdate::Property(name) => {
+                    let previous = editor
+                        .set_property(&name, version.as_str())?
+                        .unwrap_or_default();
+                    (VersionTarget::Property(name), previous)
+                }
+                PlannedUpdate::Reference(reference) => {
+                    editor.set_artifact_reference_version(&reference, version.as_str())?;
+                    let target = VersionTarget::Reference {
+                        location: reference.location,
+                        group_id: reference.group_id.unwrap_or_default(),
+                        artifact_id: reference.artifact_id,
+                    };
+                    (target, reference.version.unwrap_or_default())
+                }
+            };
+            report.updates.push(VersionUpdate {
+                module,
+                path: self.modules[module].path.clone(),
+                target,
+                previous,
+                version: version.clone(),
+            });
+        }
+        Ok(report)
+    }
+    /// Removes `-SNAPSHOT` from the version. The first step of preparing a release
+    pub fn set_release_version(&mut self) -> Result<SetVersionReport, SetVersionError> {
+        let current = self.version().ok_or(SetVersionError::MissingVersion)?;
+        self.set_version(release_version(&current))
+    }
+    /// Moves to the next snapshot version. See [next_development_version]
+    pub fn set_next_development_version(&mut self) -> Result<SetVersionReport, SetVersionError> {
+        let current = self.version().ok_or(SetVersionError::MissingVersion)?;
+        self.set_version(next_development_version(&current))
+    }
+    /// Writes the modules changed in `report`
+    pub fn save_touched(&self, report: &SetVersionReport) -> Result<(), ReactorError> {
+        for module in report.modules() {
+            self.modules[module].save()?;
+        }
+        Ok(())
+    }
+    fn plan_version_updates(&self, previous: &str) -> Vec<(usize, PlannedUpdate)> {
+        let updated: HashSet<usize> = (0..self.modules.len())
+            .filter(|index| self.effective_version(*index).as_deref() == Some(previous))
+            .collect();
+        let mut planned = Vec::new();
+        let mut properties = HashSet::new();
+        let mut plan = |module: usize, value: &str, update: PlannedUpdate| {
+            if value == previous {
+                planned.push((module, update));
+            } else if let Some((owner, name, value)) = self.find_version_property(module, value)
+                && value == previous
+                && properties.insert((owner, name.clone()))
+            {
+                planned.push((owner, PlannedUpdate::Property(name)));
+            }
+        };
+        for index in 0..self.modules.len() {
+            let module = &self.modules[index];
+            if updated.contains(&index)
+                && let Some(version) = module.editor.get_version()
+            {
+                plan(index, version.trim(), PlannedUpdate::Project);
+            }
+            for reference in module.editor.get_artifact_references() {
+                let Some(version) = reference.version.clone() else {
+                    continue;
+                };
+                let group_id = match (&reference.group_id, reference.location) {
+                    (Some(group_id), _) => module
+                        .editor
+                        .resolve_group_id(group_id)
+                        .unwrap_or_else(|| group_id.clone()),
+                    (
+                        None,
+                        ArtifactLocation::Plugin
+                        | ArtifactLocation::ManagedPlugin
+                        | ArtifactLocation::Extension,
+                    ) => DEFAULT_PLUGIN_GROUP_ID.to_owned(),
+                    (None, _) => continue,
+                };
+                let is_updated = self
+                    .find(&group_id, &reference.artifact_id)
+                    .is_some_and(|target| updated.contains(&target));
+                if is_updated {
+                    plan(index, &version, PlannedUpdate::Reference(reference));
+                }
+            }
+        }
+        planned
+    }
+    /// The version of the module with properties resolved. Falls back to the version of the `<parent>`
+    fn effective_version(&self, index: usize) -> Option<String> {
+        let module = self.modules.get(index)?;
+        let version = module.version()?;
+        let version = version.trim();
+        match self.find_version_property(index, version) {
+            Some((_, _, value)) => Some(value),
+            None => Some(version.to_owned()),
+        }
+    }
+    /// If `value` is a single property. Finds the module that defines it by searching the module and its parents.
+    ///
+    /// Returns the index of the module, the name of the property and its value. `project.*` properties are not included
+    fn find_version_property(&self, index: usize, value: &str) -> Option<(usize, String, String)> {
+        let name = value.strip_prefix("${")?.strip_suffix('}')?;
+        if name.starts_with("project.") || name.starts_with("pom.") {
+            return None;
+        }
+        let mut current = Some(index);
+        // Limit the search in case the parents loop
+        for _ in 0..self.modules.len() {
+            let module = &self.modules[current?];
+            if let Some(value) = module.editor.get_property(name) {
+                return Some((current?, name.to_owned(), value.trim().to_owned()));
+            }
+            current = module.parent;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        pom::{POM_FILE_NAME, reactor::tests::reactor_directory},
+        utils::test_utils::TempDirectory,
+    };
+
+    #[test]
+    fn set_version() -> anyhow::Result<()> {
+        let mut reactor = Reactor::load(reactor_directory())?;
+        assert_eq!(reactor.version(), Some("1.0.0-SNAPSHOT".to_owned()));
+        let report = reactor.set_release_version()?;
+        assert_eq!(report.version, "1.0.0");
+
+        let targets: Vec<_> = report
+            .updates
+            .iter()
+            .map(|update| {
+                (
+                    reactor.modules()[update.module]
+                        .artifact_id()
+                        .unwrap_or_default(),
+                    update.target.clone(),
+                )
+            })
+            .collect();
+        let parent = |artifact_id: &str| VersionTarget::Reference {
+            location: ArtifactLocation::Parent,
+            group_id: "com.example".to_owned(),
+            artifact_id: artifact_id.to_owned(),
+        };
+        assert_eq!(
+            targets,
+            vec![
+                ("root".to_owned(), VersionTarget::Project),
+                ("build-parent".to_owned(), VersionTarget::Project),
+                ("core".to_owned(), parent("build-parent")),
+                ("app".to_owned(), parent("root")),
+                ("app-web".to_owned(), parent("app")),
+                ("docs".to_owned(), parent("root")),
+            ]
+        );
+        assert_eq!(report.touched_files().len(), 6);
+        for module in reactor.modules() {
+            assert_eq!(module.version(), Some("1.0.0".to_owned()), "{module}");
+        }
+        // Uses ${project.version} so it does not need to change
+        let app_web = reactor.find_by_selector(":app-web").unwrap();
+        let written = reactor.modules()[app_web].editor().write_to_str()?;
+        assert!(written.contains("<version>${project.version}</version>"));
+
+        let report = reactor.set_next_development_version()?;
+        assert_eq!(report.version, "1.0.1-SNAPSHOT");
+        assert!(reactor.set_version("1.0.1-SNAPSHOT")?.updates.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn revision_property() -> anyhow::Result<()> {
+        let directory = TempDirectory::new("revision");
+        std::fs::create_dir_all(directory.join("lib"))?;
+        std::fs::write(
+            directory.join(POM_FILE_NAME),
+            r#"<project>
+    <groupId>test</groupId>
+    <artifactId>root</artifactId>
+    <version>${revision}</version>
+    <properties>
+        <!-- Set by CI -->
+        <revision>2.0-SNAPSHOT</revision>
+    </properties>
+    <modules>
+        <module>lib</module>
+    </modules>
+</project>"#,
+        )?;
+        std::fs::write(
+            directory.join("lib").join(POM_FILE_NAME),
+            r#"<project>
+    <parent>
+        <groupId>test</groupId>
+        <artifactId>root</artifactId>
+        <version>${revision}</version>
+    </parent>
+    <artifactId>lib</artifactId>
+</project>"#,
+        )?;
+        let mut reactor = Reactor::load(&directory)?;
+        let report = reactor.set_version("2.0")?;
+        reactor.save_touched(&report)?;
+        assert_eq!(report.previous, "2.0-SNAPSHOT");
+        assert_eq!(report.modules(), vec![0]);
+        assert_eq!(
+            report.updates[0].target,
+            VersionTarget::Property("revision".to_owned())
+        );
+        let root = std::fs::read_to_string(directory.join(POM_FILE_NAME))?;
+        assert!(root.contains("<revision>2.0</revision>"));
+        assert!(root.contains("<version>${revision}</version>"));
+        assert!(root.contains("<!-- Set by CI -->"));
+        Ok(())
+    }
+}
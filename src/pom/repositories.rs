@@ -19,7 +19,14 @@ use crate::{
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Repositories {
-    #[serde(rename = "repository")]
+    #[serde(default, rename = "repository")]
+    pub repositories: Vec<Repository>,
+}
+
+/// The same as [Repositories] but for `<pluginRepositories>`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginRepositories {
+    #[serde(default, rename = "pluginRepository")]
     pub repositories: Vec<Repository>,
 }
 
@@ -72,10 +79,41 @@ impl UpdatableElement for Repository {
         sync_element(document, element, "layout", self.layout.as_deref());
         sync_element(document, element, "checksumPolicy", self.checksum_policy);
         sync_element(document, element, "updatePolicy", self.update_policy);
-        // TODO: Layout
+        sync_rules(document, element, "releases", self.releases.as_ref())?;
+        sync_rules(document, element, "snapshots", self.snapshots.as_ref())?;
         Ok(())
     }
 }
+/// Syncs `<releases>` or `<snapshots>`. The children are only replaced if the rules changed
+fn sync_rules(
+    document: &mut edit_xml::Document,
+    repository: Element,
+    name: &'static str,
+    rules: Option<&SubRepositoryRules>,
+) -> Result<(), crate::editor::XMLEditorError> {
+    match (repository.find(document, name), rules) {
+        (Some(element), Some(rules)) => {
+            if SubRepositoryRules::from_element(element, document)? != *rules {
+                element.clear_children(document);
+                for child in rules.clone().into_children(document)? {
+                    element.push_child(document, child)?;
+                }
+            }
+        }
+        (Some(element), None) => {
+            element.detach(document)?;
+        }
+        (None, Some(rules)) => {
+            let element = Element::new(document, name);
+            for child in rules.clone().into_children(document)? {
+                element.push_child(document, child)?;
+            }
+            repository.push_child(document, element)?;
+        }
+        (None, None) => {}
+    }
+    Ok(())
+}
 impl ElementConverter for Repository {
     fn from_element(
         element: edit_xml::Element,
@@ -159,6 +197,51 @@ impl ElementConverter for Repository {
     }
 }
 
+/// A [Repository] inside of `<pluginRepositories>`.
+///
+/// The content is the same. Only the element names are different.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PluginRepository(pub Repository);
+impl HasElementName for PluginRepository {
+    fn element_name() -> &'static str {
+        "pluginRepository"
+    }
+}
+impl ChildOfListElement for PluginRepository {
+    fn parent_element_name() -> &'static str {
+        "pluginRepositories"
+    }
+}
+impl ComparableElement for PluginRepository {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.0.is_same_item(&other.0)
+    }
+}
+impl ElementConverter for PluginRepository {
+    fn from_element(
+        element: Element,
+        document: &edit_xml::Document,
+    ) -> Result<Self, crate::editor::XMLEditorError> {
+        Repository::from_element(element, document).map(PluginRepository)
+    }
+
+    fn into_children(
+        self,
+        document: &mut edit_xml::Document,
+    ) -> Result<Vec<Element>, crate::editor::XMLEditorError> {
+        self.0.into_children(document)
+    }
+}
+impl UpdatableElement for PluginRepository {
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut edit_xml::Document,
+    ) -> Result<(), crate::editor::XMLEditorError> {
+        self.0.update_element(element, document)
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Builder)]
 #[serde(rename_all = "camelCase")]
 pub struct SubRepositoryRules {
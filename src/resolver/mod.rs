@@ -4,7 +4,12 @@ use std::{borrow::Cow, fmt::Debug};
 use reqwest::header::HeaderValue;
 use url::Url;
 
-use crate::settings::Server;
+use crate::settings::{
+    Server,
+    security::{SecurityError, SettingsSecurity},
+};
+mod proxy;
+pub use proxy::*;
 
 pub trait ResolvableRepository: Debug {
     /// The repository ID
@@ -94,6 +99,9 @@ pub struct FullMavenRepository {
     pub authentication: Option<HeaderValue>,
 }
 impl FullMavenRepository {
+    /// Uses the username and password of the server as is.
+    ///
+    /// If the password is encrypted use [FullMavenRepository::new_with_encrypted_config_server]
     pub fn new_with_config_server(url: String, server: Server) -> Self {
         let authentication = if let Some((username, password)) = server.username_and_password() {
             Some(Self::basic_authentication_header(username, password))
@@ -106,6 +114,15 @@ impl FullMavenRepository {
             authentication,
         }
     }
+    /// Decrypts the password of the server with the master password before creating the repository
+    pub fn new_with_encrypted_config_server(
+        url: String,
+        server: Server,
+        security: &SettingsSecurity,
+    ) -> Result<Self, SecurityError> {
+        let server = server.decrypted(security)?;
+        Ok(Self::new_with_config_server(url, server))
+    }
     fn basic_authentication_header(username: &str, password: &str) -> HeaderValue {
         let auth = format!("{}:{}", username, password);
         let encoded = STANDARD.encode(auth.as_bytes());
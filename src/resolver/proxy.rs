@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use reqwest::ClientBuilder;
+use tracing::warn;
+use url::Url;
+
+use crate::settings::{Proxy, Settings};
+
+/// Picks the proxy from the settings to use for a request.
+///
+/// The first active proxy that supports the protocol of the URL is used. If the host is in its `nonProxyHosts` no proxy is used.
+///
+/// # Example
+/// ```no_run
+/// use maven_rs::{resolver::ProxySelector, settings::Settings};
+/// let settings = Settings::read_local_config().unwrap();
+/// let client = ProxySelector::from_settings(&settings)
+///     .apply_to(reqwest::Client::builder())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProxySelector {
+    proxies: Arc<Vec<Proxy>>,
+}
+impl ProxySelector {
+    /// Only active proxies are kept
+    pub fn new(proxies: impl IntoIterator<Item = Proxy>) -> Self {
+        Self {
+            proxies: Arc::new(
+                proxies
+                    .into_iter()
+                    .filter(|proxy| proxy.is_active())
+                    .collect(),
+            ),
+        }
+    }
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self::new(settings.proxies.proxies.iter().cloned())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+    /// The proxy to use for the URL.
+    pub fn select(&self, url: &Url) -> Option<&Proxy> {
+        select_index(&self.proxies, url).map(|index| &self.proxies[index])
+    }
+    /// Creates a [reqwest::Proxy] for each proxy. Including the credentials of the proxy.
+    ///
+    /// Each one only answers for the URLs it is selected for. So the order reqwest checks them in does not matter.
+    pub fn reqwest_proxies(&self) -> Vec<reqwest::Proxy> {
+        let mut result = Vec::with_capacity(self.proxies.len());
+        for (index, proxy) in self.proxies.iter().enumerate() {
+            let proxy_url = match proxy_url(proxy) {
+                Ok(url) => url,
+                Err(error) => {
+                    warn!(?proxy.id, ?error, "Invalid proxy host. Skipping proxy");
+                    continue;
+                }
+            };
+            let proxies = self.proxies.clone();
+            let mut reqwest_proxy = reqwest::Proxy::custom(move |url| {
+                (select_index(&proxies, url) == Some(index)).then(|| proxy_url.clone())
+            });
+            if let Some((username, password)) = proxy.username_and_password() {
+                reqwest_proxy = reqwest_proxy.basic_auth(username, password);
+            }
+            result.push(reqwest_proxy);
+        }
+        result
+    }
+    /// Adds the proxies to the client builder.
+    ///
+    /// If there are no proxies the builder is returned unchanged. So reqwest keeps using the system proxy.
+    pub fn apply_to(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        for proxy in self.reqwest_proxies() {
+            builder = builder.proxy(proxy);
+        }
+        builder
+    }
+}
+fn select_index(proxies: &[Proxy], url: &Url) -> Option<usize> {
+    let index = proxies
+        .iter()
+        .position(|proxy| proxy.supports_protocol(url.scheme()))?;
+    match url.host_str() {
+        Some(host) if proxies[index].is_non_proxy_host(host) => None,
+        _ => Some(index),
+    }
+}
+/// The proxy itself is always connected to over http
+fn proxy_url(proxy: &Proxy) -> Result<Url, url::ParseError> {
+    Url::parse(&format!(
+        "http://{}:{}",
+        proxy.host,
+        proxy.port_or_default()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::settings::ProxyBuilder;
+
+    use super::*;
+
+    fn selector() -> ProxySelector {
+        ProxySelector::new([
+            ProxyBuilder::default()
+                .id("disabled")
+                .active(false)
+                .host("disabled.corp")
+                .build()
+                .unwrap(),
+            ProxyBuilder::default()
+                .id("http")
+                .host("proxy.corp")
+                .port(3128u16)
+                .non_proxy_hosts("*.corp|localhost")
+                .build()
+                .unwrap(),
+            ProxyBuilder::default()
+                .id("https")
+                .protocol("https")
+                .host("secure-proxy.corp")
+                .build()
+                .unwrap(),
+        ])
+    }
+
+    #[test]
+    fn select() {
+        let selector = selector();
+        let selected = |url: &str| {
+            selector
+                .select(&Url::parse(url).unwrap())
+                .and_then(|proxy| proxy.id.as_deref())
+        };
+        assert_eq!(selected("http://repo1.maven.org/maven2"), Some("http"));
+        assert_eq!(selected("https://repo1.maven.org/maven2"), Some("https"));
+        assert_eq!(selected("http://nexus.corp/repository"), None);
+        assert_eq!(selected("http://localhost:8080/repository"), None);
+        assert_eq!(selected("ftp://repo.example.com"), None);
+    }
+
+    #[test]
+    fn proxy_without_port() {
+        let proxy = ProxyBuilder::default().host("proxy.corp").build().unwrap();
+        assert_eq!(
+            proxy_url(&proxy).unwrap().as_str(),
+            "http://proxy.corp:8080/"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_goes_through_proxy() -> anyhow::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await?;
+            let mut buffer = vec![0; 4096];
+            let read = socket.read(&mut buffer).await?;
+            socket
+                .write_all(b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n")
+                .await?;
+            anyhow::Ok(String::from_utf8_lossy(&buffer[..read]).into_owned())
+        });
+        let selector = ProxySelector::new([ProxyBuilder::default()
+            .host("127.0.0.1")
+            .port(port)
+            .username("user")
+            .password("secret")
+            .build()?]);
+        let client = selector.apply_to(reqwest::Client::builder()).build()?;
+        let response = client
+            .get("http://repo.example.invalid/maven2/test.pom")
+            .send()
+            .await?;
+        assert_eq!(response.status(), 404);
+
+        let request = server.await??.to_ascii_lowercase();
+        assert!(request.starts_with("get http://repo.example.invalid/maven2/test.pom"));
+        // user:secret
+        assert!(request.contains("proxy-authorization: basic dxnlcjpzzwnyzxq="));
+        Ok(())
+    }
+}
@@ -1,10 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+pub mod editor;
+mod interpolation;
+mod loader;
 mod mirrors;
+mod profiles;
+mod proxies;
+#[cfg(feature = "security")]
+pub mod security;
 mod servers;
-use crate::Error;
+use crate::{Error, pom::Repository};
+pub use interpolation::*;
+pub use loader::*;
 pub use mirrors::*;
+pub use profiles::*;
+pub use proxies::*;
 pub use servers::*;
 use std::env;
 use std::io::BufReader;
@@ -16,23 +27,87 @@ pub static SETTINGS_FILE: &str = "settings.xml";
 pub struct Settings {
     pub local_repository: Option<PathBuf>,
     pub interactive_mode: Option<bool>,
+    pub use_plugin_registry: Option<bool>,
     pub offline: Option<bool>,
     #[serde(default)]
+    pub proxies: Proxies,
+    #[serde(default)]
     pub servers: Servers,
     #[serde(default)]
     pub mirrors: Mirrors,
+    #[serde(default)]
+    pub profiles: Profiles,
+    #[serde(default)]
+    pub active_profiles: ActiveProfiles,
+    #[serde(default)]
+    pub plugin_groups: PluginGroups,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ActiveProfiles {
+    #[serde(default, rename = "activeProfile")]
+    pub active_profiles: Vec<String>,
+}
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PluginGroups {
+    #[serde(default, rename = "pluginGroup")]
+    pub plugin_groups: Vec<String>,
 }
 impl Settings {
+    pub fn get_server(&self, id: &str) -> Option<&Server> {
+        self.servers.servers.iter().find(|server| server.id == id)
+    }
+    pub fn get_profile(&self, id: &str) -> Option<&Profile> {
+        self.profiles
+            .profiles
+            .iter()
+            .find(|profile| profile.id == id)
+    }
+    /// The first active proxy. Maven only uses the first active proxy.
+    pub fn get_active_proxy(&self) -> Option<&Proxy> {
+        self.proxies.proxies.iter().find(|proxy| proxy.is_active())
+    }
+    /// The first active proxy that supports the protocol. Such as `https`
+    pub fn get_active_proxy_for_protocol(&self, protocol: &str) -> Option<&Proxy> {
+        self.proxies
+            .proxies
+            .iter()
+            .find(|proxy| proxy.is_active() && proxy.supports_protocol(protocol))
+    }
+    /// Profiles listed in `activeProfiles` or marked as `activeByDefault`
+    ///
+    /// Other activation conditions are not evaluated.
+    pub fn active_profiles(&self) -> impl Iterator<Item = &Profile> {
+        self.profiles.profiles.iter().filter(|profile| {
+            self.active_profiles.active_profiles.contains(&profile.id)
+                || profile.is_active_by_default()
+        })
+    }
+    /// The repositories of all [Settings::active_profiles]
+    pub fn active_profile_repositories(&self) -> impl Iterator<Item = &Repository> {
+        self.active_profiles()
+            .flat_map(|profile| profile.repositories.repositories.iter())
+    }
+    /// The plugin repositories of all [Settings::active_profiles]
+    pub fn active_profile_plugin_repositories(&self) -> impl Iterator<Item = &Repository> {
+        self.active_profiles()
+            .flat_map(|profile| profile.plugin_repositories.repositories.iter())
+    }
     pub fn get_local_repository(&self) -> Option<PathBuf> {
         self.local_repository.clone()
     }
     /// Attempts to read the local configuration file.
+    ///
+    /// Only reads `~/.m2/settings.xml`. Use [SettingsLoader] to include the global settings and `.mvn/maven.config`
     pub fn read_local_config() -> Result<Settings, Error> {
         let result = get_settings_path().ok_or(Error::NoHomeDirectory)?;
         if !result.exists() {
             return Ok(Settings::default());
         }
-        let file = std::fs::File::open(result)?;
+        Self::read_from_path(result)
+    }
+    /// Reads a settings file.
+    pub fn read_from_path(path: impl AsRef<Path>) -> Result<Settings, Error> {
+        let file = std::fs::File::open(path)?;
         quick_xml::de::from_reader(BufReader::new(file)).map_err(Error::from)
     }
     /// Returns the local repository or the default repository.
@@ -0,0 +1,480 @@
+//! Settings Editor
+//!
+//! [SettingsEditor] is to `settings.xml` what [PomEditor](crate::pom::editor::PomEditor) is to pom files.
+//!
+//! [Settings](crate::settings::Settings) is read with Serde. Writing it back with Serde drops comments and reorders elements.
+//! [SettingsEditor] edits the dom in place. So hand written comments and layout are kept.
+use std::io::Write;
+
+use edit_xml::{Document, Element, ReadOptions, WriteOptions};
+
+use super::{Mirror, Profile, Proxy, Server};
+use crate::editor::{
+    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
+    XMLEditorError,
+    utils::{
+        MissingElementError, add_or_update_item, create_basic_text_element,
+        get_all_children_of_element, remove_item,
+    },
+};
+
+/// A struct that allows editing and creating `settings.xml` files
+#[derive(Debug)]
+pub struct SettingsEditor {
+    /// The root element must always be a settings element.
+    document: Document,
+    pub ident_level: usize,
+}
+impl Default for SettingsEditor {
+    fn default() -> Self {
+        let document = Document::new_with_root("settings", |settings| {
+            settings
+                .attribute("xmlns", "http://maven.apache.org/SETTINGS/1.2.0")
+                .attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")
+                .attribute(
+                    "xsi:schemaLocation",
+                    "http://maven.apache.org/SETTINGS/1.2.0 https://maven.apache.org/xsd/settings-1.2.0.xsd",
+                )
+        });
+        Self {
+            document,
+            ident_level: 2,
+        }
+    }
+}
+macro_rules! simple_type_getter_setter {
+    (
+        $(#[$docs:meta])*
+        $name:literal {
+            set: $set:ident,
+            get: $get:ident,
+        }
+    ) => {
+        $(#[$docs])*
+        ///
+        /// If [None] is passed in. The element will be removed
+        pub fn $set<S, O>(&mut self, value: O)
+        where
+            S: Into<String>,
+            O: Into<Option<S>>,
+        {
+            let root = self.root();
+            let value: Option<S> = value.into();
+            crate::editor::utils::sync_element(&mut self.document, root, $name, value);
+        }
+        $(#[$docs])*
+        pub fn $get(&self) -> Option<String> {
+            self.root()
+                .find(&self.document, $name)
+                .map(|x| x.text_content(&self.document))
+        }
+    };
+}
+macro_rules! list_item_methods {
+    (
+        $(#[$get_docs:meta])*
+        get: $get:ident,
+        $(#[$add_docs:meta])*
+        add: $add:ident,
+        $(#[$remove_docs:meta])*
+        remove: $remove:ident,
+        $(#[$clear_docs:meta])*
+        clear: $clear:ident,
+        $parent:literal => $list_element:ident
+    ) => {
+        $(#[$get_docs])*
+        pub fn $get(&self) -> Result<Vec<$list_element>, XMLEditorError> {
+            let root = self.root();
+            let Some(parent_element) = root.find(&self.document, $parent) else {
+                return Ok(vec![]);
+            };
+            let result =
+                get_all_children_of_element::<$list_element>(&self.document, parent_element)?;
+            Ok(result.into_iter().map(|(v, _)| v).collect())
+        }
+        $(#[$add_docs])*
+        pub fn $add(
+            &mut self,
+            value: $list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let root = self.root();
+            let parent_element = root.find(&self.document, $parent);
+            add_or_update_item(&mut self.document, parent_element, root, value)
+        }
+        $(#[$remove_docs])*
+        pub fn $remove(
+            &mut self,
+            value: &$list_element,
+        ) -> Result<Option<$list_element>, XMLEditorError> {
+            let root = self.root();
+            let parent_element = root.find(&self.document, $parent);
+            remove_item(&mut self.document, parent_element, value)
+        }
+        $(#[$clear_docs])*
+        pub fn $clear(&mut self) -> Result<(), XMLEditorError> {
+            let root = self.root();
+            let parent_element = root.find(&self.document, $parent);
+            if let Some(parent_element) = parent_element {
+                parent_element.clear_children(&mut self.document);
+            }
+            Ok(())
+        }
+    };
+}
+impl SettingsEditor {
+    simple_type_getter_setter!(
+        /// The path to the local repository
+        "localRepository" {
+            set: set_local_repository,
+            get: get_local_repository,
+        }
+    );
+    simple_type_getter_setter!(
+        /// If Maven should prompt the user for input
+        "interactiveMode" {
+            set: set_interactive_mode,
+            get: get_interactive_mode,
+        }
+    );
+    simple_type_getter_setter!(
+        /// If Maven should use the plugin registry
+        "usePluginRegistry" {
+            set: set_use_plugin_registry,
+            get: get_use_plugin_registry,
+        }
+    );
+    simple_type_getter_setter!(
+        /// If Maven should run in offline mode
+        "offline" {
+            set: set_offline,
+            get: get_offline,
+        }
+    );
+    list_item_methods!(
+        /// Gets all the servers in the settings file
+        get: get_servers,
+        /// Adds or Updates a server in the settings file. Servers are matched by id.
+        ///
+        /// ```rust
+        /// use maven_rs::settings::{editor::SettingsEditor, Server};
+        /// let mut editor = SettingsEditor::default();
+        /// editor.add_or_update_server(Server {
+        ///     id: "internal".to_string(),
+        ///     username: Some("deployer".to_string()),
+        ///     password: Some("secret".to_string()),
+        ///     ..Default::default()
+        /// }).unwrap();
+        /// let servers = editor.get_servers().unwrap();
+        /// assert_eq!(servers.len(), 1);
+        /// assert_eq!(servers[0].username_and_password(), Some(("deployer", "secret")));
+        /// ```
+        add: add_or_update_server,
+        /// Removes the server with the same id
+        remove: remove_server,
+        /// Clears all the servers in the settings file
+        clear: clear_servers,
+        "servers" => Server
+    );
+    list_item_methods!(
+        /// Gets all the mirrors in the settings file
+        get: get_mirrors,
+        /// Adds or Updates a mirror in the settings file. Mirrors are matched by id.
+        add: add_or_update_mirror,
+        /// Removes the mirror with the same id
+        remove: remove_mirror,
+        /// Clears all the mirrors in the settings file
+        clear: clear_mirrors,
+        "mirrors" => Mirror
+    );
+    list_item_methods!(
+        /// Gets all the proxies in the settings file
+        get: get_proxies,
+        /// Adds or Updates a proxy in the settings file. Proxies are matched by id.
+        add: add_or_update_proxy,
+        /// Removes the proxy with the same id
+        remove: remove_proxy,
+        /// Clears all the proxies in the settings file
+        clear: clear_proxies,
+        "proxies" => Proxy
+    );
+    list_item_methods!(
+        /// Gets all the profiles in the settings file
+        get: get_profiles,
+        /// Adds or Updates a profile in the settings file. Profiles are matched by id.
+        add: add_or_update_profile,
+        /// Removes the profile with the same id
+        remove: remove_profile,
+        /// Clears all the profiles in the settings file
+        clear: clear_profiles,
+        "profiles" => Profile
+    );
+    list_item_methods!(
+        /// Gets all the ids in `activeProfiles`
+        get: get_active_profiles,
+        /// Adds an id to `activeProfiles` if it is not already present
+        add: add_active_profile,
+        /// Removes an id from `activeProfiles`
+        remove: remove_active_profile,
+        /// Clears `activeProfiles`
+        clear: clear_active_profiles,
+        "activeProfiles" => ActiveProfile
+    );
+    list_item_methods!(
+        /// Gets all the plugin groups
+        get: get_plugin_groups,
+        /// Adds a plugin group if it is not already present
+        add: add_plugin_group,
+        /// Removes a plugin group
+        remove: remove_plugin_group,
+        /// Clears all the plugin groups
+        clear: clear_plugin_groups,
+        "pluginGroups" => PluginGroup
+    );
+    /// Loads a settings file from a string
+    pub fn load_from_str(value: &str) -> Result<Self, XMLEditorError> {
+        let document = Document::parse_str_with_opts(
+            value,
+            ReadOptions {
+                require_decl: false,
+                ..Default::default()
+            },
+        )?;
+        Self::assert_requirements_for_settings(&document)?;
+        Ok(Self {
+            document,
+            ident_level: 2,
+        })
+    }
+    /// Loads a settings file from a reader
+    ///
+    /// # Errors
+    /// If the xml is not a valid settings file
+    pub fn load_from_reader<R: std::io::Read>(reader: R) -> Result<Self, XMLEditorError> {
+        let document = Document::parse_reader_with_opts(
+            reader,
+            ReadOptions {
+                require_decl: false,
+                ..Default::default()
+            },
+        )?;
+        Self::assert_requirements_for_settings(&document)?;
+        Ok(Self {
+            document,
+            ident_level: 2,
+        })
+    }
+    /// Asserts that the document has a root element of settings
+    fn assert_requirements_for_settings(document: &Document) -> Result<(), XMLEditorError> {
+        let root = document
+            .root_element()
+            .ok_or(MissingElementError("settings"))?;
+        if root.name(document) != "settings" {
+            return Err(XMLEditorError::UnexpectedElementType {
+                expected: "settings",
+                found: root.name(document).to_owned(),
+            });
+        }
+        Ok(())
+    }
+    pub(crate) fn root(&self) -> Element {
+        self.document.root_element().unwrap()
+    }
+    pub fn write_to_str(&self) -> Result<String, XMLEditorError> {
+        self.document
+            .write_str_with_opts(WriteOptions {
+                write_decl: true,
+                indent_size: self.ident_level,
+                ..Default::default()
+            })
+            .map_err(XMLEditorError::from)
+    }
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), XMLEditorError> {
+        self.document
+            .write_with_opts(
+                writer,
+                WriteOptions {
+                    write_decl: true,
+                    indent_size: self.ident_level,
+                    ..Default::default()
+                },
+            )
+            .map_err(XMLEditorError::from)
+    }
+}
+/// An id inside of `<activeProfiles>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile(pub String);
+/// A group id inside of `<pluginGroups>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginGroup(pub String);
+macro_rules! string_list_item {
+    ($item:ident, $name:literal, $parent:literal) => {
+        impl From<&str> for $item {
+            fn from(value: &str) -> Self {
+                Self(value.to_owned())
+            }
+        }
+        impl From<String> for $item {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+        impl HasElementName for $item {
+            fn element_name() -> &'static str {
+                $name
+            }
+        }
+        impl ElementConverter for $item {
+            fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+                Ok(Self(element.text_content(document)))
+            }
+            fn into_element(self, document: &mut Document) -> Result<Element, XMLEditorError> {
+                Ok(create_basic_text_element(document, $name, self.0))
+            }
+            fn into_children(self, _: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+                Ok(vec![])
+            }
+        }
+        impl ChildOfListElement for $item {
+            fn parent_element_name() -> &'static str {
+                $parent
+            }
+        }
+        impl ComparableElement for $item {
+            fn is_same_item(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl UpdatableElement for $item {
+            /// Nothing to update. The text is the only value
+            fn update_element(&self, _: Element, _: &mut Document) -> Result<(), XMLEditorError> {
+                Ok(())
+            }
+        }
+    };
+}
+string_list_item!(ActiveProfile, "activeProfile", "activeProfiles");
+string_list_item!(PluginGroup, "pluginGroup", "pluginGroups");
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::settings::Settings;
+
+    const SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<settings>
+  <!-- Where artifacts are cached -->
+  <localRepository>/opt/m2</localRepository>
+  <servers>
+    <!-- Deploy credentials. Ask the build team for these -->
+    <server>
+      <id>internal</id>
+      <!-- Rotated every 90 days -->
+      <username>deployer</username>
+      <password>old-secret</password>
+    </server>
+  </servers>
+</settings>"#;
+
+    #[test]
+    pub fn keeps_comments() -> anyhow::Result<()> {
+        let mut editor = SettingsEditor::load_from_str(SETTINGS)?;
+        let old = editor.add_or_update_server(Server {
+            id: "internal".to_owned(),
+            username: Some("deployer".to_owned()),
+            password: Some("new-secret".to_owned()),
+            ..Default::default()
+        })?;
+        assert_eq!(
+            old.and_then(|server| server.password),
+            Some("old-secret".to_owned())
+        );
+        editor.add_or_update_server(Server {
+            id: "snapshots".to_owned(),
+            username: Some("ci".to_owned()),
+            password: Some("ci-secret".to_owned()),
+            ..Default::default()
+        })?;
+        editor.add_or_update_mirror(Mirror {
+            id: "internal-mirror".to_owned(),
+            name: "Internal Mirror".to_owned(),
+            url: "https://repo.example.com/maven2".to_owned(),
+            mirror_of: "central".to_owned(),
+        })?;
+        editor.add_or_update_proxy(Proxy {
+            id: Some("corporate".to_owned()),
+            host: "proxy.example.com".to_owned(),
+            port: Some(3128),
+            ..Default::default()
+        })?;
+        editor.add_or_update_profile(Profile {
+            id: "internal".to_owned(),
+            ..Default::default()
+        })?;
+        editor.add_active_profile("internal".into())?;
+        editor.add_active_profile("internal".into())?;
+
+        let value = editor.write_to_str()?;
+        println!("{}", value);
+        assert!(value.contains("<!-- Where artifacts are cached -->"));
+        assert!(value.contains("<!-- Deploy credentials. Ask the build team for these -->"));
+        assert!(value.contains("<!-- Rotated every 90 days -->"));
+
+        let settings: Settings = quick_xml::de::from_str(&value)?;
+        assert_eq!(settings.servers.servers.len(), 2);
+        assert_eq!(
+            settings
+                .get_server("internal")
+                .and_then(|server| server.password.as_deref()),
+            Some("new-secret")
+        );
+        assert_eq!(settings.mirrors.mirrors.len(), 1);
+        assert_eq!(
+            settings.get_active_proxy().map(|proxy| proxy.port),
+            Some(Some(3128))
+        );
+        assert_eq!(settings.active_profiles.active_profiles, vec!["internal"]);
+        Ok(())
+    }
+    #[test]
+    pub fn keeps_server_configuration() -> anyhow::Result<()> {
+        let xml = SETTINGS.replace(
+            "<password>old-secret</password>",
+            "<password>old-secret</password>\n      <configuration><timeout>10000</timeout></configuration>",
+        );
+        let mut editor = SettingsEditor::load_from_str(&xml)?;
+        editor.add_or_update_server(Server {
+            id: "internal".to_owned(),
+            username: Some("deployer".to_owned()),
+            password: Some("new-secret".to_owned()),
+            ..Default::default()
+        })?;
+        let settings: Settings = quick_xml::de::from_str(&editor.write_to_str()?)?;
+        let server = settings.get_server("internal").unwrap();
+        assert_eq!(server.password.as_deref(), Some("new-secret"));
+        assert_eq!(
+            server
+                .configuration
+                .as_ref()
+                .and_then(|configuration| configuration.find_value("timeout")),
+            Some("10000")
+        );
+        Ok(())
+    }
+    #[test]
+    pub fn remove_items() -> anyhow::Result<()> {
+        let mut editor = SettingsEditor::load_from_str(SETTINGS)?;
+        let removed = editor.remove_server(&Server {
+            id: "internal".to_owned(),
+            ..Default::default()
+        })?;
+        assert!(removed.is_some());
+        assert_eq!(editor.get_servers()?, vec![]);
+        assert_eq!(editor.get_local_repository(), Some("/opt/m2".to_owned()));
+        editor.set_local_repository::<String, _>(None);
+        assert_eq!(editor.get_local_repository(), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+use std::{collections::HashMap, env, path::PathBuf};
+
+use thiserror::Error;
+
+use crate::{
+    pom::Repository,
+    types::{ConfigurationElement, prop::ParseState},
+};
+
+use super::{Profile, Proxy, Server, Settings};
+
+#[derive(Debug, Error)]
+pub enum InterpolationError {
+    #[error("Unresolved variable ${{{variable}}} in {field}")]
+    UnresolvedVariable { field: String, variable: String },
+    #[error("Invalid value in {field}: {error}")]
+    InvalidValue { field: String, error: String },
+}
+/// Replaces `${env.X}` and system properties such as `${user.home}` in settings values.
+///
+/// Values are parsed as a [crate::types::Property]. Variables starting with `env.` are read from the environment variables. Everything else is a system property.
+///
+/// By default unresolved variables are left as is. Use [SettingsInterpolator::strict] to return an error instead.
+///
+/// # Example
+/// ```
+/// use maven_rs::settings::{Settings, SettingsInterpolator};
+/// let mut settings = Settings {
+///     local_repository: Some("${user.home}/repository".into()),
+///     ..Default::default()
+/// };
+/// SettingsInterpolator::empty()
+///     .with_system_property("user.home", "/home/user")
+///     .interpolate(&mut settings)
+///     .unwrap();
+/// assert_eq!(settings.local_repository, Some("/home/user/repository".into()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SettingsInterpolator {
+    pub environment: HashMap<String, String>,
+    pub system_properties: HashMap<String, String>,
+    pub strict: bool,
+}
+impl SettingsInterpolator {
+    /// Uses the environment variables of the current process and sets `user.home` and `user.dir`
+    pub fn new() -> Self {
+        let mut system_properties = HashMap::new();
+        if let Some(home) = env::home_dir() {
+            system_properties.insert("user.home".to_owned(), home.display().to_string());
+        }
+        if let Ok(current_dir) = env::current_dir() {
+            system_properties.insert("user.dir".to_owned(), current_dir.display().to_string());
+        }
+        Self {
+            environment: env::vars().collect(),
+            system_properties,
+            strict: false,
+        }
+    }
+    /// No environment variables or system properties
+    pub fn empty() -> Self {
+        Self::default()
+    }
+    pub fn with_system_property(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.system_properties.insert(key.into(), value.into());
+        self
+    }
+    /// Adds system properties. Such as the ones in [super::LoadedSettings::system_properties]
+    pub fn with_system_properties<I, K, V>(mut self, properties: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.system_properties.extend(
+            properties
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+    pub fn with_environment_variable(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.environment.insert(key.into(), value.into());
+        self
+    }
+    /// Return an error if a variable can not be resolved
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Finds the value of a variable
+    pub fn lookup(&self, name: &str) -> Option<String> {
+        match name.strip_prefix("env.") {
+            Some(name) => self.environment.get(name).cloned(),
+            None => self.system_properties.get(name).cloned(),
+        }
+    }
+    /// Interpolates a single value.
+    ///
+    /// `field` is only used for errors.
+    pub fn interpolate_value(
+        &self,
+        value: &str,
+        field: &str,
+    ) -> Result<String, InterpolationError> {
+        if !value.contains("${") {
+            return Ok(value.to_owned());
+        }
+        let state = ParseState {
+            allow_unclosed_variable: true,
+        };
+        let property = state
+            .parse(value)
+            .map_err(|error| InterpolationError::InvalidValue {
+                field: field.to_owned(),
+                error: error.map(|_| ()).to_string(),
+            })?;
+        let lookup = |name: &str| self.lookup(name);
+        if self.strict {
+            property
+                .try_resolve(lookup)
+                .map_err(|error| InterpolationError::UnresolvedVariable {
+                    field: field.to_owned(),
+                    variable: error.0,
+                })
+        } else {
+            Ok(property.resolve(lookup))
+        }
+    }
+    /// Interpolates every value in the settings.
+    ///
+    /// Field names in errors use the same keys as [super::LoadedSettings::origin]. Such as `servers/{id}/password`
+    pub fn interpolate(&self, settings: &mut Settings) -> Result<(), InterpolationError> {
+        if let Some(local_repository) = &settings.local_repository
+            && let Some(value) = local_repository.to_str()
+        {
+            let value = self.interpolate_value(value, "localRepository")?;
+            settings.local_repository = Some(PathBuf::from(value));
+        }
+        for proxy in &mut settings.proxies.proxies {
+            self.interpolate_proxy(proxy)?;
+        }
+        for server in &mut settings.servers.servers {
+            self.interpolate_server(server)?;
+        }
+        for mirror in &mut settings.mirrors.mirrors {
+            let field = format!("mirrors/{}", mirror.id);
+            self.string(&mut mirror.name, &field, "name")?;
+            self.string(&mut mirror.url, &field, "url")?;
+            self.string(&mut mirror.mirror_of, &field, "mirrorOf")?;
+        }
+        for profile in &mut settings.profiles.profiles {
+            self.interpolate_profile(profile)?;
+        }
+        for active_profile in &mut settings.active_profiles.active_profiles {
+            *active_profile = self.interpolate_value(active_profile, "activeProfiles")?;
+        }
+        for plugin_group in &mut settings.plugin_groups.plugin_groups {
+            *plugin_group = self.interpolate_value(plugin_group, "pluginGroups")?;
+        }
+        Ok(())
+    }
+    fn interpolate_proxy(&self, proxy: &mut Proxy) -> Result<(), InterpolationError> {
+        let field = format!("proxies/{}", proxy.id.as_deref().unwrap_or("default"));
+        self.optional(&mut proxy.protocol, &field, "protocol")?;
+        self.string(&mut proxy.host, &field, "host")?;
+        self.optional(&mut proxy.username, &field, "username")?;
+        self.optional(&mut proxy.password, &field, "password")?;
+        self.optional(&mut proxy.non_proxy_hosts, &field, "nonProxyHosts")
+    }
+    fn interpolate_server(&self, server: &mut Server) -> Result<(), InterpolationError> {
+        let field = format!("servers/{}", server.id);
+        self.optional(&mut server.username, &field, "username")?;
+        self.optional(&mut server.password, &field, "password")?;
+        self.optional(&mut server.private_key, &field, "privateKey")?;
+        self.optional(&mut server.passphrase, &field, "passphrase")?;
+        self.optional(&mut server.file_permissions, &field, "filePermissions")?;
+        self.optional(
+            &mut server.directory_permissions,
+            &field,
+            "directoryPermissions",
+        )?;
+        if let Some(configuration) = &mut server.configuration {
+            self.interpolate_configuration(configuration, &format!("{field}/configuration"))?;
+        }
+        Ok(())
+    }
+    fn interpolate_profile(&self, profile: &mut Profile) -> Result<(), InterpolationError> {
+        let field = format!("profiles/{}", profile.id);
+        if let Some(properties) = &mut profile.properties {
+            for (key, value) in properties.0.iter_mut() {
+                *value = self.interpolate_value(value, &format!("{field}/properties/{key}"))?;
+            }
+        }
+        if let Some(activation) = &mut profile.activation {
+            if let Some(property) = &mut activation.property {
+                self.optional(&mut property.value, &field, "activation/property/value")?;
+            }
+            if let Some(file) = &mut activation.file {
+                self.optional(&mut file.exists, &field, "activation/file/exists")?;
+                self.optional(&mut file.missing, &field, "activation/file/missing")?;
+            }
+        }
+        for repository in profile
+            .repositories
+            .repositories
+            .iter_mut()
+            .chain(profile.plugin_repositories.repositories.iter_mut())
+        {
+            self.interpolate_repository(repository, &field)?;
+        }
+        Ok(())
+    }
+    fn interpolate_repository(
+        &self,
+        repository: &mut Repository,
+        parent: &str,
+    ) -> Result<(), InterpolationError> {
+        let field = format!(
+            "{parent}/repositories/{}",
+            repository.id.as_deref().unwrap_or_default()
+        );
+        self.optional(&mut repository.name, &field, "name")?;
+        self.string(&mut repository.url, &field, "url")?;
+        self.optional(&mut repository.layout, &field, "layout")
+    }
+    fn interpolate_configuration(
+        &self,
+        configuration: &mut ConfigurationElement,
+        field: &str,
+    ) -> Result<(), InterpolationError> {
+        for (_, value) in &mut configuration.attributes {
+            *value = self.interpolate_value(value, field)?;
+        }
+        if let Some(value) = &mut configuration.value {
+            *value = self.interpolate_value(value, field)?;
+        }
+        for child in &mut configuration.children {
+            let field = format!("{field}/{}", child.name);
+            self.interpolate_configuration(child, &field)?;
+        }
+        Ok(())
+    }
+    fn string(
+        &self,
+        value: &mut String,
+        parent: &str,
+        name: &str,
+    ) -> Result<(), InterpolationError> {
+        *value = self.interpolate_value(value, &format!("{parent}/{name}"))?;
+        Ok(())
+    }
+    fn optional(
+        &self,
+        value: &mut Option<String>,
+        parent: &str,
+        name: &str,
+    ) -> Result<(), InterpolationError> {
+        if let Some(value) = value {
+            self.string(value, parent, name)?;
+        }
+        Ok(())
+    }
+}
+impl Settings {
+    /// Shortcut for [SettingsInterpolator::interpolate]
+    pub fn interpolate(
+        &mut self,
+        interpolator: &SettingsInterpolator,
+    ) -> Result<(), InterpolationError> {
+        interpolator.interpolate(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn settings() -> Settings {
+        quick_xml::de::from_str(
+            r#"<settings>
+                <localRepository>${user.home}/.m2/custom</localRepository>
+                <servers>
+                    <server>
+                        <id>nexus</id>
+                        <username>${env.NEXUS_USER}</username>
+                        <password>${env.NEXUS_PASSWORD}</password>
+                    </server>
+                </servers>
+                <mirrors>
+                    <mirror>
+                        <id>internal</id>
+                        <name>Internal</name>
+                        <url>${nexus.url}/repository/maven-public</url>
+                        <mirrorOf>*</mirrorOf>
+                    </mirror>
+                </mirrors>
+            </settings>"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn interpolate() -> anyhow::Result<()> {
+        let interpolator = SettingsInterpolator::empty()
+            .with_system_property("user.home", "/home/user")
+            .with_system_property("nexus.url", "https://nexus.example.com")
+            .with_environment_variable("NEXUS_USER", "deployer")
+            .with_environment_variable("NEXUS_PASSWORD", "pa$$word");
+        let mut settings = settings();
+        settings.interpolate(&interpolator)?;
+        assert_eq!(
+            settings.local_repository,
+            Some(PathBuf::from("/home/user/.m2/custom"))
+        );
+        let server = settings.get_server("nexus").unwrap();
+        assert_eq!(server.username.as_deref(), Some("deployer"));
+        assert_eq!(server.password.as_deref(), Some("pa$$word"));
+        assert_eq!(
+            settings.mirrors.mirrors[0].url,
+            "https://nexus.example.com/repository/maven-public"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn strict() {
+        let interpolator = SettingsInterpolator::empty()
+            .with_system_property("user.home", "/home/user")
+            .with_system_property("nexus.url", "https://nexus.example.com")
+            .with_environment_variable("NEXUS_USER", "deployer");
+
+        let mut settings = settings();
+        settings.interpolate(&interpolator).unwrap();
+        assert_eq!(
+            settings.get_server("nexus").unwrap().password.as_deref(),
+            Some("${env.NEXUS_PASSWORD}")
+        );
+
+        let mut settings = self::settings();
+        let error = settings
+            .interpolate(&interpolator.strict(true))
+            .unwrap_err();
+        match error {
+            InterpolationError::UnresolvedVariable { field, variable } => {
+                assert_eq!(field, "servers/nexus/password");
+                assert_eq!(variable, "env.NEXUS_PASSWORD");
+            }
+            other => panic!("Unexpected error {other}"),
+        }
+    }
+}
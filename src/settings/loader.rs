@@ -0,0 +1,606 @@
+//! Loads the settings the same way Maven does.
+//!
+//! Maven reads `${maven.home}/conf/settings.xml` (global) and `~/.m2/settings.xml` (user) then merges them.
+//! The user settings win. Lists are merged by id.
+//!
+//! A project can also contain `.mvn/maven.config` which can point to other settings files (`-s`, `-gs`),
+//! enable offline mode (`-o`) and define system properties (`-D`).
+use std::{
+    collections::HashMap,
+    env, fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::Error;
+
+use super::{Settings, get_settings_path};
+
+pub static MAVEN_HOME_ENV: &str = "MAVEN_HOME";
+pub static M2_HOME_ENV: &str = "M2_HOME";
+pub static MVN_FOLDER: &str = ".mvn";
+pub static MAVEN_CONFIG_FILE: &str = "maven.config";
+
+/// Where a value in [LoadedSettings] came from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SettingsSource {
+    /// `${maven.home}/conf/settings.xml` or the file passed with `-gs`
+    Global(PathBuf),
+    /// `~/.m2/settings.xml` or the file passed with `-s`
+    User(PathBuf),
+    /// `.mvn/maven.config`
+    MavenConfig(PathBuf),
+}
+impl SettingsSource {
+    pub fn path(&self) -> &Path {
+        match self {
+            SettingsSource::Global(path)
+            | SettingsSource::User(path)
+            | SettingsSource::MavenConfig(path) => path,
+        }
+    }
+}
+impl fmt::Display for SettingsSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsSource::Global(path) => write!(f, "global settings ({})", path.display()),
+            SettingsSource::User(path) => write!(f, "user settings ({})", path.display()),
+            SettingsSource::MavenConfig(path) => write!(f, "maven.config ({})", path.display()),
+        }
+    }
+}
+/// The options inside of `.mvn/maven.config` that affect the settings
+///
+/// ```text
+/// -s .mvn/settings.xml
+/// --offline
+/// -Drevision=1.0.0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MavenConfig {
+    /// `-s` or `--settings`
+    pub user_settings: Option<PathBuf>,
+    /// `-gs` or `--global-settings`
+    pub global_settings: Option<PathBuf>,
+    /// `-o` or `--offline`
+    pub offline: bool,
+    /// `-Dkey=value`. A `-Dkey` without a value is set to `true`
+    pub system_properties: HashMap<String, String>,
+    /// Arguments that are not used by the settings
+    pub other_arguments: Vec<String>,
+}
+impl MavenConfig {
+    /// Parses the content of a `maven.config` file.
+    ///
+    /// Relative settings paths are resolved against `base_directory`. This should be the project directory. (The parent of `.mvn`)
+    pub fn parse(content: &str, base_directory: &Path) -> Self {
+        let mut config = MavenConfig::default();
+        let mut arguments = split_arguments(content).into_iter();
+        while let Some(argument) = arguments.next() {
+            if let Some(property) = argument.strip_prefix("-D") {
+                let property = if property.is_empty() {
+                    arguments.next().unwrap_or_default()
+                } else {
+                    property.to_owned()
+                };
+                let (key, value) = property
+                    .split_once('=')
+                    .unwrap_or((property.as_str(), "true"));
+                if !key.is_empty() {
+                    config
+                        .system_properties
+                        .insert(key.to_owned(), value.to_owned());
+                }
+                continue;
+            }
+            let (option, inline_value) = match argument.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => {
+                    (option.to_owned(), Some(value.to_owned()))
+                }
+                _ => (argument.clone(), None),
+            };
+            match option.as_str() {
+                "-o" | "--offline" => config.offline = true,
+                "-s" | "--settings" | "-gs" | "--global-settings" => {
+                    let Some(value) = inline_value.or_else(|| arguments.next()) else {
+                        config.other_arguments.push(argument);
+                        continue;
+                    };
+                    let path = base_directory.join(value);
+                    if option == "-s" || option == "--settings" {
+                        config.user_settings = Some(path);
+                    } else {
+                        config.global_settings = Some(path);
+                    }
+                }
+                _ => config.other_arguments.push(argument),
+            }
+        }
+        config
+    }
+    /// Reads `.mvn/maven.config` inside of the project directory.
+    ///
+    /// Returns None if the file does not exist.
+    pub fn read_from_project(project_directory: &Path) -> Result<Option<Self>, Error> {
+        let path = project_directory.join(MVN_FOLDER).join(MAVEN_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(Self::parse(&content, project_directory)))
+    }
+}
+/// Finds the project directory the same way Maven does. Walking up from `start` until a directory containing `.mvn` is found.
+pub fn find_project_directory(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|directory| directory.join(MVN_FOLDER).is_dir())
+        .map(Path::to_path_buf)
+}
+/// Returns the maven home directory. Using `MAVEN_HOME` then `M2_HOME`
+pub fn get_maven_home() -> Option<PathBuf> {
+    [MAVEN_HOME_ENV, M2_HOME_ENV]
+        .into_iter()
+        .filter_map(env::var_os)
+        .find(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+/// Returns the path to the global settings file `${maven.home}/conf/settings.xml`
+pub fn get_global_settings_path() -> Option<PathBuf> {
+    get_maven_home().map(|home| home.join("conf").join(super::SETTINGS_FILE))
+}
+/// Splits the arguments of a `maven.config` file.
+///
+/// Arguments are separated by whitespace. Quotes can be used to include whitespace. Lines starting with `#` are ignored.
+fn split_arguments(content: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut current = String::new();
+        let mut in_argument = false;
+        let mut quote = None;
+        for c in line.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), c) => current.push(c),
+                (None, '"' | '\'') => {
+                    quote = Some(c);
+                    in_argument = true;
+                }
+                (None, c) if c.is_whitespace() => {
+                    if in_argument {
+                        arguments.push(std::mem::take(&mut current));
+                        in_argument = false;
+                    }
+                }
+                (None, c) => {
+                    current.push(c);
+                    in_argument = true;
+                }
+            }
+        }
+        if in_argument {
+            arguments.push(current);
+        }
+    }
+    arguments
+}
+/// Loads and merges the settings from all sources.
+///
+/// Paths set on the loader take priority over the ones in `maven.config`. Which take priority over the defaults.
+///
+/// # Example
+/// ```no_run
+/// use maven_rs::settings::SettingsLoader;
+/// let loaded = SettingsLoader::new()
+///     .with_project_directory("my-project")
+///     .load()
+///     .unwrap();
+/// if let Some(source) = loaded.origin("localRepository") {
+///     println!("localRepository set by {source}");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SettingsLoader {
+    pub global_settings: Option<PathBuf>,
+    pub user_settings: Option<PathBuf>,
+    pub project_directory: Option<PathBuf>,
+}
+impl SettingsLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Overrides `${maven.home}/conf/settings.xml`
+    pub fn with_global_settings(mut self, path: impl Into<PathBuf>) -> Self {
+        self.global_settings = Some(path.into());
+        self
+    }
+    /// Overrides `~/.m2/settings.xml`
+    pub fn with_user_settings(mut self, path: impl Into<PathBuf>) -> Self {
+        self.user_settings = Some(path.into());
+        self
+    }
+    /// The directory containing `.mvn/maven.config`
+    pub fn with_project_directory(mut self, path: impl Into<PathBuf>) -> Self {
+        self.project_directory = Some(path.into());
+        self
+    }
+    /// Reads all sources and merges them.
+    ///
+    /// The default settings files are skipped if they are missing. A missing home directory is not an error.
+    ///
+    /// # Errors
+    /// If a settings file set with [SettingsLoader::with_user_settings], [SettingsLoader::with_global_settings]
+    /// or `-s` and `-gs` in `maven.config` does not exist. Same as Maven
+    pub fn load(&self) -> Result<LoadedSettings, Error> {
+        let maven_config = match &self.project_directory {
+            Some(directory) => MavenConfig::read_from_project(directory)?
+                .map(|config| (directory.join(MVN_FOLDER).join(MAVEN_CONFIG_FILE), config)),
+            None => None,
+        };
+        let config = maven_config.as_ref().map(|(_, config)| config);
+
+        let global_path = match self
+            .global_settings
+            .clone()
+            .or_else(|| config.and_then(|config| config.global_settings.clone()))
+        {
+            Some(path) => Some(specified_settings_file(path, "global")?),
+            None => get_global_settings_path().filter(|path| path.exists()),
+        };
+        let user_path = match self
+            .user_settings
+            .clone()
+            .or_else(|| config.and_then(|config| config.user_settings.clone()))
+        {
+            Some(path) => Some(specified_settings_file(path, "user")?),
+            None => get_settings_path().filter(|path| path.exists()),
+        };
+
+        let mut loaded = LoadedSettings::default();
+        if let Some(path) = global_path {
+            let settings = Settings::read_from_path(&path)?;
+            loaded.apply(settings, SettingsSource::Global(path));
+        }
+        if let Some(path) = user_path {
+            let settings = Settings::read_from_path(&path)?;
+            loaded.apply(settings, SettingsSource::User(path));
+        }
+        if let Some((path, config)) = maven_config {
+            loaded.apply_maven_config(config, SettingsSource::MavenConfig(path));
+        }
+        Ok(loaded)
+    }
+}
+/// Errors if a settings file that was set explicitly does not exist
+fn specified_settings_file(path: PathBuf, kind: &'static str) -> Result<PathBuf, Error> {
+    if path.exists() {
+        Ok(path)
+    } else {
+        Err(Error::MissingSettingsFile { kind, path })
+    }
+}
+/// The result of [SettingsLoader::load]
+#[derive(Debug, Clone, Default)]
+pub struct LoadedSettings {
+    /// The merged settings
+    pub settings: Settings,
+    /// System properties defined in `maven.config`
+    pub system_properties: HashMap<String, String>,
+    /// All sources that were read. In the order they were applied
+    pub sources: Vec<SettingsSource>,
+    origins: HashMap<String, SettingsSource>,
+}
+impl LoadedSettings {
+    /// Where a value came from.
+    ///
+    /// Keys are the element names in the settings file. List items use `{list}/{id}`
+    ///
+    /// - `localRepository`, `interactiveMode`, `usePluginRegistry`, `offline`
+    /// - `servers/{id}`, `mirrors/{id}`, `proxies/{id}`, `profiles/{id}`
+    /// - `activeProfiles/{id}`, `pluginGroups/{group}`
+    ///
+    /// Proxies without an id use `default` like Maven.
+    pub fn origin(&self, key: &str) -> Option<&SettingsSource> {
+        self.origins.get(key)
+    }
+    /// All keys and where they came from
+    pub fn origins(&self) -> impl Iterator<Item = (&str, &SettingsSource)> {
+        self.origins
+            .iter()
+            .map(|(key, source)| (key.as_str(), source))
+    }
+    /// Merges settings on top of the current settings. The new settings win.
+    pub fn apply(&mut self, settings: Settings, source: SettingsSource) {
+        let Settings {
+            local_repository,
+            interactive_mode,
+            use_plugin_registry,
+            offline,
+            proxies,
+            servers,
+            mirrors,
+            profiles,
+            active_profiles,
+            plugin_groups,
+        } = settings;
+        let current = &mut self.settings;
+        let origins = &mut self.origins;
+        merge_value(
+            &mut current.local_repository,
+            local_repository,
+            "localRepository",
+            &source,
+            origins,
+        );
+        merge_value(
+            &mut current.interactive_mode,
+            interactive_mode,
+            "interactiveMode",
+            &source,
+            origins,
+        );
+        merge_value(
+            &mut current.use_plugin_registry,
+            use_plugin_registry,
+            "usePluginRegistry",
+            &source,
+            origins,
+        );
+        merge_value(&mut current.offline, offline, "offline", &source, origins);
+
+        merge_by_id(
+            &mut current.proxies.proxies,
+            proxies.proxies,
+            |proxy| proxy.id.as_deref().unwrap_or("default"),
+            "proxies",
+            &source,
+            origins,
+        );
+        merge_by_id(
+            &mut current.servers.servers,
+            servers.servers,
+            |server| server.id.as_str(),
+            "servers",
+            &source,
+            origins,
+        );
+        merge_by_id(
+            &mut current.mirrors.mirrors,
+            mirrors.mirrors,
+            |mirror| mirror.id.as_str(),
+            "mirrors",
+            &source,
+            origins,
+        );
+        merge_by_id(
+            &mut current.profiles.profiles,
+            profiles.profiles,
+            |profile| profile.id.as_str(),
+            "profiles",
+            &source,
+            origins,
+        );
+        merge_by_id(
+            &mut current.active_profiles.active_profiles,
+            active_profiles.active_profiles,
+            String::as_str,
+            "activeProfiles",
+            &source,
+            origins,
+        );
+        merge_by_id(
+            &mut current.plugin_groups.plugin_groups,
+            plugin_groups.plugin_groups,
+            String::as_str,
+            "pluginGroups",
+            &source,
+            origins,
+        );
+        self.sources.push(source);
+    }
+    /// Applies `-o` and `-D` from a `maven.config`.
+    ///
+    /// `-s` and `-gs` are handled by [SettingsLoader]
+    pub fn apply_maven_config(&mut self, config: MavenConfig, source: SettingsSource) {
+        if config.offline {
+            self.settings.offline = Some(true);
+            self.origins.insert("offline".to_owned(), source.clone());
+        }
+        self.system_properties.extend(config.system_properties);
+        self.sources.push(source);
+    }
+}
+fn merge_value<T>(
+    current: &mut Option<T>,
+    new: Option<T>,
+    key: &str,
+    source: &SettingsSource,
+    origins: &mut HashMap<String, SettingsSource>,
+) {
+    if new.is_some() {
+        *current = new;
+        origins.insert(key.to_owned(), source.clone());
+    }
+}
+/// The same as Maven's `shallowMergeById`.
+///
+/// Items in `new` replace items with the same id. The result is the new items followed by the remaining current items.
+fn merge_by_id<T>(
+    current: &mut Vec<T>,
+    new: Vec<T>,
+    id: impl Fn(&T) -> &str,
+    list: &str,
+    source: &SettingsSource,
+    origins: &mut HashMap<String, SettingsSource>,
+) {
+    if new.is_empty() {
+        return;
+    }
+    for item in &new {
+        origins.insert(format!("{list}/{}", id(item)), source.clone());
+    }
+    let remaining: Vec<T> = std::mem::take(current)
+        .into_iter()
+        .filter(|item| !new.iter().any(|new_item| id(new_item) == id(item)))
+        .collect();
+    *current = new;
+    current.extend(remaining);
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::utils::test_utils::TempDirectory;
+
+    #[test]
+    fn parse_maven_config() {
+        let config = MavenConfig::parse(
+            r#"
+            # Comment
+            -s .mvn/settings.xml
+            --global-settings=/opt/maven/global.xml
+            -o -T 4
+            -Drevision=1.0.0 -D "message=hello world" -DskipTests
+            "#,
+            Path::new("/project"),
+        );
+        assert_eq!(
+            config.user_settings,
+            Some(PathBuf::from("/project/.mvn/settings.xml"))
+        );
+        assert_eq!(
+            config.global_settings,
+            Some(PathBuf::from("/opt/maven/global.xml"))
+        );
+        assert!(config.offline);
+        assert_eq!(config.other_arguments, vec!["-T", "4"]);
+        assert_eq!(
+            config.system_properties.get("revision").map(String::as_str),
+            Some("1.0.0")
+        );
+        assert_eq!(
+            config.system_properties.get("message").map(String::as_str),
+            Some("hello world")
+        );
+        assert_eq!(
+            config
+                .system_properties
+                .get("skipTests")
+                .map(String::as_str),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn merge_layers() -> anyhow::Result<()> {
+        let directory = TempDirectory::new("settings-loader");
+        let project = directory.join("project");
+        std::fs::create_dir_all(project.join(MVN_FOLDER))?;
+        let global = directory.join("global.xml");
+        let user = directory.join("user.xml");
+        std::fs::write(
+            &global,
+            r#"<settings>
+                <localRepository>/global/repository</localRepository>
+                <interactiveMode>false</interactiveMode>
+                <servers>
+                    <server><id>shared</id><username>global</username></server>
+                    <server><id>global-only</id><username>global</username></server>
+                </servers>
+                <pluginGroups><pluginGroup>org.global</pluginGroup></pluginGroups>
+            </settings>"#,
+        )?;
+        std::fs::write(
+            &user,
+            r#"<settings>
+                <localRepository>/user/repository</localRepository>
+                <servers>
+                    <server><id>shared</id><username>user</username></server>
+                </servers>
+            </settings>"#,
+        )?;
+        std::fs::write(
+            project.join(MVN_FOLDER).join(MAVEN_CONFIG_FILE),
+            "-s ../user.xml\n-o\n-Drevision=2.0.0",
+        )?;
+
+        let loaded = SettingsLoader::new()
+            .with_global_settings(&global)
+            .with_project_directory(&project)
+            .load()?;
+        let settings = &loaded.settings;
+        assert_eq!(
+            settings.local_repository,
+            Some(PathBuf::from("/user/repository"))
+        );
+        assert_eq!(settings.interactive_mode, Some(false));
+        assert_eq!(settings.offline, Some(true));
+        let servers: Vec<_> = settings
+            .servers
+            .servers
+            .iter()
+            .map(|server| (server.id.as_str(), server.username.as_deref()))
+            .collect();
+        assert_eq!(
+            servers,
+            vec![("shared", Some("user")), ("global-only", Some("global"))]
+        );
+        assert_eq!(
+            loaded.system_properties.get("revision").map(String::as_str),
+            Some("2.0.0")
+        );
+
+        let user_source = SettingsSource::User(project.join("../user.xml"));
+        let config_source =
+            SettingsSource::MavenConfig(project.join(MVN_FOLDER).join(MAVEN_CONFIG_FILE));
+        assert_eq!(loaded.origin("localRepository"), Some(&user_source));
+        assert_eq!(
+            loaded.origin("interactiveMode"),
+            Some(&SettingsSource::Global(global.clone()))
+        );
+        assert_eq!(loaded.origin("offline"), Some(&config_source));
+        assert_eq!(loaded.origin("servers/shared"), Some(&user_source));
+        assert_eq!(
+            loaded.origin("servers/global-only"),
+            Some(&SettingsSource::Global(global.clone()))
+        );
+        assert_eq!(
+            loaded.origin("pluginGroups/org.global"),
+            Some(&SettingsSource::Global(global))
+        );
+        assert_eq!(loaded.sources.len(), 3);
+        Ok(())
+    }
+    #[test]
+    fn missing_specified_settings() {
+        let missing = std::env::temp_dir().join("maven-rs-missing-settings.xml");
+        let error = SettingsLoader::new()
+            .with_user_settings(&missing)
+            .load()
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            Error::MissingSettingsFile { kind: "user", path } if *path == missing
+        ));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "The specified user settings file does not exist: {}",
+                missing.display()
+            )
+        );
+        let error = SettingsLoader::new()
+            .with_global_settings(&missing)
+            .load()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::MissingSettingsFile { kind: "global", .. }
+        ));
+    }
+}
@@ -2,7 +2,7 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::editor::{
-    ChildOfListElement, ElementConverter, HasElementName, UpdatableElement,
+    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
     utils::{
         create_basic_text_element, find_or_create_then_set_text_content, from_element_using_builder,
     },
@@ -57,6 +57,11 @@ impl ChildOfListElement for Mirror {
     }
 }
 
+impl ComparableElement for Mirror {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
 impl UpdatableElement for Mirror {
     fn update_element(
         &self,
@@ -0,0 +1,280 @@
+use edit_xml::{Document, Element};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    editor::{
+        ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
+        XMLEditorError,
+        utils::{MissingElementError, create_basic_text_element, get_or_create_top_level_element},
+    },
+    pom::{Activation, PluginRepositories, Properties, Repositories, Repository},
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    #[serde(default, rename = "profile")]
+    pub profiles: Vec<Profile>,
+}
+/// A profile inside of a settings file.
+///
+/// Unlike a profile in a pom file. It can only contain repositories, plugin repositories and properties.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub activation: Option<Activation>,
+    pub properties: Option<Properties>,
+    #[serde(default)]
+    pub repositories: Repositories,
+    #[serde(default)]
+    pub plugin_repositories: PluginRepositories,
+}
+impl Profile {
+    pub fn is_active_by_default(&self) -> bool {
+        self.activation
+            .as_ref()
+            .and_then(|activation| activation.active_by_default)
+            .unwrap_or(false)
+    }
+}
+impl HasElementName for Profile {
+    fn element_name() -> &'static str {
+        "profile"
+    }
+}
+impl ElementConverter for Profile {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut id = None;
+        let mut profile = Profile::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "id" => id = Some(child.text_content(document)),
+                "activation" => {
+                    profile.activation = Some(Activation::from_element(child, document)?)
+                }
+                "properties" => {
+                    profile.properties = Some(Properties::from_element(child, document)?)
+                }
+                "repositories" => {
+                    profile.repositories.repositories = read_repositories(child, document)?;
+                }
+                "pluginRepositories" => {
+                    profile.plugin_repositories.repositories = read_repositories(child, document)?;
+                }
+                _ => {}
+            }
+        }
+        profile.id = id.ok_or(MissingElementError("id"))?;
+        Ok(profile)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        let Self {
+            id,
+            activation,
+            properties,
+            repositories,
+            plugin_repositories,
+        } = self;
+        let mut children = vec![create_basic_text_element(document, "id", id)];
+        if let Some(activation) = activation {
+            children.push(activation.into_element(document)?);
+        }
+        if let Some(properties) = properties {
+            children.push(properties.into_element(document)?);
+        }
+        if !repositories.repositories.is_empty() {
+            let element = Element::new(document, "repositories");
+            write_repositories(element, "repository", repositories.repositories, document)?;
+            children.push(element);
+        }
+        if !plugin_repositories.repositories.is_empty() {
+            let element = Element::new(document, "pluginRepositories");
+            write_repositories(
+                element,
+                "pluginRepository",
+                plugin_repositories.repositories,
+                document,
+            )?;
+            children.push(element);
+        }
+        Ok(children)
+    }
+}
+impl ChildOfListElement for Profile {
+    fn parent_element_name() -> &'static str {
+        "profiles"
+    }
+}
+impl ComparableElement for Profile {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl UpdatableElement for Profile {
+    /// Each section of the profile is only rewritten if it changed. Keeping comments in the untouched sections.
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        let current = Profile::from_element(element, document)?;
+        if current.activation != self.activation {
+            sync_section(element, document, self.activation.clone())?;
+        }
+        if current.properties != self.properties {
+            sync_section(element, document, self.properties.clone())?;
+        }
+        if current.repositories != self.repositories {
+            sync_repositories(
+                element,
+                document,
+                "repositories",
+                "repository",
+                &self.repositories.repositories,
+            )?;
+        }
+        if current.plugin_repositories != self.plugin_repositories {
+            sync_repositories(
+                element,
+                document,
+                "pluginRepositories",
+                "pluginRepository",
+                &self.plugin_repositories.repositories,
+            )?;
+        }
+        Ok(())
+    }
+}
+/// Reads the children of `<repositories>` or `<pluginRepositories>`
+fn read_repositories(
+    element: Element,
+    document: &Document,
+) -> Result<Vec<Repository>, XMLEditorError> {
+    element
+        .child_elements(document)
+        .into_iter()
+        .map(|child| Repository::from_element(child, document))
+        .collect()
+}
+fn write_repositories(
+    parent: Element,
+    child_name: &'static str,
+    repositories: Vec<Repository>,
+    document: &mut Document,
+) -> Result<(), XMLEditorError> {
+    for repository in repositories {
+        let repository_element = Element::new(document, child_name);
+        for child in repository.into_children(document)? {
+            repository_element.push_child(document, child)?;
+        }
+        parent.push_child(document, repository_element)?;
+    }
+    Ok(())
+}
+fn sync_section<E>(
+    parent: Element,
+    document: &mut Document,
+    value: Option<E>,
+) -> Result<(), XMLEditorError>
+where
+    E: UpdatableElement + HasElementName,
+{
+    match value {
+        Some(value) => {
+            let element = get_or_create_top_level_element(E::element_name(), document, parent);
+            value.replace_all_elements(element, document)
+        }
+        None => {
+            if let Some(element) = parent.find(document, E::element_name()) {
+                element.detach(document)?;
+            }
+            Ok(())
+        }
+    }
+}
+fn sync_repositories(
+    parent: Element,
+    document: &mut Document,
+    name: &'static str,
+    child_name: &'static str,
+    repositories: &[Repository],
+) -> Result<(), XMLEditorError> {
+    if repositories.is_empty() {
+        if let Some(element) = parent.find(document, name) {
+            element.detach(document)?;
+        }
+        return Ok(());
+    }
+    let element = get_or_create_top_level_element(name, document, parent);
+    element.clear_children(document);
+    write_repositories(element, child_name, repositories.to_vec(), document)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::editor::utils::test_utils;
+
+    use super::*;
+    #[test]
+    fn parse_methods() -> anyhow::Result<()> {
+        let xml = r#"
+            <profile>
+                <id>internal</id>
+                <activation>
+                    <activeByDefault>true</activeByDefault>
+                </activation>
+                <properties>
+                    <internal.url>https://repo.example.com</internal.url>
+                </properties>
+                <repositories>
+                    <repository>
+                        <id>internal</id>
+                        <url>https://repo.example.com/releases</url>
+                    </repository>
+                </repositories>
+                <pluginRepositories>
+                    <pluginRepository>
+                        <id>internal-plugins</id>
+                        <url>https://repo.example.com/plugins</url>
+                    </pluginRepository>
+                </pluginRepositories>
+            </profile>
+        "#;
+        let mut properties = Properties::default();
+        properties.0.insert(
+            "internal.url".to_owned(),
+            "https://repo.example.com".to_owned(),
+        );
+        let expected = Profile {
+            id: "internal".to_owned(),
+            activation: Some(Activation {
+                active_by_default: Some(true),
+                ..Default::default()
+            }),
+            properties: Some(properties),
+            repositories: Repositories {
+                repositories: vec![Repository {
+                    id: Some("internal".to_owned()),
+                    url: "https://repo.example.com/releases".to_owned(),
+                    ..Default::default()
+                }],
+            },
+            plugin_repositories: PluginRepositories {
+                repositories: vec![Repository {
+                    id: Some("internal-plugins".to_owned()),
+                    url: "https://repo.example.com/plugins".to_owned(),
+                    ..Default::default()
+                }],
+            },
+        };
+        let via_edit_xml = test_utils::create_xml_to_element::<Profile>(xml)?;
+        let via_serde: Profile = quick_xml::de::from_str(xml)?;
+        assert_eq!(via_edit_xml, expected);
+        assert_eq!(via_serde, expected);
+        assert!(expected.is_active_by_default());
+        Ok(())
+    }
+}
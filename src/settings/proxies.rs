@@ -0,0 +1,247 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::{
+    ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
+    utils::{
+        add_if_present, create_basic_text_element, find_or_create_then_set_text_content,
+        sync_element, typed_from_element_using_builder,
+    },
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proxies {
+    #[serde(default, rename = "proxy")]
+    pub proxies: Vec<Proxy>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Builder)]
+#[serde(rename_all = "camelCase")]
+pub struct Proxy {
+    #[builder(setter(into, strip_option), default)]
+    pub id: Option<String>,
+    /// Defaults to true if not set
+    #[builder(setter(into, strip_option), default)]
+    pub active: Option<bool>,
+    /// Defaults to `http` if not set
+    #[builder(setter(into, strip_option), default)]
+    pub protocol: Option<String>,
+    #[builder(setter(into))]
+    pub host: String,
+    #[builder(setter(into, strip_option), default)]
+    pub port: Option<u16>,
+    #[builder(setter(into, strip_option), default)]
+    pub username: Option<String>,
+    #[builder(setter(into, strip_option), default)]
+    pub password: Option<String>,
+    /// A `|` separated list of hosts that should not use the proxy. Such as `*.example.com|localhost`
+    #[builder(setter(into, strip_option), default)]
+    pub non_proxy_hosts: Option<String>,
+}
+impl Proxy {
+    pub fn is_active(&self) -> bool {
+        self.active.unwrap_or(true)
+    }
+    pub fn protocol_or_default(&self) -> &str {
+        self.protocol.as_deref().unwrap_or("http")
+    }
+    /// The port or the default port of `8080`. The same default as the settings model of Maven
+    pub fn port_or_default(&self) -> u16 {
+        self.port.unwrap_or(8080)
+    }
+    pub fn username_and_password(&self) -> Option<(&str, &str)> {
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => Some((username.as_str(), password.as_str())),
+            _ => None,
+        }
+    }
+    /// The hosts in [Proxy::non_proxy_hosts]. Split on `|` and `,`
+    pub fn non_proxy_hosts(&self) -> Vec<&str> {
+        self.non_proxy_hosts
+            .as_deref()
+            .map(|hosts| {
+                hosts
+                    .split(['|', ','])
+                    .map(str::trim)
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    /// If the proxy is used for the protocol. [Proxy::protocol] can be a comma separated list such as `http,https`
+    pub fn supports_protocol(&self, protocol: &str) -> bool {
+        self.protocol_or_default()
+            .split(',')
+            .any(|supported| supported.trim().eq_ignore_ascii_case(protocol))
+    }
+    /// If the host matches one of [Proxy::non_proxy_hosts]. A `*` matches any number of characters.
+    ///
+    /// ```
+    /// use maven_rs::settings::ProxyBuilder;
+    /// let proxy = ProxyBuilder::default()
+    ///     .host("proxy.corp")
+    ///     .non_proxy_hosts("*.corp|localhost")
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(proxy.is_non_proxy_host("repo.corp"));
+    /// assert!(proxy.is_non_proxy_host("LOCALHOST"));
+    /// assert!(!proxy.is_non_proxy_host("repo1.maven.org"));
+    /// ```
+    pub fn is_non_proxy_host(&self, host: &str) -> bool {
+        self.non_proxy_hosts()
+            .into_iter()
+            .any(|pattern| wildcard_matches(pattern, host))
+    }
+}
+/// Case insensitive match where `*` matches any number of characters
+fn wildcard_matches(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let value = value.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    // split always returns at least one part
+    let first = parts.next().unwrap_or_default();
+    let Some(mut remaining) = value.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No wildcard
+        return remaining.is_empty();
+    };
+    for part in parts {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+impl HasElementName for Proxy {
+    fn element_name() -> &'static str {
+        "proxy"
+    }
+}
+impl ElementConverter for Proxy {
+    typed_from_element_using_builder!(
+        ProxyBuilder,
+        element,
+        document,
+        "id"(String) => id,
+        "active"(bool) => active,
+        "protocol"(String) => protocol,
+        "host"(String) => host,
+        "port"(u16) => port,
+        "username"(String) => username,
+        "password"(String) => password,
+        "nonProxyHosts"(String) => non_proxy_hosts
+    );
+    fn into_children(
+        self,
+        document: &mut edit_xml::Document,
+    ) -> Result<Vec<edit_xml::Element>, crate::editor::XMLEditorError> {
+        let Self {
+            id,
+            active,
+            protocol,
+            host,
+            port,
+            username,
+            password,
+            non_proxy_hosts,
+        } = self;
+        let mut children = vec![];
+        add_if_present!(document, children, id, "id");
+        add_if_present!(document, children, active, "active");
+        add_if_present!(document, children, protocol, "protocol");
+        children.push(create_basic_text_element(document, "host", host));
+        add_if_present!(document, children, port, "port");
+        add_if_present!(document, children, username, "username");
+        add_if_present!(document, children, password, "password");
+        add_if_present!(document, children, non_proxy_hosts, "nonProxyHosts");
+        Ok(children)
+    }
+}
+impl ChildOfListElement for Proxy {
+    fn parent_element_name() -> &'static str {
+        "proxies"
+    }
+}
+impl ComparableElement for Proxy {
+    fn is_same_item(&self, other: &Self) -> bool {
+        if self.id.is_none() {
+            return false;
+        }
+        self.id == other.id
+    }
+}
+impl UpdatableElement for Proxy {
+    fn update_element(
+        &self,
+        element: edit_xml::Element,
+        document: &mut edit_xml::Document,
+    ) -> Result<(), crate::editor::XMLEditorError> {
+        sync_element(
+            document,
+            element,
+            "active",
+            self.active.map(|x| x.to_string()),
+        );
+        sync_element(document, element, "protocol", self.protocol.as_deref());
+        find_or_create_then_set_text_content(document, element, "host", self.host.as_str());
+        sync_element(document, element, "port", self.port.map(|x| x.to_string()));
+        sync_element(document, element, "username", self.username.as_deref());
+        sync_element(document, element, "password", self.password.as_deref());
+        sync_element(
+            document,
+            element,
+            "nonProxyHosts",
+            self.non_proxy_hosts.as_deref(),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_matches("*.corp", "repo.corp"));
+        assert!(wildcard_matches("*.corp", "a.b.corp"));
+        assert!(!wildcard_matches("*.corp", "corp"));
+        assert!(wildcard_matches("192.168.*", "192.168.1.20"));
+        assert!(wildcard_matches("repo*.example.*", "repo1.example.com"));
+        assert!(!wildcard_matches("repo*.example.*", "nexus.example.com"));
+        assert!(wildcard_matches("localhost", "LocalHost"));
+        assert!(!wildcard_matches("localhost", "localhost.corp"));
+        assert!(wildcard_matches("*", "anything"));
+    }
+
+    #[test]
+    fn protocols() {
+        let proxy = ProxyBuilder::default()
+            .host("proxy.corp")
+            .protocol("http, https")
+            .build()
+            .unwrap();
+        assert!(proxy.supports_protocol("https"));
+        assert!(proxy.supports_protocol("HTTP"));
+        assert!(!proxy.supports_protocol("ftp"));
+        let default = ProxyBuilder::default().host("proxy.corp").build().unwrap();
+        assert!(default.supports_protocol("http"));
+        assert!(!default.supports_protocol("https"));
+    }
+
+    #[test]
+    fn default_port() {
+        let proxy = ProxyBuilder::default().host("proxy.corp").build().unwrap();
+        assert_eq!(proxy.port_or_default(), 8080);
+        let proxy = ProxyBuilder::default()
+            .host("proxy.corp")
+            .port(3128u16)
+            .build()
+            .unwrap();
+        assert_eq!(proxy.port_or_default(), 3128);
+    }
+}
@@ -0,0 +1,364 @@
+//! Maven password encryption
+//!
+//! Maven encrypts passwords in `settings.xml` with a master password.
+//! The master password is itself encrypted inside of `settings-security.xml`
+//!
+//! [More Info](https://maven.apache.org/guides/mini/guide-encryption.html)
+//!
+//! Encrypted values look like `{COQLCE6DU6GtcS5P=}`. The content between the braces is
+//! base64 of `salt(8) + padding length(1) + AES-128-CBC cipher text + random padding`.
+//! The key and IV are the SHA-256 of the password followed by the salt.
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    string::FromUtf8Error,
+};
+
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, block_padding::Pkcs7};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{Proxy, Server, get_settings_directory};
+
+pub static SETTINGS_SECURITY_FILE: &str = "settings-security.xml";
+/// The password used to encrypt the master password.
+pub static MASTER_PASSWORD_KEY: &str = "settings.security";
+
+const SALT_SIZE: usize = 8;
+const CHUNK_SIZE: usize = 16;
+const SPICE_SIZE: usize = 16;
+
+type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+#[derive(Debug, Error)]
+pub enum SecurityError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error(transparent)]
+    XMLDeserialize(#[from] quick_xml::DeError),
+    #[error("Invalid Base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Invalid encrypted value: {0}")]
+    InvalidEncryptedValue(&'static str),
+    #[error("Unable to decrypt the value. The password is most likely wrong")]
+    DecryptionFailed,
+    #[error("The decrypted value is not valid UTF-8: {0}")]
+    InvalidUtf8(#[from] FromUtf8Error),
+    #[error("No master password is set in the settings security file")]
+    NoMasterPassword,
+    #[error("Unable to generate a random salt: {0}")]
+    Random(#[from] getrandom::Error),
+    #[error("Relocation loop detected at {0}")]
+    RelocationLoop(PathBuf),
+}
+/// The content of `settings-security.xml`
+///
+/// ```xml
+/// <settingsSecurity>
+///     <master>{jSMOWnoPFgsHVpMvz5VrIt5kRbzGpI8u+9EF1iFQyJQ=}</master>
+/// </settingsSecurity>
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "settingsSecurity")]
+pub struct SettingsSecurity {
+    /// The encrypted master password
+    pub master: Option<String>,
+    /// The path to another settings security file. Used to keep the master password on a removable drive
+    pub relocation: Option<PathBuf>,
+}
+impl SettingsSecurity {
+    /// Creates a new settings security with the master password encrypted
+    pub fn new_with_master_password(master_password: &str) -> Result<Self, SecurityError> {
+        Ok(Self {
+            master: Some(encrypt(master_password, MASTER_PASSWORD_KEY)?),
+            relocation: None,
+        })
+    }
+    /// Reads `~/.m2/settings-security.xml`
+    ///
+    /// Returns None if the file does not exist or the home directory is not found.
+    pub fn read_local() -> Result<Option<Self>, SecurityError> {
+        let Some(path) = get_settings_security_path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::read_from_path(path).map(Some)
+    }
+    /// Reads a settings security file. Following any relocations.
+    pub fn read_from_path(path: impl AsRef<Path>) -> Result<Self, SecurityError> {
+        let mut path = path.as_ref().to_path_buf();
+        let mut visited = vec![];
+        loop {
+            if visited.contains(&path) {
+                return Err(SecurityError::RelocationLoop(path));
+            }
+            let file = std::fs::File::open(&path)?;
+            let security: SettingsSecurity = quick_xml::de::from_reader(BufReader::new(file))?;
+            let Some(relocation) = &security.relocation else {
+                return Ok(security);
+            };
+            let relocation = relocation.clone();
+            visited.push(path);
+            path = relocation;
+        }
+    }
+    /// Decrypts the master password
+    pub fn decrypt_master_password(&self) -> Result<String, SecurityError> {
+        let master = self
+            .master
+            .as_deref()
+            .ok_or(SecurityError::NoMasterPassword)?;
+        decrypt(master, MASTER_PASSWORD_KEY)
+    }
+    /// Decrypts a value from the settings file with the master password.
+    ///
+    /// Values that are not encrypted are returned as is.
+    pub fn decrypt(&self, value: &str) -> Result<String, SecurityError> {
+        if !is_encrypted(value) {
+            return Ok(value.to_owned());
+        }
+        decrypt(value, &self.decrypt_master_password()?)
+    }
+    /// Encrypts a value with the master password. The result is ready to be put in the settings file.
+    pub fn encrypt(&self, value: &str) -> Result<String, SecurityError> {
+        encrypt(value, &self.decrypt_master_password()?)
+    }
+}
+/// Returns the path to `~/.m2/settings-security.xml`
+///
+/// If the home directory is not found, None is returned.
+pub fn get_settings_security_path() -> Option<PathBuf> {
+    get_settings_directory().map(|dir| dir.join(SETTINGS_SECURITY_FILE))
+}
+/// Finds the content between the first unescaped `{` and the next unescaped `}`
+fn encrypted_content(value: &str) -> Option<&str> {
+    let bytes = value.as_bytes();
+    let is_unescaped = |index: usize| index == 0 || bytes[index - 1] != b'\\';
+    let start = (0..bytes.len()).find(|&index| bytes[index] == b'{' && is_unescaped(index))?;
+    let end =
+        (start + 2..bytes.len()).find(|&index| bytes[index] == b'}' && is_unescaped(index))?;
+    Some(&value[start + 1..end])
+}
+/// Checks if the value contains an encrypted value. `{...}`
+pub fn is_encrypted(value: &str) -> bool {
+    encrypted_content(value).is_some()
+}
+/// Decrypts an encrypted value. Such as `{COQLCE6DU6GtcS5P=}`
+pub fn decrypt(value: &str, password: &str) -> Result<String, SecurityError> {
+    let content =
+        encrypted_content(value).ok_or(SecurityError::InvalidEncryptedValue("missing braces"))?;
+    let all_bytes = STANDARD.decode(content)?;
+    if all_bytes.len() <= SALT_SIZE {
+        return Err(SecurityError::InvalidEncryptedValue("too short"));
+    }
+    let (salt, rest) = all_bytes.split_at(SALT_SIZE);
+    let pad_length = rest[0] as usize;
+    let encrypted_end = rest
+        .len()
+        .checked_sub(pad_length)
+        .filter(|end| *end > 1)
+        .ok_or(SecurityError::InvalidEncryptedValue(
+            "invalid padding length",
+        ))?;
+    let encrypted = &rest[1..encrypted_end];
+
+    let (key, iv) = key_and_iv(password, salt);
+    let clear = Aes128CbcDec::new(&key.into(), &iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(encrypted)
+        .map_err(|_| SecurityError::DecryptionFailed)?;
+    Ok(String::from_utf8(clear)?)
+}
+/// Encrypts a value. The result is wrapped in braces.
+pub fn encrypt(value: &str, password: &str) -> Result<String, SecurityError> {
+    let mut salt = [0u8; SALT_SIZE];
+    getrandom::fill(&mut salt)?;
+    let mut filler = [0u8; CHUNK_SIZE];
+    getrandom::fill(&mut filler)?;
+    Ok(encrypt_with_salt(value, password, salt, &filler))
+}
+fn encrypt_with_salt(
+    value: &str,
+    password: &str,
+    salt: [u8; SALT_SIZE],
+    filler: &[u8; CHUNK_SIZE],
+) -> String {
+    let (key, iv) = key_and_iv(password, &salt);
+    let encrypted = Aes128CbcEnc::new(&key.into(), &iv.into())
+        .encrypt_padded_vec_mut::<Pkcs7>(value.as_bytes());
+    let pad_length = CHUNK_SIZE - (SALT_SIZE + encrypted.len() + 1) % CHUNK_SIZE;
+
+    let mut all_bytes = Vec::with_capacity(SALT_SIZE + 1 + encrypted.len() + pad_length);
+    all_bytes.extend_from_slice(&salt);
+    all_bytes.push(pad_length as u8);
+    all_bytes.extend_from_slice(&encrypted);
+    all_bytes.extend_from_slice(&filler[..pad_length]);
+    format!("{{{}}}", STANDARD.encode(all_bytes))
+}
+/// Repeats SHA-256 over the password and salt until there are enough bytes for the key and IV
+fn key_and_iv(password: &str, salt: &[u8]) -> ([u8; SPICE_SIZE], [u8; SPICE_SIZE]) {
+    let mut key_and_iv = [0u8; SPICE_SIZE * 2];
+    let mut current_position = 0;
+    let mut previous: Option<Vec<u8>> = None;
+    while current_position < key_and_iv.len() {
+        let mut digest = Sha256::new();
+        if let Some(previous) = &previous {
+            digest.update(previous);
+        }
+        digest.update(password.as_bytes());
+        digest.update(salt);
+        let result = digest.finalize();
+        let needed = (key_and_iv.len() - current_position).min(result.len());
+        key_and_iv[current_position..current_position + needed].copy_from_slice(&result[..needed]);
+        current_position += needed;
+        previous = Some(result[..needed].to_vec());
+    }
+    let mut key = [0u8; SPICE_SIZE];
+    let mut iv = [0u8; SPICE_SIZE];
+    key.copy_from_slice(&key_and_iv[..SPICE_SIZE]);
+    iv.copy_from_slice(&key_and_iv[SPICE_SIZE..]);
+    (key, iv)
+}
+impl Server {
+    /// Returns a copy of the server with the password and passphrase decrypted
+    pub fn decrypted(&self, security: &SettingsSecurity) -> Result<Server, SecurityError> {
+        let mut server = self.clone();
+        if let Some(password) = &self.password {
+            server.password = Some(security.decrypt(password)?);
+        }
+        if let Some(passphrase) = &self.passphrase {
+            server.passphrase = Some(security.decrypt(passphrase)?);
+        }
+        Ok(server)
+    }
+}
+impl Proxy {
+    /// Returns a copy of the proxy with the password decrypted
+    pub fn decrypted(&self, security: &SettingsSecurity) -> Result<Proxy, SecurityError> {
+        let mut proxy = self.clone();
+        if let Some(password) = &self.password {
+            proxy.password = Some(security.decrypt(password)?);
+        }
+        Ok(proxy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::utils::test_utils::TempDirectory;
+    /// Generated with an independent implementation of Maven's cipher
+    const MASTER: &str = "{AQIDBAUGBwgH3uobx+MOHO29Q0mds5/FHQAAAAAAAAA=}";
+    const SERVER_PASSWORD: &str = "{CgsMDQ4PEBEHIsuf/QEIkKxAwT+VWZrBYwAAAAAAAAA=}";
+
+    #[test]
+    fn known_values() -> anyhow::Result<()> {
+        assert_eq!(
+            encrypt_with_salt(
+                "master-password",
+                MASTER_PASSWORD_KEY,
+                [1, 2, 3, 4, 5, 6, 7, 8],
+                &[0; CHUNK_SIZE]
+            ),
+            MASTER
+        );
+        let security = SettingsSecurity {
+            master: Some(MASTER.to_owned()),
+            relocation: None,
+        };
+        assert_eq!(security.decrypt_master_password()?, "master-password");
+        assert_eq!(security.decrypt(SERVER_PASSWORD)?, "server-secret");
+        assert_eq!(security.decrypt("not-encrypted")?, "not-encrypted");
+        Ok(())
+    }
+    #[test]
+    fn round_trip() -> anyhow::Result<()> {
+        let security = SettingsSecurity::new_with_master_password("master")?;
+        for value in [
+            "",
+            "a",
+            "a password that is longer than a single block",
+            "ünïcödé",
+        ] {
+            let encrypted = security.encrypt(value)?;
+            assert!(is_encrypted(&encrypted));
+            assert_eq!(security.decrypt(&encrypted)?, value);
+        }
+        let wrong = SettingsSecurity::new_with_master_password("wrong")?;
+        let encrypted = security.encrypt("value")?;
+        assert!(
+            wrong
+                .decrypt(&encrypted)
+                .map_or(true, |value| value != "value")
+        );
+        Ok(())
+    }
+    #[test]
+    fn decorated_values() {
+        assert_eq!(encrypted_content("{abc=}"), Some("abc="));
+        assert_eq!(
+            encrypted_content("Oleg reset this password on 2009-03-11 {abc=}"),
+            Some("abc=")
+        );
+        assert_eq!(encrypted_content("\\{abc=}"), None);
+        assert_eq!(encrypted_content("{}"), None);
+        assert_eq!(encrypted_content("plain"), None);
+    }
+    #[test]
+    fn decrypt_server() -> anyhow::Result<()> {
+        let security = SettingsSecurity {
+            master: Some(MASTER.to_owned()),
+            relocation: None,
+        };
+        let server = Server {
+            id: "internal".to_owned(),
+            username: Some("deployer".to_owned()),
+            password: Some(SERVER_PASSWORD.to_owned()),
+            ..Default::default()
+        };
+        let decrypted = server.decrypted(&security)?;
+        assert_eq!(
+            decrypted.username_and_password(),
+            Some(("deployer", "server-secret"))
+        );
+        Ok(())
+    }
+    #[test]
+    fn relocation() -> anyhow::Result<()> {
+        let directory = TempDirectory::new("security");
+        let relocated = directory.join("relocated.xml");
+        let main = directory.join("settings-security.xml");
+        std::fs::write(
+            &relocated,
+            format!("<settingsSecurity><master>{MASTER}</master></settingsSecurity>"),
+        )?;
+        std::fs::write(
+            &main,
+            format!(
+                "<settingsSecurity><relocation>{}</relocation></settingsSecurity>",
+                relocated.display()
+            ),
+        )?;
+        let security = SettingsSecurity::read_from_path(&main)?;
+        assert_eq!(security.decrypt_master_password()?, "master-password");
+
+        std::fs::write(
+            &relocated,
+            format!(
+                "<settingsSecurity><relocation>{}</relocation></settingsSecurity>",
+                main.display()
+            ),
+        )?;
+        assert!(matches!(
+            SettingsSecurity::read_from_path(&main),
+            Err(SecurityError::RelocationLoop(_))
+        ));
+        Ok(())
+    }
+}
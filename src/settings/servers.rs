@@ -1,9 +1,15 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::editor::{
-    ChildOfListElement, ElementConverter, HasElementName, UpdatableElement,
-    utils::{add_if_present, create_basic_text_element, from_element_using_builder},
+use crate::{
+    editor::{
+        ChildOfListElement, ComparableElement, ElementConverter, HasElementName, UpdatableElement,
+        utils::{
+            add_if_present, create_basic_text_element, get_or_create_top_level_element,
+            sync_element,
+        },
+    },
+    types::ConfigurationElement,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -13,6 +19,7 @@ pub struct Servers {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Builder, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct Server {
     pub id: String,
     #[builder(setter(into, strip_option), default)]
@@ -27,7 +34,9 @@ pub struct Server {
     pub file_permissions: Option<String>,
     #[builder(setter(into, strip_option), default)]
     pub directory_permissions: Option<String>,
-    // TODO: configuration elements
+    /// Extra configuration used by the transport. Such as `httpHeaders` or timeouts
+    #[builder(setter(into, strip_option), default)]
+    pub configuration: Option<ConfigurationElement>,
 }
 
 impl Server {
@@ -53,18 +62,43 @@ impl HasElementName for Server {
     }
 }
 impl ElementConverter for Server {
-    from_element_using_builder!(
-        ServerBuilder,
-        element,
-        document,
-        "id" => id,
-        "username" => username,
-        "password" => password,
-        "privateKey" => private_key,
-        "passphrase" => passphrase,
-        "filePermissions" => file_permissions,
-        "directoryPermissions" => directory_permissions
-    );
+    fn from_element(
+        element: edit_xml::Element,
+        document: &edit_xml::Document,
+    ) -> Result<Self, crate::editor::XMLEditorError> {
+        let mut builder = ServerBuilder::default();
+        for child in element.child_elements(document) {
+            match child.name(document) {
+                "id" => {
+                    builder.id(child.text_content(document));
+                }
+                "username" => {
+                    builder.username(child.text_content(document));
+                }
+                "password" => {
+                    builder.password(child.text_content(document));
+                }
+                "privateKey" => {
+                    builder.private_key(child.text_content(document));
+                }
+                "passphrase" => {
+                    builder.passphrase(child.text_content(document));
+                }
+                "filePermissions" => {
+                    builder.file_permissions(child.text_content(document));
+                }
+                "directoryPermissions" => {
+                    builder.directory_permissions(child.text_content(document));
+                }
+                "configuration" => {
+                    builder.configuration(ConfigurationElement::from_element(child, document)?);
+                }
+                _ => {}
+            }
+        }
+        let result = builder.build()?;
+        Ok(result)
+    }
 
     fn into_children(
         self,
@@ -78,6 +112,7 @@ impl ElementConverter for Server {
             passphrase,
             file_permissions,
             directory_permissions,
+            configuration,
         } = self;
         let mut children = vec![create_basic_text_element(document, "id", id)];
 
@@ -92,6 +127,9 @@ impl ElementConverter for Server {
             directory_permissions,
             "directoryPermissions"
         );
+        if let Some(configuration) = configuration {
+            children.push(configuration.into_element(document)?);
+        }
 
         Ok(children)
     }
@@ -102,16 +140,42 @@ impl ChildOfListElement for Server {
         "servers"
     }
 }
+impl ComparableElement for Server {
+    fn is_same_item(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
 impl UpdatableElement for Server {
-    /// Will rewrite the entire element with the current element. Because it might be a change from a password to a private key.
+    /// Every field is synced. So switching from a password to a private key removes the password.
+    /// The `<configuration>` is the exception. If the update has none the existing one is kept,
+    /// because it is often written by hand and not known to the code updating the credentials.
+    ///
+    /// Elements are updated in place to keep any comments inside of the server.
     fn update_element(
         &self,
         element: edit_xml::Element,
         document: &mut edit_xml::Document,
     ) -> Result<(), crate::editor::XMLEditorError> {
-        element.clear_children(document);
-        for child in self.clone().into_children(document)? {
-            element.push_child(document, child)?;
+        sync_element(document, element, "username", self.username.as_deref());
+        sync_element(document, element, "password", self.password.as_deref());
+        sync_element(document, element, "privateKey", self.private_key.as_deref());
+        sync_element(document, element, "passphrase", self.passphrase.as_deref());
+        sync_element(
+            document,
+            element,
+            "filePermissions",
+            self.file_permissions.as_deref(),
+        );
+        sync_element(
+            document,
+            element,
+            "directoryPermissions",
+            self.directory_permissions.as_deref(),
+        );
+        if let Some(configuration) = &self.configuration {
+            let configuration_element =
+                get_or_create_top_level_element("configuration", document, element);
+            configuration.update_element(configuration_element, document)?;
         }
         Ok(())
     }
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use edit_xml::{Document, Element, Node};
+use serde::{
+    Deserialize, Serialize,
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+};
+
+use crate::editor::{
+    ElementConverter, HasElementName, UpdatableElement, XMLEditorError, utils::text_value,
+};
+
+/// An XML element without a fixed schema.
+///
+/// Used for `<configuration>` blocks where the content depends on what is reading it.
+///
+/// ```xml
+/// <configuration>
+///     <httpHeaders>
+///         <property>
+///             <name>Authorization</name>
+///             <value>Bearer token</value>
+///         </property>
+///     </httpHeaders>
+///     <timeout>10000</timeout>
+/// </configuration>
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigurationElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    /// The text of the element. Only set if the element has no children
+    pub value: Option<String>,
+    pub children: Vec<ConfigurationElement>,
+}
+impl ConfigurationElement {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+    pub fn new_with_value(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: Some(value.into()),
+            ..Default::default()
+        }
+    }
+    /// Adds a child and returns self
+    pub fn with_child(mut self, child: ConfigurationElement) -> Self {
+        self.children.push(child);
+        self
+    }
+    /// Finds the first child with the name
+    pub fn child(&self, name: &str) -> Option<&ConfigurationElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+    /// Finds all children with the name
+    pub fn children_named<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a ConfigurationElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+    /// Follows a path of child names separated by `/`
+    ///
+    /// `httpHeaders/property` returns the first `property` in the first `httpHeaders`
+    pub fn find(&self, path: &str) -> Option<&ConfigurationElement> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self, |current, name| current.child(name))
+    }
+    /// The value of the child found using [ConfigurationElement::find]
+    pub fn find_value(&self, path: &str) -> Option<&str> {
+        self.find(path).and_then(|element| element.value.as_deref())
+    }
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+impl HasElementName for ConfigurationElement {
+    fn element_name() -> &'static str {
+        "configuration"
+    }
+}
+impl ElementConverter for ConfigurationElement {
+    fn from_element(element: Element, document: &Document) -> Result<Self, XMLEditorError> {
+        let mut attributes: Vec<(String, String)> = element
+            .attributes(document)
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        attributes.sort();
+        let children = element
+            .child_elements(document)
+            .into_iter()
+            .map(|child| ConfigurationElement::from_element(child, document))
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = if children.is_empty() {
+            let has_text = element
+                .children(document)
+                .iter()
+                .any(|node| matches!(node, Node::Text(_) | Node::CData(_)));
+            has_text.then(|| text_value(element, document))
+        } else {
+            None
+        };
+        Ok(Self {
+            name: element.name(document).to_owned(),
+            attributes,
+            value,
+            children,
+        })
+    }
+    /// Uses [ConfigurationElement::name] instead of [HasElementName::element_name]
+    fn into_element(self, document: &mut Document) -> Result<Element, XMLEditorError> {
+        let element = Element::new(document, self.name.clone());
+        for (key, value) in &self.attributes {
+            element.set_attribute(document, key, value);
+        }
+        if let Some(value) = &self.value {
+            element.set_text_content(document, value);
+        }
+        for child in self.into_children(document)? {
+            element.push_child(document, child)?;
+        }
+        Ok(element)
+    }
+
+    fn into_children(self, document: &mut Document) -> Result<Vec<Element>, XMLEditorError> {
+        self.children
+            .into_iter()
+            .map(|child| child.into_element(document))
+            .collect()
+    }
+}
+impl UpdatableElement for ConfigurationElement {
+    /// The content has no schema to match against. So all children and attributes are replaced.
+    fn update_element(
+        &self,
+        element: Element,
+        document: &mut Document,
+    ) -> Result<(), XMLEditorError> {
+        element.clear_children(document);
+        let stale: Vec<String> = element
+            .attributes(document)
+            .keys()
+            .filter(|key| !self.attributes.iter().any(|(name, _)| name == *key))
+            .cloned()
+            .collect();
+        for key in stale {
+            element.remove_attribute(document, &key);
+        }
+        for (key, value) in &self.attributes {
+            element.set_attribute(document, key, value);
+        }
+        if let Some(value) = &self.value {
+            element.set_text_content(document, value);
+        }
+        for child in self.clone().into_children(document)? {
+            element.push_child(document, child)?;
+        }
+        Ok(())
+    }
+}
+impl Serialize for ConfigurationElement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.attributes.is_empty() && self.children.is_empty() {
+            return match &self.value {
+                Some(value) => serializer.serialize_str(value),
+                None => serializer.serialize_unit(),
+            };
+        }
+        let mut map = serializer.serialize_map(None)?;
+        for (key, value) in &self.attributes {
+            map.serialize_entry(&format!("@{key}"), value)?;
+        }
+        if let Some(value) = &self.value {
+            map.serialize_entry("$text", value)?;
+        }
+        for child in &self.children {
+            map.serialize_entry(&child.name, child)?;
+        }
+        map.end()
+    }
+}
+impl<'de> Deserialize<'de> for ConfigurationElement {
+    /// The name of the element is not known here. The parent sets it. Otherwise it defaults to `configuration`
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ConfigurationVisitor;
+        impl<'de> Visitor<'de> for ConfigurationVisitor {
+            type Value = ConfigurationElement;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an XML element")
+            }
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(ConfigurationElement::new_with_value(
+                    ConfigurationElement::element_name(),
+                    value,
+                ))
+            }
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(ConfigurationElement::new(
+                    ConfigurationElement::element_name(),
+                ))
+            }
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut element = ConfigurationElement::new(ConfigurationElement::element_name());
+                while let Some(key) = map.next_key::<String>()? {
+                    if let Some(attribute) = key.strip_prefix('@') {
+                        element
+                            .attributes
+                            .push((attribute.to_owned(), map.next_value()?));
+                    } else if key == "$text" {
+                        element.value = Some(map.next_value()?);
+                    } else {
+                        let mut child: ConfigurationElement = map.next_value()?;
+                        child.name = key;
+                        element.children.push(child);
+                    }
+                }
+                element.attributes.sort();
+                Ok(element)
+            }
+        }
+        deserializer.deserialize_any(ConfigurationVisitor)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::editor::utils::test_utils;
+
+    use super::*;
+
+    #[test]
+    fn parse_methods() -> anyhow::Result<()> {
+        let xml = r#"
+            <configuration>
+                <httpHeaders>
+                    <property>
+                        <name>Authorization</name>
+                        <value>Bearer token</value>
+                    </property>
+                </httpHeaders>
+                <timeout>10000</timeout>
+                <empty/>
+                <withAttribute key="value">text</withAttribute>
+            </configuration>
+        "#;
+        let expected = ConfigurationElement::new("configuration")
+            .with_child(
+                ConfigurationElement::new("httpHeaders").with_child(
+                    ConfigurationElement::new("property")
+                        .with_child(ConfigurationElement::new_with_value(
+                            "name",
+                            "Authorization",
+                        ))
+                        .with_child(ConfigurationElement::new_with_value(
+                            "value",
+                            "Bearer token",
+                        )),
+                ),
+            )
+            .with_child(ConfigurationElement::new_with_value("timeout", "10000"))
+            .with_child(ConfigurationElement::new("empty"))
+            .with_child(ConfigurationElement {
+                name: "withAttribute".to_owned(),
+                attributes: vec![("key".to_owned(), "value".to_owned())],
+                value: Some("text".to_owned()),
+                children: vec![],
+            });
+        let via_edit_xml = test_utils::create_xml_to_element::<ConfigurationElement>(xml)?;
+        let via_serde: ConfigurationElement = quick_xml::de::from_str(xml.trim())?;
+        assert_eq!(via_edit_xml, expected);
+        assert_eq!(via_serde, expected);
+        assert_eq!(
+            expected.find_value("httpHeaders/property/name"),
+            Some("Authorization")
+        );
+
+        let serialized = quick_xml::se::to_string_with_root("configuration", &expected)?;
+        let round_trip: ConfigurationElement = quick_xml::de::from_str(&serialized)?;
+        assert_eq!(round_trip, expected);
+        Ok(())
+    }
+    #[test]
+    fn update_replaces_attributes() -> anyhow::Result<()> {
+        let mut document = Document::parse_str(
+            r#"<?xml version="1.0" encoding="UTF-8"?><configuration old="1" key="old"><timeout>10000</timeout></configuration>"#,
+        )?;
+        let element = document.root_element().unwrap();
+        let update = ConfigurationElement {
+            name: "configuration".to_owned(),
+            attributes: vec![("key".to_owned(), "new".to_owned())],
+            value: None,
+            children: vec![ConfigurationElement::new_with_value("timeout", "20000")],
+        };
+        update.update_element(element, &mut document)?;
+        assert_eq!(
+            ConfigurationElement::from_element(element, &document)?,
+            update
+        );
+        Ok(())
+    }
+}
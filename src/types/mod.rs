@@ -1,5 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
+use thiserror::Error;
+
 use prop::ParseState;
 
 use crate::{
@@ -7,7 +9,13 @@ use crate::{
     utils::{parse::ParseErrorExt, serde_utils::serde_via_string_types},
 };
 
+mod configuration;
 pub(crate) mod prop;
+mod version;
+mod version_range;
+pub use configuration::*;
+pub use version::*;
+pub use version_range::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Property {
@@ -63,7 +71,62 @@ impl Property {
         };
         name.starts_with("project.")
     }
+    /// Builds the value by replacing each variable with the result of `lookup`
+    ///
+    /// Variables that `lookup` can not find are kept as `${name}`
+    ///
+    /// ```
+    /// use maven_rs::types::Property;
+    /// let property: Property = "${user.home}/repository".parse().unwrap();
+    /// let value = property.resolve(|name| (name == "user.home").then(|| "/home/user".to_owned()));
+    /// assert_eq!(value, "/home/user/repository");
+    /// ```
+    pub fn resolve<F>(&self, mut lookup: F) -> String
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut output = String::new();
+        // Not strict. So it can not fail
+        let _ = self.resolve_into(&mut lookup, false, &mut output);
+        output
+    }
+    /// The same as [Property::resolve] but returns an error for the first variable that can not be resolved
+    pub fn try_resolve<F>(&self, mut lookup: F) -> Result<String, UnresolvedVariableError>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut output = String::new();
+        self.resolve_into(&mut lookup, true, &mut output)?;
+        Ok(output)
+    }
+    fn resolve_into(
+        &self,
+        lookup: &mut dyn FnMut(&str) -> Option<String>,
+        strict: bool,
+        output: &mut String,
+    ) -> Result<(), UnresolvedVariableError> {
+        match self {
+            Property::Variable(name) => match lookup(name) {
+                Some(value) => output.push_str(&value),
+                None if strict => return Err(UnresolvedVariableError(name.clone())),
+                None => output.push_str(&self.to_string()),
+            },
+            Property::UnclosedVariable(_) | Property::Literal(_) => {
+                output.push_str(&self.to_string())
+            }
+            Property::Expression(parts) => {
+                for part in parts {
+                    part.resolve_into(lookup, strict, output)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
+/// Returned by [Property::try_resolve]
+#[derive(Debug, Error)]
+#[error("Unresolved variable ${{{0}}}")]
+pub struct UnresolvedVariableError(pub String);
 
 impl TryFrom<String> for Property {
     type Error = ParseErrorExt<String, winnow::error::ContextError>;
@@ -149,6 +212,14 @@ mod tests {
         assert!(!value.is_maven_variable());
     }
     #[test]
+    fn resolve() {
+        let value: Property = "${env.HOME}/${missing}".parse().unwrap();
+        let lookup = |name: &str| (name == "env.HOME").then(|| "/home/user".to_owned());
+        assert_eq!(value.resolve(lookup), "/home/user/${missing}");
+        let error = value.try_resolve(lookup).unwrap_err();
+        assert_eq!(error.0, "missing");
+    }
+    #[test]
     fn test_unclosed_var() {
         let result = "${var".parse::<Property>();
         assert!(result.is_err())
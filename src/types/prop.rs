@@ -68,8 +68,9 @@ fn parse_unclosed_var<'i>(input: &mut Input<'i, '_>) -> ModalResult<&'i str> {
         .parse_next(input)
 }
 
+/// Everything up to the next `${`. A `$` that does not start a variable is part of the literal
 fn parse_literal<'i>(input: &mut Input<'i, '_>) -> ModalResult<&'i str> {
-    preceded(not(eof), parse_rest)
+    preceded(not(eof), alt((take_until(1.., "${"), rest)))
         .context(Label("literal"))
         .parse_next(input)
 }
@@ -89,7 +90,7 @@ fn parse_var_prefix<'i>(input: &mut Input<'i, '_>) -> ModalResult<&'i str> {
 
 fn parse_var_value<'i>(input: &mut Input<'i, '_>) -> ModalResult<&'i str> {
     take_while(0.., |c: char| {
-        c.is_space() || c.is_alphanumeric() || c == '.' || c == '-'
+        c.is_space() || c.is_alphanumeric() || c == '.' || c == '-' || c == '_'
     })
     .context(Label("var_value"))
     .parse_next(input)
@@ -233,6 +234,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dollar_in_literal() {
+        let parsed = ParseState::default()
+            .parse("pa$$word-${env.MY_PASSWORD}$")
+            .unwrap();
+        assert_eq!(
+            parsed,
+            Property::Expression(vec![
+                Property::Literal("pa$$word-".into()),
+                Property::Variable("env.MY_PASSWORD".into()),
+                Property::Literal("$".into()),
+            ])
+        );
+    }
+
     #[test]
     fn fuzz() {
         let rand = &mut rand::rngs::ThreadRng::default();
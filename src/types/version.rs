@@ -0,0 +1,490 @@
+use std::{cmp::Ordering, convert::Infallible, fmt::Display, hash::Hash, str::FromStr};
+
+use crate::{
+    editor::{InvalidValueError, PomValue},
+    utils::serde_utils::serde_via_string_types,
+};
+
+/// Qualifiers in the order Maven sorts them. An empty qualifier is a release.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+/// The index of the release qualifier in [QUALIFIERS]
+const RELEASE_INDEX: usize = 5;
+/// Qualifiers Maven sorts as unknown but are commonly used for unstable versions.
+const UNSTABLE_QUALIFIERS: [&str; 4] = ["ea", "preview", "pre", "dev"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    /// Digits with leading zeros removed. Empty is zero
+    Int(String),
+    /// A qualifier with aliases resolved
+    Str(String),
+    List(Vec<Item>),
+}
+impl Item {
+    fn int(value: &str) -> Self {
+        Item::Int(value.trim_start_matches('0').to_owned())
+    }
+    fn string(value: &str, followed_by_digit: bool) -> Self {
+        let value = if followed_by_digit && value.len() == 1 {
+            match value {
+                "a" => "alpha",
+                "b" => "beta",
+                "m" => "milestone",
+                other => other,
+            }
+        } else {
+            value
+        };
+        let value = match value {
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            other => other,
+        };
+        Item::Str(value.to_owned())
+    }
+    fn is_null(&self) -> bool {
+        match self {
+            Item::Int(value) | Item::Str(value) => value.is_empty(),
+            Item::List(items) => items.is_empty(),
+        }
+    }
+    /// Maven compares qualifiers as strings. Known qualifiers become their index. Unknown ones are sorted after all known ones.
+    fn comparable_qualifier(value: &str) -> String {
+        match QUALIFIERS.iter().position(|x| *x == value) {
+            Some(index) => index.to_string(),
+            None => format!("{}-{}", QUALIFIERS.len(), value),
+        }
+    }
+    fn compare(&self, other: Option<&Item>) -> Ordering {
+        match (self, other) {
+            (Item::Int(value), None) => {
+                if value.is_empty() {
+                    Ordering::Equal
+                } else {
+                    Ordering::Greater
+                }
+            }
+            (Item::Int(left), Some(Item::Int(right))) => left
+                .len()
+                .cmp(&right.len())
+                .then_with(|| left.as_str().cmp(right.as_str())),
+            (Item::Int(_), Some(_)) => Ordering::Greater,
+
+            (Item::Str(value), None) => Self::comparable_qualifier(value)
+                .as_str()
+                .cmp(RELEASE_INDEX.to_string().as_str()),
+            (Item::Str(_), Some(Item::Int(_))) => Ordering::Less,
+            (Item::Str(left), Some(Item::Str(right))) => {
+                Self::comparable_qualifier(left).cmp(&Self::comparable_qualifier(right))
+            }
+            (Item::Str(_), Some(Item::List(_))) => Ordering::Less,
+
+            (Item::List(items), None) => match items.first() {
+                Some(first) => first.compare(None),
+                None => Ordering::Equal,
+            },
+            (Item::List(_), Some(Item::Int(_))) => Ordering::Less,
+            (Item::List(_), Some(Item::Str(_))) => Ordering::Greater,
+            (Item::List(left), Some(Item::List(right))) => compare_lists(left, right),
+        }
+    }
+    fn is_unstable(&self) -> bool {
+        match self {
+            Item::Int(_) => false,
+            Item::Str(value) => {
+                let is_known_pre_release = QUALIFIERS
+                    .iter()
+                    .position(|x| x == value)
+                    .is_some_and(|index| index < RELEASE_INDEX);
+                is_known_pre_release || UNSTABLE_QUALIFIERS.contains(&value.as_str())
+            }
+            Item::List(items) => items.iter().any(Item::is_unstable),
+        }
+    }
+}
+fn compare_lists(left: &[Item], right: &[Item]) -> Ordering {
+    let mut left = left.iter();
+    let mut right = right.iter();
+    loop {
+        let result = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(right)) => right.compare(None).reverse(),
+            (Some(left), right) => left.compare(right),
+        };
+        if result != Ordering::Equal {
+            return result;
+        }
+    }
+}
+/// Removes null items from the end of the list. Skipping over any lists.
+fn normalize(items: &mut Vec<Item>) {
+    for index in (0..items.len()).rev() {
+        if items[index].is_null() {
+            items.remove(index);
+        } else if !matches!(items[index], Item::List(_)) {
+            break;
+        }
+    }
+}
+fn parse_item(is_digit: bool, value: &str) -> Item {
+    if is_digit {
+        Item::int(value)
+    } else {
+        Item::string(value, false)
+    }
+}
+
+/// A version compared the same way Maven compares versions.
+///
+/// Follows the rules of Maven's `ComparableVersion`.
+/// - Versions are split on `.`, `-` and transitions between digits and letters.
+/// - Numbers are compared numerically.
+/// - Qualifiers are ordered `alpha < beta < milestone < rc < snapshot < release < sp`. Unknown qualifiers are after all known ones and compared alphabetically.
+/// - Trailing zeros and release qualifiers (`ga`, `final`, `release`) are ignored. So `1`, `1.0` and `1.0.0-ga` are equal
+///
+/// ```rust
+/// use maven_rs::types::MavenVersion;
+/// let mut versions: Vec<MavenVersion> = ["1.10", "1.2", "1.2-SNAPSHOT", "1.2-rc1", "1.2-sp1"]
+///     .into_iter()
+///     .map(MavenVersion::from)
+///     .collect();
+/// versions.sort();
+/// let sorted: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+/// assert_eq!(sorted, vec!["1.2-rc1", "1.2-SNAPSHOT", "1.2", "1.2-sp1", "1.10"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    original: String,
+    items: Vec<Item>,
+}
+impl MavenVersion {
+    pub fn new(version: impl Into<String>) -> Self {
+        let original = version.into();
+        let items = Self::parse_items(&original);
+        Self { original, items }
+    }
+    fn parse_items(version: &str) -> Vec<Item> {
+        let version = version.trim().to_lowercase();
+        // Each list is a child of the list before it.
+        let mut stack: Vec<Vec<Item>> = vec![vec![]];
+        let mut is_digit = false;
+        let mut start_index = 0;
+        for (index, c) in version.char_indices() {
+            let list = stack.last_mut().expect("Stack is never empty");
+            if c == '.' {
+                if index == start_index {
+                    list.push(Item::Int(String::new()));
+                } else {
+                    list.push(parse_item(is_digit, &version[start_index..index]));
+                }
+                start_index = index + 1;
+            } else if c == '-' {
+                if index == start_index {
+                    list.push(Item::Int(String::new()));
+                } else {
+                    list.push(parse_item(is_digit, &version[start_index..index]));
+                }
+                start_index = index + 1;
+                stack.push(vec![]);
+            } else if c.is_ascii_digit() {
+                if !is_digit && index > start_index {
+                    list.push(Item::string(&version[start_index..index], true));
+                    start_index = index;
+                    stack.push(vec![]);
+                }
+                is_digit = true;
+            } else {
+                if is_digit && index > start_index {
+                    list.push(parse_item(true, &version[start_index..index]));
+                    start_index = index;
+                    stack.push(vec![]);
+                }
+                is_digit = false;
+            }
+        }
+        if version.len() > start_index {
+            let list = stack.last_mut().expect("Stack is never empty");
+            list.push(parse_item(is_digit, &version[start_index..]));
+        }
+        // Fold the stack back into a tree. Normalizing each list on the way out.
+        let mut current = stack.pop().expect("Stack is never empty");
+        normalize(&mut current);
+        while let Some(mut parent) = stack.pop() {
+            parent.push(Item::List(current));
+            normalize(&mut parent);
+            current = parent;
+        }
+        current
+    }
+    /// The version as it was written
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+    /// Checks if the version is a snapshot. Either ending with `SNAPSHOT` or a timestamped snapshot such as `1.0-20220914.170440-5`
+    pub fn is_snapshot(&self) -> bool {
+        is_snapshot_version(&self.original)
+    }
+    /// Checks if the version has a qualifier that is before a release. (alpha, beta, milestone, rc, snapshot)
+    ///
+    /// Also includes common unstable qualifiers Maven does not know about (ea, preview, pre, dev)
+    pub fn is_pre_release(&self) -> bool {
+        self.is_snapshot() || self.items.iter().any(Item::is_unstable)
+    }
+    /// The leading numeric segments of the version
+    ///
+    /// `1.2.3-rc1` returns `[1, 2, 3]`
+    pub fn numeric_segments(&self) -> Vec<u64> {
+        self.items
+            .iter()
+            .map_while(|item| match item {
+                Item::Int(value) if value.is_empty() => Some(0),
+                Item::Int(value) => value.parse().ok(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+/// Checks if the version is a snapshot. Either ending with `SNAPSHOT` or a timestamped snapshot such as `1.0-20220914.170440-5`
+pub fn is_snapshot_version(version: &str) -> bool {
+    if version.ends_with("SNAPSHOT") {
+        return true;
+    }
+    // {base}-{yyyyMMdd.HHmmss}-{buildNumber}
+    let mut parts = version.rsplitn(3, '-');
+    let (Some(build_number), Some(timestamp), Some(_)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let is_timestamp = timestamp.len() == 15
+        && timestamp.char_indices().all(|(index, c)| {
+            if index == 8 {
+                c == '.'
+            } else {
+                c.is_ascii_digit()
+            }
+        });
+    is_timestamp && !build_number.is_empty() && build_number.chars().all(|c| c.is_ascii_digit())
+}
+/// The version that is released from a snapshot. `1.2.3-SNAPSHOT` returns `1.2.3`
+///
+/// Versions that are not a `-SNAPSHOT` are returned unchanged
+pub fn release_version(version: &str) -> String {
+    version
+        .strip_suffix("-SNAPSHOT")
+        .unwrap_or(version)
+        .to_owned()
+}
+/// The snapshot version to continue development on after releasing `version`
+///
+/// The last number of the version is incremented the same way the maven-release-plugin does.
+/// ```rust
+/// use maven_rs::types::next_development_version;
+/// assert_eq!(next_development_version("1.2.3"), "1.2.4-SNAPSHOT");
+/// assert_eq!(next_development_version("1.2.3-SNAPSHOT"), "1.2.4-SNAPSHOT");
+/// assert_eq!(next_development_version("2.0-beta-1"), "2.0-beta-2-SNAPSHOT");
+/// ```
+pub fn next_development_version(version: &str) -> String {
+    let release = release_version(version);
+    let Some(end) = release
+        .rfind(|c: char| c.is_ascii_digit())
+        .map(|index| index + 1)
+    else {
+        return format!("{release}.1-SNAPSHOT");
+    };
+    let start = release[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let digits = &release[start..end];
+    let next = match digits.parse::<u64>() {
+        // Keep leading zeros. `1.09` becomes `1.10`
+        Ok(number) => format!("{:0width$}", number + 1, width = digits.len()),
+        Err(_) => format!("{digits}1"),
+    };
+    format!("{}{next}{}-SNAPSHOT", &release[..start], &release[end..])
+}
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for MavenVersion {}
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_lists(&self.items, &other.items)
+    }
+}
+impl Hash for MavenVersion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // Equal versions normalize to the same items
+        format!("{:?}", self.items).hash(state);
+    }
+}
+impl Display for MavenVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.original)
+    }
+}
+impl FromStr for MavenVersion {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+impl From<&str> for MavenVersion {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+impl From<String> for MavenVersion {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+impl AsRef<str> for MavenVersion {
+    fn as_ref(&self) -> &str {
+        &self.original
+    }
+}
+impl PomValue for MavenVersion {
+    fn from_str_for_editor(value: &str) -> Result<Self, InvalidValueError> {
+        Ok(Self::new(value))
+    }
+
+    fn to_string_for_editor(&self) -> String {
+        self.original.clone()
+    }
+}
+serde_via_string_types!(MavenVersion);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn assert_order(versions: &[&str]) {
+        for window in versions.windows(2) {
+            let low = MavenVersion::from(window[0]);
+            let high = MavenVersion::from(window[1]);
+            assert!(low < high, "Expected {} < {}", low, high);
+            assert!(high > low, "Expected {} > {}", high, low);
+        }
+    }
+    fn assert_equal(versions: &[&str]) {
+        for left in versions {
+            for right in versions {
+                assert_eq!(
+                    MavenVersion::from(*left),
+                    MavenVersion::from(*right),
+                    "Expected {} == {}",
+                    left,
+                    right
+                );
+            }
+        }
+    }
+    #[test]
+    fn qualifier_order() {
+        assert_order(&[
+            "1-alpha2snapshot",
+            "1-alpha2",
+            "1-alpha-123",
+            "1-beta-2",
+            "1-beta123",
+            "1-m2",
+            "1-m11",
+            "1-rc",
+            "1-cr2",
+            "1-rc123",
+            "1-SNAPSHOT",
+            "1",
+            "1-sp",
+            "1-sp2",
+            "1-sp123",
+            "1-abc",
+            "1-def",
+            "1-pom-1",
+            "1-1-snapshot",
+            "1-1",
+            "1-2",
+            "1-123",
+        ]);
+    }
+    #[test]
+    fn number_order() {
+        assert_order(&[
+            "2.0", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-1", "2.1.0.1", "2.2", "2.123", "11.a2",
+            "11.a11", "11.b2", "11.b11", "11.m2", "11.m11", "11",
+        ]);
+    }
+    #[test]
+    fn equality() {
+        assert_equal(&[
+            "1",
+            "1.0",
+            "1.0.0",
+            "1-ga",
+            "1-final",
+            "1-release",
+            "1.0-GA",
+        ]);
+        assert_equal(&["1-cr1", "1-rc1", "1-RC1"]);
+        assert_equal(&["1a1", "1-a1", "1-alpha-1", "1alpha1"]);
+    }
+    #[test]
+    fn pre_release_and_snapshot() {
+        assert!(MavenVersion::from("1.0-SNAPSHOT").is_snapshot());
+        assert!(MavenVersion::from("1.0-20220914.170440-5").is_snapshot());
+        assert!(!MavenVersion::from("1.0-20220914").is_snapshot());
+        for version in [
+            "1.0-alpha1",
+            "1.0-b2",
+            "1.0-M3",
+            "2.0-rc1",
+            "21-ea",
+            "1.0-SNAPSHOT",
+        ] {
+            assert!(
+                MavenVersion::from(version).is_pre_release(),
+                "{} should be a pre-release",
+                version
+            );
+        }
+        for version in ["1.0", "1.0-jre", "1.0-sp1", "1.0.Final", "30.1-android"] {
+            assert!(
+                !MavenVersion::from(version).is_pre_release(),
+                "{} should not be a pre-release",
+                version
+            );
+        }
+    }
+    #[test]
+    fn numeric_segments() {
+        assert_eq!(
+            MavenVersion::from("1.2.3-rc1").numeric_segments(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(MavenVersion::from("33.0-jre").numeric_segments(), vec![33]);
+    }
+    #[test]
+    fn development_versions() {
+        assert_eq!(release_version("1.0.0-SNAPSHOT"), "1.0.0");
+        assert_eq!(release_version("1.0.0"), "1.0.0");
+        for (version, next) in [
+            ("1.0.0-SNAPSHOT", "1.0.1-SNAPSHOT"),
+            ("1.09", "1.10-SNAPSHOT"),
+            ("2.0-beta", "2.1-beta-SNAPSHOT"),
+            ("33.0.0-jre", "33.0.1-jre-SNAPSHOT"),
+            ("release", "release.1-SNAPSHOT"),
+        ] {
+            assert_eq!(next_development_version(version), next, "{version}");
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{
+    editor::{InvalidValueError, PomValue},
+    utils::serde_utils::serde_via_string_types,
+};
+
+use super::MavenVersion;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum VersionRangeError {
+    #[error("Unbounded range: {0}")]
+    Unbounded(String),
+    #[error("Single version must be surrounded by []: {0}")]
+    SingleVersionNotInclusive(String),
+    #[error("Range defies version ordering: {0}")]
+    DefiesOrdering(String),
+    #[error("Ranges overlap: {0}")]
+    Overlapping(String),
+    #[error("Only fully-qualified sets allowed in multiple set scenario: {0}")]
+    NotFullyQualified(String),
+}
+/// A single set of a [VersionRange]
+///
+/// `None` for a bound means it is unbounded on that side
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Restriction {
+    pub lower: Option<MavenVersion>,
+    pub lower_inclusive: bool,
+    pub upper: Option<MavenVersion>,
+    pub upper_inclusive: bool,
+}
+impl Restriction {
+    /// A restriction that contains every version
+    pub fn everything() -> Self {
+        Self {
+            lower: None,
+            lower_inclusive: false,
+            upper: None,
+            upper_inclusive: false,
+        }
+    }
+    /// A restriction that only contains the given version. `[1.0]`
+    pub fn exact(version: MavenVersion) -> Self {
+        Self {
+            lower: Some(version.clone()),
+            lower_inclusive: true,
+            upper: Some(version),
+            upper_inclusive: true,
+        }
+    }
+    pub fn contains(&self, version: &MavenVersion) -> bool {
+        if let Some(lower) = &self.lower {
+            match lower.cmp(version) {
+                std::cmp::Ordering::Greater => return false,
+                std::cmp::Ordering::Equal if !self.lower_inclusive => return false,
+                _ => {}
+            }
+        }
+        if let Some(upper) = &self.upper {
+            match upper.cmp(version) {
+                std::cmp::Ordering::Less => return false,
+                std::cmp::Ordering::Equal if !self.upper_inclusive => return false,
+                _ => {}
+            }
+        }
+        true
+    }
+    fn parse(spec: &str) -> Result<Self, VersionRangeError> {
+        let lower_inclusive = spec.starts_with('[');
+        let upper_inclusive = spec.ends_with(']');
+        let inner = spec[1..spec.len() - 1].trim();
+        let Some((lower, upper)) = inner.split_once(',') else {
+            if !lower_inclusive || !upper_inclusive {
+                return Err(VersionRangeError::SingleVersionNotInclusive(
+                    spec.to_owned(),
+                ));
+            }
+            return Ok(Self::exact(MavenVersion::new(inner)));
+        };
+        let lower = Some(lower.trim())
+            .filter(|value| !value.is_empty())
+            .map(MavenVersion::new);
+        let upper = Some(upper.trim())
+            .filter(|value| !value.is_empty())
+            .map(MavenVersion::new);
+        if let (Some(lower), Some(upper)) = (&lower, &upper)
+            && upper < lower
+        {
+            return Err(VersionRangeError::DefiesOrdering(spec.to_owned()));
+        }
+        Ok(Self {
+            lower,
+            lower_inclusive,
+            upper,
+            upper_inclusive,
+        })
+    }
+}
+impl Display for Restriction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let (true, true, Some(lower), Some(upper)) = (
+            self.lower_inclusive,
+            self.upper_inclusive,
+            &self.lower,
+            &self.upper,
+        ) && lower == upper
+        {
+            return write!(f, "[{}]", lower);
+        }
+        write!(f, "{}", if self.lower_inclusive { '[' } else { '(' })?;
+        if let Some(lower) = &self.lower {
+            write!(f, "{}", lower)?;
+        }
+        write!(f, ",")?;
+        if let Some(upper) = &self.upper {
+            write!(f, "{}", upper)?;
+        }
+        write!(f, "{}", if self.upper_inclusive { ']' } else { ')' })
+    }
+}
+/// A Maven version range.
+///
+/// | Range | Meaning |
+/// |---|---|
+/// | `1.0` | Any version. `1.0` is recommended |
+/// | `[1.0]` | Exactly `1.0` |
+/// | `(,1.0]` | x <= 1.0 |
+/// | `[1.2,1.3]` | 1.2 <= x <= 1.3 |
+/// | `[1.0,2.0)` | 1.0 <= x < 2.0 |
+/// | `[1.5,)` | x >= 1.5 |
+/// | `(,1.0],[1.2,)` | x <= 1.0 or x >= 1.2 |
+///
+/// ```rust
+/// use maven_rs::types::{MavenVersion, VersionRange};
+/// let range: VersionRange = "[1.0,2.0)".parse().unwrap();
+/// assert!(range.contains(&MavenVersion::from("1.5")));
+/// assert!(!range.contains(&MavenVersion::from("2.0")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionRange {
+    /// The version used if the range is a plain version
+    pub recommended: Option<MavenVersion>,
+    pub restrictions: Vec<Restriction>,
+}
+impl VersionRange {
+    pub fn contains(&self, version: &MavenVersion) -> bool {
+        self.restrictions
+            .iter()
+            .any(|restriction| restriction.contains(version))
+    }
+    /// Finds the highest version in the range
+    pub fn highest_match<'a, I>(&self, versions: I) -> Option<&'a MavenVersion>
+    where
+        I: IntoIterator<Item = &'a MavenVersion>,
+    {
+        versions
+            .into_iter()
+            .filter(|version| self.contains(version))
+            .max()
+    }
+}
+impl FromStr for VersionRange {
+    type Err = VersionRangeError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut process = spec.trim();
+        let mut restrictions: Vec<Restriction> = Vec::new();
+        while process.starts_with('[') || process.starts_with('(') {
+            let Some(index) = process.find([')', ']']) else {
+                return Err(VersionRangeError::Unbounded(spec.to_owned()));
+            };
+            let restriction = Restriction::parse(&process[..=index])?;
+            if let Some(previous) = restrictions.last() {
+                let overlaps = match (&previous.upper, &restriction.lower) {
+                    (Some(upper), Some(lower)) => lower < upper,
+                    _ => true,
+                };
+                if overlaps {
+                    return Err(VersionRangeError::Overlapping(spec.to_owned()));
+                }
+            }
+            restrictions.push(restriction);
+            process = process[index + 1..].trim();
+            if let Some(rest) = process.strip_prefix(',') {
+                process = rest.trim();
+            }
+        }
+        if process.is_empty() {
+            return Ok(Self {
+                recommended: None,
+                restrictions,
+            });
+        }
+        if !restrictions.is_empty() {
+            return Err(VersionRangeError::NotFullyQualified(spec.to_owned()));
+        }
+        Ok(Self {
+            recommended: Some(MavenVersion::new(process)),
+            restrictions: vec![Restriction::everything()],
+        })
+    }
+}
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(recommended) = &self.recommended {
+            return write!(f, "{}", recommended);
+        }
+        for (index, restriction) in self.restrictions.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", restriction)?;
+        }
+        Ok(())
+    }
+}
+impl PomValue for VersionRange {
+    fn from_str_for_editor(value: &str) -> Result<Self, InvalidValueError> {
+        Self::from_str(value).map_err(|err| InvalidValueError::InvalidFormattedValue {
+            error: err.to_string(),
+        })
+    }
+
+    fn to_string_for_editor(&self) -> String {
+        self.to_string()
+    }
+}
+serde_via_string_types!(VersionRange);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn contains(range: &str, version: &str) -> bool {
+        range
+            .parse::<VersionRange>()
+            .unwrap()
+            .contains(&MavenVersion::from(version))
+    }
+    #[test]
+    fn parse_and_contains() {
+        assert!(contains("[1.0]", "1.0"));
+        assert!(!contains("[1.0]", "1.1"));
+        assert!(contains("(,1.0]", "0.9"));
+        assert!(contains("(,1.0]", "1.0"));
+        assert!(!contains("(,1.0)", "1.0"));
+        assert!(contains("[1.0,2.0)", "1.9.9"));
+        assert!(!contains("[1.0,2.0)", "2.0"));
+        assert!(!contains("[1.0,2.0)", "0.9"));
+        assert!(contains("[1.5,)", "10"));
+        assert!(contains("(,1.0],[1.2,)", "1.3"));
+        assert!(!contains("(,1.0],[1.2,)", "1.1"));
+        // A plain version is only a recommendation
+        assert!(contains("1.0", "5.0"));
+    }
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "[1.0,2.0".parse::<VersionRange>(),
+            Err(VersionRangeError::Unbounded("[1.0,2.0".to_owned()))
+        );
+        assert!(matches!(
+            "(1.0)".parse::<VersionRange>(),
+            Err(VersionRangeError::SingleVersionNotInclusive(_))
+        ));
+        assert!(matches!(
+            "[2.0,1.0]".parse::<VersionRange>(),
+            Err(VersionRangeError::DefiesOrdering(_))
+        ));
+        assert!(matches!(
+            "[1.0,2.0],[1.5,3.0]".parse::<VersionRange>(),
+            Err(VersionRangeError::Overlapping(_))
+        ));
+        assert!(matches!(
+            "[1.0,2.0],3.0".parse::<VersionRange>(),
+            Err(VersionRangeError::NotFullyQualified(_))
+        ));
+    }
+    #[test]
+    fn display_round_trip() {
+        for range in [
+            "[1.0]",
+            "(,1.0]",
+            "[1.0,2.0)",
+            "[1.5,)",
+            "(,1.0],[1.2,)",
+            "1.0",
+        ] {
+            assert_eq!(range.parse::<VersionRange>().unwrap().to_string(), range);
+        }
+    }
+}
@@ -95,3 +95,37 @@ pub mod bug_testing {
             .join("bugs")
     }
 }
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use std::{
+        ops::Deref,
+        path::{Path, PathBuf},
+    };
+
+    /// A directory in the temp directory that is removed when it is dropped. Even if the test panics
+    pub struct TempDirectory(PathBuf);
+    impl TempDirectory {
+        /// Creates `maven-rs-{name}-{process id}`
+        pub fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("maven-rs-{name}-{}", std::process::id()));
+            std::fs::create_dir_all(&path).expect("Failed to create the temp directory");
+            Self(path)
+        }
+    }
+    impl Deref for TempDirectory {
+        type Target = Path;
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+    impl AsRef<Path> for TempDirectory {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+    impl Drop for TempDirectory {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+}
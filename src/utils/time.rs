@@ -7,7 +7,16 @@ macro_rules! define_time_format {
             use chrono::NaiveDateTime;
             use serde::{Deserialize, Deserializer, Serializer};
 
-            static FORMAT: &str = $format;
+            pub static FORMAT: &str = $format;
+
+            /// Formats the date with this format.
+            pub fn format(date: &NaiveDateTime) -> String {
+                date.format(FORMAT).to_string()
+            }
+            /// Parses a date in this format.
+            pub fn parse(value: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+                NaiveDateTime::parse_from_str(value.trim(), FORMAT)
+            }
 
             pub fn serialize<S>(
                 date: &Option<NaiveDateTime>,
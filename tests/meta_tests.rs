@@ -46,3 +46,47 @@ pub fn test_snapshot() {
 
     println!("{:#?}", snapshot_meta);
 }
+#[test]
+pub fn test_deploy_onto_existing_snapshot() -> anyhow::Result<()> {
+    let buf = meta_path().join("kakara-engine").join("snapshot.xml");
+    let file = std::fs::File::open(buf)?;
+    let snapshot_meta: SnapshotMetadata = quick_xml::de::from_reader(BufReader::new(file))?;
+    let deployed_at = chrono::NaiveDate::from_ymd_opt(2022, 9, 15)
+        .unwrap()
+        .and_hms_opt(8, 30, 0)
+        .unwrap();
+    let deploy = SnapshotMetadata::deploy(
+        Some(snapshot_meta),
+        "org.kakara",
+        "engine",
+        "1.0-SNAPSHOT",
+        [
+            MavenFileExtension::from("jar"),
+            MavenFileExtension::from("pom"),
+        ],
+        deployed_at,
+    )?;
+    assert_eq!(
+        deploy.files[0].file_name,
+        "engine-1.0-20220915.083000-6.jar"
+    );
+    assert_eq!(
+        deploy.files[1].file_name,
+        "engine-1.0-20220915.083000-6.pom"
+    );
+    assert_eq!(
+        deploy.metadata.get_latest_artifact_name("jar"),
+        Some("engine-1.0-20220915.083000-6.jar".to_string())
+    );
+    assert_eq!(
+        deploy
+            .metadata
+            .get_latest_artifact_name(MavenFileExtension::from("jar").with_classifier("sources")),
+        Some("engine-1.0-20220914.170440-5-sources.jar".to_string())
+    );
+    let serialized = quick_xml::se::to_string_with_root("metadata", &deploy.metadata)?;
+    println!("{}", serialized);
+    let parsed: SnapshotMetadata = quick_xml::de::from_str(&serialized)?;
+    assert_eq!(parsed.versioning.next_build_number(), 7);
+    Ok(())
+}
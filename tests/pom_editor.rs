@@ -36,3 +36,48 @@ pub fn test_read_test_pom() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+pub fn keep_test_poms_unchanged() -> anyhow::Result<()> {
+    let test_poms = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("test_poms");
+    for name in ["test-pom.xml", "gson-2.11.0.pom", "lwjgl-bom-3.3.4.pom"] {
+        let source = std::fs::read_to_string(test_poms.join(name))?;
+        let editor = PomEditor::load_from_str(&source)?;
+        assert_eq!(editor.write_to_str()?, source, "{name} was changed");
+    }
+    let source = std::fs::read_to_string(test_poms.join("test-pom.xml"))?;
+    let editor = PomEditor::load_from_str(&source)?;
+    assert_eq!(
+        editor.get_description().as_deref(),
+        Some("An simple library that makes\n        finding annotations in classes easy")
+    );
+    Ok(())
+}
+
+#[test]
+pub fn only_edited_elements_change() -> anyhow::Result<()> {
+    let file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("test_poms")
+        .join("test-pom.xml");
+    let source = std::fs::read_to_string(file_path)?;
+    let mut editor = PomEditor::load_from_str(&source)?;
+    editor.add_or_update_dependency(Dependency {
+        group_id: "com.google.guava".to_string(),
+        artifact_id: "guava".to_string(),
+        version: Some("33.0-jre".parse().unwrap()),
+        depend_type: None,
+        scope: None,
+        classifier: None,
+    })?;
+    let expected = source.replace(
+        "            <scope>test</scope>\n        </dependency>\n    </dependencies>",
+        "            <scope>test</scope>\n        </dependency>\n        <dependency>\n            <groupId>com.google.guava</groupId>\n            <artifactId>guava</artifactId>\n            <version>33.0-jre</version>\n        </dependency>\n    </dependencies>",
+    );
+    assert_eq!(editor.write_to_str()?, expected);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use maven_rs::pom::editor::PomEditor;
+
+#[test]
+fn apply_model_loss() {
+    let src = r#"<project>
+    <artifactId>x</artifactId>
+    <dependencies>
+        <dependency>
+            <groupId>a</groupId>
+            <artifactId>b</artifactId>
+            <version>1</version>
+            <scope>test</scope>
+            <!-- keep me -->
+            <exclusions>
+                <exclusion>
+                    <groupId>c</groupId>
+                    <artifactId>d</artifactId>
+                </exclusion>
+            </exclusions>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin>
+                <artifactId>maven-compiler-plugin</artifactId>
+                <version>3.13.0</version>
+                <configuration>
+                    <release>17</release>
+                </configuration>
+                <dependencies>
+                    <dependency>
+                        <groupId>e</groupId>
+                        <artifactId>f</artifactId>
+                        <version>1</version>
+                    </dependency>
+                </dependencies>
+            </plugin>
+        </plugins>
+    </build>
+</project>
+"#;
+    let mut e = PomEditor::load_from_str(src).unwrap();
+    let mut pom = e.to_model().unwrap();
+    pom.dependencies.dependencies[0].scope = None;
+    pom.build.as_mut().unwrap().plugins.plugins[0]
+        .dependencies
+        .dependencies
+        .clear();
+    e.apply_model(&pom).unwrap();
+    println!("{}", e.write_to_str().unwrap());
+}
@@ -0,0 +1,73 @@
+use maven_rs::settings::Settings;
+
+use std::io::BufReader;
+use std::path::PathBuf;
+fn settings_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("settings")
+}
+#[test]
+pub fn test_full_settings() -> anyhow::Result<()> {
+    let file = std::fs::File::open(settings_path().join("settings.xml"))?;
+    let settings: Settings = quick_xml::de::from_reader(BufReader::new(file))?;
+
+    assert_eq!(settings.use_plugin_registry, Some(false));
+    assert_eq!(
+        settings.plugin_groups.plugin_groups,
+        vec!["org.mortbay.jetty", "dev.kingtux"]
+    );
+
+    let proxy = settings.get_active_proxy().expect("No active proxy");
+    assert_eq!(proxy.id.as_deref(), Some("corporate"));
+    assert_eq!(proxy.port, Some(3128));
+    assert_eq!(proxy.non_proxy_hosts(), vec!["*.example.com", "localhost"]);
+    assert_eq!(
+        proxy.username_and_password(),
+        Some(("proxy-user", "proxy-pass"))
+    );
+
+    let server = settings.get_server("internal").expect("No internal server");
+    let configuration = server.configuration.as_ref().expect("No configuration");
+    assert_eq!(
+        configuration.find_value("httpHeaders/property/name"),
+        Some("X-Team")
+    );
+    assert_eq!(configuration.find_value("timeout"), Some("30000"));
+    let ssh = settings.get_server("ssh-host").expect("No ssh server");
+    assert_eq!(
+        ssh.private_key_and_passphrase(),
+        Some(("/home/user/.ssh/id_ed25519", "phrase"))
+    );
+
+    let active: Vec<_> = settings
+        .active_profiles()
+        .map(|profile| profile.id.as_str())
+        .collect();
+    assert_eq!(active, vec!["internal", "defaults"]);
+    let repositories: Vec<_> = settings
+        .active_profile_repositories()
+        .map(|repository| repository.url.as_str())
+        .collect();
+    assert_eq!(repositories, vec!["https://repo.example.com/releases"]);
+    let plugin_repositories: Vec<_> = settings
+        .active_profile_plugin_repositories()
+        .map(|repository| repository.url.as_str())
+        .collect();
+    assert_eq!(
+        plugin_repositories,
+        vec!["https://repo.example.com/plugins"]
+    );
+    let defaults = settings
+        .get_profile("defaults")
+        .expect("No defaults profile");
+    assert_eq!(
+        defaults
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.get("skipTests")),
+        Some("false")
+    );
+    Ok(())
+}